@@ -1,10 +1,12 @@
 use cosmwasm_schema::write_api;
-use marketplace::msg::{InstantiateMsg, QueryMsg, ExecuteMsg};
+use marketplace::msg::{InstantiateMsg, QueryMsg, ExecuteMsg, MigrateMsg, SudoMsg};
 
 fn main() {
     write_api! {
         instantiate: InstantiateMsg,
         execute: ExecuteMsg,
         query: QueryMsg,
+        migrate: MigrateMsg,
+        sudo: SudoMsg,
     }
 }
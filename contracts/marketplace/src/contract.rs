@@ -5,10 +5,8 @@ use cw_utils::parse_reply_instantiate_data;
 use crate::contract_imports::*;
 use crate::state::ROYALTY_REGISTRY;
 
-const CONTRACT_NAME: &str = "crates.io:fuzion_market";
-const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
-
-const WEEK_IN_SECS: u64 = 604800;
+pub const CONTRACT_NAME: &str = "crates.io:fuzion_market";
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // Instantiate
@@ -18,16 +16,42 @@ const WEEK_IN_SECS: u64 = 604800;
 pub fn instantiate(
     deps: DepsMut,
     env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    OWNER.save(deps.storage, &Some(owner.clone()))?;
+    PENDING_OWNER.save(deps.storage, &None)?;
+
+    let config = msg.config.unwrap_or_default();
+    config.validate()?;
+    CONFIG.save(deps.storage, &config)?;
+
     LISTING_ID_USED.save(deps.storage, 0, &true)?;
 
     BUCKET_ID_USED.save(deps.storage, 0, &true)?;
 
-    FEE_DENOM.save(deps.storage, &FeeDenom::JUNO(env.block.time.seconds()))?;
+    let fee_routes = match msg.fee_routes {
+        Some(routes) => FeeRouteUnvalidated::validate_all(routes, &deps)?,
+        None => FeeRoute::default_routes(),
+    };
+
+    let fee_schedule = FeeSchedule::new(
+        FeeDenomUnvalidated::validate_all(msg.fee_denoms, &deps)?,
+        msg.fee_mode,
+        msg.fee_any_fungible.unwrap_or(false),
+        msg.flat_fee.map(|f| f.validate(&deps)).transpose()?,
+        config.max_flat_fee,
+        env.block.time.seconds(),
+    )?;
+    fee_schedule.check_routes(&fee_routes)?;
+    FEE_SCHEDULE.save(deps.storage, &fee_schedule)?;
+    FEE_ROUTES.save(deps.storage, &fee_routes)?;
 
     // Instantiate Royalty Registry
     let init_msg = to_binary(&RoyaltyInstantiateMsg {})?;
@@ -52,6 +76,7 @@ pub fn instantiate(
 
     Ok(Response::new()
         .add_attribute("action", "instantiate")
+        .add_attribute("owner", owner)
         .add_submessage(sub_msg)
     )
 }
@@ -67,12 +92,23 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    if let Some(version) = MIGRATION_IN_PROGRESS.may_load(deps.storage)? {
+        return Err(ContractError::MigrationInProgress(version));
+    }
+
     match msg {
         ExecuteMsg::FeeCycle {} => execute_cycle_fee(deps, env),
+        ExecuteMsg::SweepFees {
+            denoms,
+        } => execute_sweep_fees(deps, &env, denoms),
+        ExecuteMsg::ProcessExpired {
+            limit,
+        } => execute_process_expired(deps, &env, &info.sender, limit),
 
         // ~~~~ Receive Wrappers ~~~~ //
         ExecuteMsg::Receive(receive_msg) => execute_receive(deps, &env, &info, &receive_msg),
-        ExecuteMsg::ReceiveNft(receive_nft_msg) => execute_receive_nft(deps, info, receive_nft_msg),
+        ExecuteMsg::ReceiveNft(receive_nft_msg) => execute_receive_nft(deps, &env, info, receive_nft_msg),
+        ExecuteMsg::Batch(actions) => execute_batch(deps, &env, &info.sender, &info.funds, actions),
 
         // ~~~~ Listing Executions ~~~~ //
         ExecuteMsg::CreateListing {
@@ -98,7 +134,8 @@ pub fn execute(
         } => execute_finalize(deps, &env, &info.sender, listing_id, seconds),
         ExecuteMsg::DeleteListing {
             listing_id,
-        } => execute_delete_listing(deps, &env, info.sender, listing_id),
+            destination,
+        } => execute_delete_listing(deps, &env, info.sender, listing_id, destination),
 
         // ~~~~ Bucket Executions ~~~~ //
         ExecuteMsg::CreateBucket {
@@ -109,46 +146,190 @@ pub fn execute(
         } => execute_add_to_bucket(deps, Balance::from(info.funds), &info.sender, bucket_id),
         ExecuteMsg::RemoveBucket {
             bucket_id,
-        } => execute_withdraw_bucket(deps, &env, &info.sender, bucket_id),
+            destination,
+        } => execute_withdraw_bucket(deps, &info.sender, bucket_id, destination),
+        ExecuteMsg::DepositToListing {
+            listing_id,
+            cw20,
+            nfts,
+        } => execute_deposit_to_listing(
+            deps,
+            &env,
+            &info.sender,
+            &info.funds,
+            listing_id,
+            cw20,
+            nfts,
+        ),
+        ExecuteMsg::AddApprovedNfts {
+            listing_id,
+            nfts,
+        } => execute_add_approved_nfts(deps, &env, &info.sender, listing_id, nfts),
+        ExecuteMsg::DepositToBucket {
+            bucket_id,
+            cw20,
+            nfts,
+        } => execute_deposit_to_bucket(
+            deps,
+            &env,
+            &info.sender,
+            &info.funds,
+            bucket_id,
+            cw20,
+            nfts,
+        ),
 
         // ~~~~ Marketplace Executions ~~~~ //
         ExecuteMsg::BuyListing {
             listing_id,
             bucket_id,
-        } => execute_buy_listing(deps, &env, &info.sender, listing_id, bucket_id),
+            quantity,
+        } => execute_buy_listing(deps, &env, &info.sender, &info.funds, listing_id, bucket_id, quantity),
         ExecuteMsg::WithdrawPurchased {
             listing_id,
-        } => execute_withdraw_purchased(deps, &env, &info.sender, listing_id),
+            destination,
+        } => execute_withdraw_purchased(deps, &info.sender, listing_id, destination),
+        ExecuteMsg::BuyListingWithFunds {
+            listing_id,
+        } => execute_buy_listing_with_funds(
+            deps,
+            &env,
+            &info.sender,
+            &Balance::from(info.funds),
+            listing_id,
+        ),
+        ExecuteMsg::BuyListingWithAllowances {
+            listing_id,
+        } => execute_buy_listing_with_allowances(deps, &env, &info.sender, &info.funds, listing_id),
+        ExecuteMsg::MakeOffer {
+            listing_id,
+            bucket_id,
+            seconds,
+        } => execute_make_offer(deps, &env, &info.sender, listing_id, bucket_id, seconds),
+        ExecuteMsg::RetractOffer {
+            bucket_id,
+        } => execute_retract_offer(deps, &info.sender, bucket_id),
+        ExecuteMsg::AcceptOffer {
+            listing_id,
+            bucket_id,
+        } => execute_accept_offer(deps, &env, &info.sender, listing_id, bucket_id),
+        ExecuteMsg::CreateCollectionOffer {
+            offer_id,
+            collection,
+            quantity,
+            seconds,
+        } => execute_create_collection_offer(
+            deps,
+            &env,
+            &info.sender,
+            &Balance::from(info.funds),
+            offer_id,
+            &collection,
+            quantity,
+            seconds,
+        ),
+        ExecuteMsg::CancelCollectionOffer {
+            offer_id,
+        } => execute_cancel_collection_offer(deps, &info.sender, offer_id),
+        ExecuteMsg::PlaceBid {
+            listing_id,
+        } => execute_place_bid(deps, &env, &info.sender, &Balance::from(info.funds), listing_id),
+        ExecuteMsg::CommitBid {
+            listing_id,
+            commitment,
+        } => execute_commit_bid(
+            deps,
+            &env,
+            &info.sender,
+            &Balance::from(info.funds),
+            listing_id,
+            commitment,
+        ),
+        ExecuteMsg::RevealBid {
+            listing_id,
+            amount,
+            salt,
+        } => execute_reveal_bid(deps, &env, &info.sender, listing_id, amount, &salt),
+        ExecuteMsg::ClaimDeposit {
+            listing_id,
+            bidder,
+        } => execute_claim_deposit(deps, &env, &info.sender, listing_id, bidder),
+        ExecuteMsg::SettleAuction {
+            listing_id,
+        } => execute_settle_auction(deps, &env, listing_id),
+
+        // ~~~~ Ownership Executions ~~~~ //
+        ExecuteMsg::ProposeOwner {
+            new_owner,
+        } => execute_propose_owner(deps, &info.sender, &new_owner),
+        ExecuteMsg::AcceptOwnership {} => execute_accept_ownership(deps, &info.sender),
+        ExecuteMsg::RenounceOwnership {} => execute_renounce_ownership(deps, &info.sender),
+
+        // ~~~~ Config Executions ~~~~ //
+        ExecuteMsg::UpdateFeeSchedule {
+            denoms,
+            mode,
+            any_fungible,
+            flat_fee,
+        } => execute_update_fee_schedule(deps, &env, &info.sender, denoms, mode, any_fungible, flat_fee),
+        ExecuteMsg::UpdateFeeRoutes {
+            routes,
+        } => execute_update_fee_routes(deps, &info.sender, routes),
+        ExecuteMsg::UpdateConfig {
+            min_expiration_seconds,
+            max_expiration_seconds,
+            max_num_assets,
+            max_royalty_bps,
+            fee_bps,
+            fee_cycle_seconds,
+            expiry_bounty,
+            max_flat_fee,
+            max_sealed_bids,
+        } => execute_update_config(
+            deps,
+            &info.sender,
+            min_expiration_seconds,
+            max_expiration_seconds,
+            max_num_assets,
+            max_royalty_bps,
+            fee_bps,
+            fee_cycle_seconds,
+            expiry_bounty,
+            max_flat_fee,
+            max_sealed_bids,
+        ),
     }
 }
 
 /// Anyone can call this, but it will only take effect
-/// if WEEK_IN_SECS has passed since last cycle
+/// if `Config.fee_cycle_seconds` has passed since last cycle
+/// - Errors if the fee schedule does not rotate
 pub fn execute_cycle_fee(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
-    let (updatable, new) = match FEE_DENOM.load(deps.storage)? {
-        FeeDenom::JUNO(last) => {
-            (last.saturating_add(WEEK_IN_SECS), FeeDenom::USDC(env.block.time.seconds()))
-        }
-        FeeDenom::USDC(lastx) => {
-            (lastx.saturating_add(WEEK_IN_SECS), FeeDenom::JUNO(env.block.time.seconds()))
-        }
+    let cycle_seconds = CONFIG.load(deps.storage)?.fee_cycle_seconds;
+    let mut fee_schedule = FEE_SCHEDULE.load(deps.storage)?;
+
+    let Some(updatable) = fee_schedule.next_rotation(cycle_seconds) else {
+        return Err(ContractError::GenericError("Fee schedule does not rotate".to_string()));
     };
 
-    // if current block is <= updatable Error (Cycle every week)
+    // if current block is <= updatable Error (Cycle every fee_cycle_seconds)
     if env.block.time.seconds() <= updatable {
         return Err(ContractError::GenericError("FeeDenom not yet ready to cycle".to_string()));
     };
 
     // Ready to cycle
-    FEE_DENOM.save(deps.storage, &new)?;
+    fee_schedule.rotate(env.block.time.seconds());
+    FEE_SCHEDULE.save(deps.storage, &fee_schedule)?;
 
-    Ok(Response::new().add_attribute("Cycle", "Fee"))
+    let new_denom = fee_schedule.current().map(|d| d.asset.key().to_string()).unwrap_or_default();
+
+    Ok(Response::new().add_attribute("Cycle", "Fee").add_attribute("fee_denom", new_denom))
 }
 
 // CW20 Filter
 pub fn execute_receive(
     deps: DepsMut,
-    _env: &Env,
+    env: &Env,
     info: &MessageInfo,
     wrapper: &Cw20ReceiveMsg,
 ) -> Result<Response, ContractError> {
@@ -185,12 +366,38 @@ pub fn execute_receive(
         ReceiveMsg::AddToBucketCw20 {
             bucket_id,
         } => execute_add_to_bucket(deps, balance, &user_wallet, bucket_id),
+        ReceiveMsg::CreateCollectionOfferCw20 {
+            offer_id,
+            collection,
+            quantity,
+            seconds,
+        } => execute_create_collection_offer(
+            deps,
+            env,
+            &user_wallet,
+            &balance,
+            offer_id,
+            &collection,
+            quantity,
+            seconds,
+        ),
+        ReceiveMsg::PlaceBidCw20 {
+            listing_id,
+        } => execute_place_bid(deps, env, &user_wallet, &balance, listing_id),
+        ReceiveMsg::CommitBidCw20 {
+            listing_id,
+            commitment,
+        } => execute_commit_bid(deps, env, &user_wallet, &balance, listing_id, commitment),
+        ReceiveMsg::BuyListingCw20 {
+            listing_id,
+        } => execute_buy_listing_with_funds(deps, env, &user_wallet, &balance, listing_id),
     }
 }
 
 // CW721 filter
 pub fn execute_receive_nft(
     deps: DepsMut,
+    env: &Env,
     info: MessageInfo,
     wrapper: Cw721ReceiveMsg,
 ) -> Result<Response, ContractError> {
@@ -230,10 +437,12 @@ pub fn execute_receive_nft(
         ReceiveNftMsg::AddToBucketCw721 {
             bucket_id,
         } => execute_add_to_bucket_cw721(deps, &user_wallet, incoming_nft, bucket_id),
+        ReceiveNftMsg::FillCollectionOffer {
+            offer_id,
+        } => execute_fill_collection_offer(deps, env, &user_wallet, incoming_nft, offer_id),
     }
 }
 
-
 //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // Reply
 //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -263,6 +472,88 @@ pub fn reply(
 }
 
 
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Sudo
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Lets the chain (ie a clock module) return expired Listings
+/// - Never errors when there is nothing to process, so the chain
+///   doesn't stop calling the contract
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    let limit = match msg {
+        SudoMsg::ProcessExpired {
+            limit,
+        } => limit,
+        SudoMsg::ClockEndBlock {} => None,
+    };
+
+    // Listings are only partly migrated, they're returned once migration is done
+    if MIGRATION_IN_PROGRESS.may_load(deps.storage)?.is_some() {
+        return Ok(Response::new()
+            .add_attribute("action", "sudo_process_expired")
+            .add_attribute("listings_returned", "0"));
+    }
+
+    let (msgs, listing_ids) = process_expired(deps.storage, &env, limit)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "sudo_process_expired")
+        .add_attribute("listings_returned", listing_ids.len().to_string())
+        .add_messages(msgs))
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Migrate
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Errors if the stored contract is not a Fuzion Market or if
+/// the stored version is newer than this one
+///
+/// Otherwise runs every registered state migration between the
+/// stored version and this version
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::ForeignContract(stored.contract));
+    }
+
+    let stored_version = parse_version(&stored.version)?;
+    let current_version = parse_version(CONTRACT_VERSION)?;
+
+    if stored_version > current_version {
+        return Err(ContractError::Downgrade {
+            from: stored.version,
+            to: CONTRACT_VERSION.to_string(),
+        });
+    }
+
+    let limit = msg.limit.unwrap_or(DEFAULT_MIGRATION_LIMIT).clamp(1, MAX_MIGRATION_LIMIT);
+    let progress = run_migrations(deps.branch(), &env, &stored_version, &current_version, limit)?;
+
+    // cw2 version stays at the old version until every migration is done,
+    // so calling migrate again resumes instead of skipping the rest
+    if progress.complete {
+        set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    }
+
+    let applied = if progress.applied.is_empty() {
+        "none".to_string()
+    } else {
+        progress.applied.join(",")
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", stored.version)
+        .add_attribute("to_version", CONTRACT_VERSION)
+        .add_attribute("migrations_applied", applied)
+        .add_attribute("migration_complete", progress.complete.to_string()))
+}
+
+
 //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // Query
 //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -271,23 +562,111 @@ pub fn reply(
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::GetFeeDenom {} => to_binary(&get_fee_denom(deps)?),
+        QueryMsg::GetFeeSchedule {} => to_binary(&get_fee_schedule(deps)?),
+        QueryMsg::GetFeeRoutes {} => to_binary(&get_fee_routes(deps)?),
+        QueryMsg::GetFeeLedger {} => to_binary(&get_fee_ledger(deps)?),
         QueryMsg::GetListingsByOwner {
             owner,
-            page_num,
-        } => to_binary(&get_listings_by_owner(deps, owner.as_str(), page_num)?),
+            start_after,
+            limit,
+        } => to_binary(&get_listings_by_owner(deps, owner.as_str(), start_after, limit)?),
         QueryMsg::GetListingsByWhitelist {
             owner,
-        } => to_binary(&get_whitelisted(deps, env, owner)?),
+            start_after,
+            limit,
+        } => to_binary(&get_whitelisted(deps, env, owner, start_after, limit)?),
         QueryMsg::GetBuckets {
             bucket_owner,
-            page_num,
-        } => to_binary(&get_buckets(deps, bucket_owner.as_str(), page_num)?),
+            start_after,
+            limit,
+        } => to_binary(&get_buckets(deps, bucket_owner.as_str(), start_after, limit)?),
         QueryMsg::GetListingsForMarket {
-            page_num,
-        } => to_binary(&get_listings_for_market(deps, env, page_num)?),
-        // QueryMsg::GetListingInfo {
-        //     listing_id,
-        // } => to_binary(&get_single_listing(deps, listing_id)?),
-        QueryMsg::GetRoyaltyAddr {} => to_binary(&get_royalty_contract(deps)?)
+            start_after,
+            limit,
+        } => to_binary(&get_listings_for_market(deps, env, start_after, limit)?),
+        QueryMsg::GetMarketListings {
+            filter,
+            sort_by,
+            order,
+            start_after,
+            limit,
+        } => to_binary(&get_market_listings(
+            deps,
+            filter.unwrap_or_default(),
+            sort_by.unwrap_or(MarketSort::FinalizedTime),
+            order.unwrap_or(SortOrder::Ascending).into(),
+            start_after,
+            limit,
+        )?),
+        QueryMsg::GetListing {
+            listing_id,
+        } => to_binary(&get_listing(deps, listing_id)?),
+        QueryMsg::GetCurrentPrice {
+            listing_id,
+        } => to_binary(&get_current_price(deps, &env, listing_id)?),
+        QueryMsg::GetBucket {
+            bucket_id,
+        } => to_binary(&get_bucket(deps, bucket_id)?),
+        QueryMsg::GetListingsById {
+            listing_ids,
+        } => to_binary(&get_listings_by_id(deps, listing_ids)?),
+        QueryMsg::GetLapsedApprovals {
+            listing_ids,
+        } => to_binary(&get_lapsed_approvals(deps, &env, listing_ids)?),
+        QueryMsg::GetBucketsById {
+            bucket_ids,
+        } => to_binary(&get_buckets_by_id(deps, bucket_ids)?),
+        QueryMsg::WhereIsNft {
+            collection,
+            token_id,
+        } => to_binary(&where_is_nft(deps, collection, token_id)?),
+        QueryMsg::ListingsByCollection {
+            collection,
+            start_after,
+            limit,
+        } => to_binary(&get_listings_by_collection(deps, collection, start_after, limit)?),
+        QueryMsg::ListingsContainingDenom {
+            denom,
+            start_after,
+            limit,
+        } => to_binary(&get_listings_containing_denom(deps, denom, start_after, limit)?),
+        QueryMsg::BucketsByCollection {
+            collection,
+            start_after,
+            limit,
+        } => to_binary(&get_buckets_by_collection(deps, collection, start_after, limit)?),
+        QueryMsg::BucketsContainingDenom {
+            denom,
+            start_after,
+            limit,
+        } => to_binary(&get_buckets_containing_denom(deps, denom, start_after, limit)?),
+        QueryMsg::ListingsByAskAsset {
+            asset,
+            start_after,
+            limit,
+        } => to_binary(&get_listings_by_ask_asset(deps, env, asset, start_after, limit)?),
+        QueryMsg::GetOffersByListing {
+            listing_id,
+            start_after,
+            limit,
+        } => to_binary(&get_offers_by_listing(deps, listing_id, start_after, limit)?),
+        QueryMsg::GetOffersByBidder {
+            bidder,
+            start_after,
+            limit,
+        } => to_binary(&get_offers_by_bidder(deps, &bidder, start_after, limit)?),
+        QueryMsg::GetCollectionOffers {
+            collection,
+            start_after,
+            limit,
+        } => to_binary(&get_collection_offers(deps, env, &collection, start_after, limit)?),
+        QueryMsg::GetCollectionOffersByBidder {
+            bidder,
+            start_after,
+            limit,
+        } => to_binary(&get_collection_offers_by_bidder(deps, &bidder, start_after, limit)?),
+        QueryMsg::GetRoyaltyAddr {} => to_binary(&get_royalty_contract(deps)?),
+        QueryMsg::GetOwnership {} => to_binary(&get_ownership(deps)?),
+        QueryMsg::GetConfig {} => to_binary(&get_config(deps)?),
     }
 }
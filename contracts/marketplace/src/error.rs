@@ -66,6 +66,12 @@ pub enum ContractError {
     #[error("Error Message: Listing not purchasable")]
     NotPurchasable {},
 
+    #[error("Error Message: Bucket is locked by an offer")]
+    BucketLocked {},
+
+    #[error("Error Message: Offer is expired")]
+    OfferExpired {},
+
     #[error("Error Message: Missing Instantiate Option {0}")]
     MissingInit(String),
 
@@ -77,4 +83,22 @@ pub enum ContractError {
 
     #[error("Error Message: Fee calculation error")]
     FeeCalc,
+
+    #[error("Error Message: Flat fee of {0} must be sent in, and nothing else")]
+    FlatFeeRequired(String),
+
+    #[error("Error Message: Invalid Config: {0}")]
+    InvalidConfig(String),
+
+    #[error("Error Message: Cannot migrate from contract {0}")]
+    ForeignContract(String),
+
+    #[error("Error Message: Migration to {0} is unfinished, call migrate again")]
+    MigrationInProgress(String),
+
+    #[error("Error Message: Cannot migrate from version {from} to older version {to}")]
+    Downgrade {
+        from: String,
+        to: String,
+    },
 }
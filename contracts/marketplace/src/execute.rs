@@ -18,7 +18,7 @@ pub fn execute_create_bucket(
     }
 
     // Check that bucket_id isn't used (edge case)
-    if BUCKETS.has(deps.storage, (creator.clone(), bucket_id)) {
+    if bucketz().has(deps.storage, (creator.clone(), bucket_id)) {
         return Err(ContractError::IdAlreadyExists {});
     }
 
@@ -27,11 +27,12 @@ pub fn execute_create_bucket(
     funds.normalized_check()?;
 
     // Save bucket
-    BUCKETS.save(
+    bucketz().save(
         deps.storage,
         (creator.clone(), bucket_id),
         &Bucket {
             owner: creator.clone(),
+            id: bucket_id,
             funds: GenericBalance::from_balance(funds),
             fee_amount: None,
         },
@@ -60,16 +61,17 @@ pub fn execute_create_bucket_cw721(
     }
 
     // Check that bucket_id isn't used (edge case)
-    if BUCKETS.has(deps.storage, (user_wallet.clone(), bucket_id)) {
+    if bucketz().has(deps.storage, (user_wallet.clone(), bucket_id)) {
         return Err(ContractError::IdAlreadyExists {});
     }
 
     // NFT validation checks are handled in receiver wrapper
-    BUCKETS.save(
+    bucketz().save(
         deps.storage,
         (user_wallet.clone(), bucket_id),
         &Bucket {
             owner: user_wallet.clone(),
+            id: bucket_id,
             funds: GenericBalance::from_nft(nft),
             fee_amount: None,
         },
@@ -94,15 +96,18 @@ pub fn execute_add_to_bucket(
     funds.normalized_check()?;
 
     // Ensure bucket exists & Sender is owner
-    let Some(the_bucket) = BUCKETS.may_load(deps.storage, (sender.clone(), bucket_id))? else {
+    let Some(the_bucket) = bucketz().may_load(deps.storage, (sender.clone(), bucket_id))? else {
         return Err(ContractError::NotFound { typ: "Bucket".to_string(), id: bucket_id.to_string() })
     };
 
     // Authorized check
-    if sender != &the_bucket.owner {
+    if *sender != the_bucket.owner {
         return Err(ContractError::Unauthorized {});
     }
 
+    // Offered Buckets can't change until the Offer is retracted
+    check_bucket_unlocked(deps.storage, bucket_id)?;
+
     // Add tokens
     let new_bucket: Bucket = {
         let old_funds = the_bucket.funds.clone();
@@ -115,12 +120,13 @@ pub fn execute_add_to_bucket(
         }
     }?;
 
-    // Check that bucket funds are valid (specifically not over 35)
-    new_bucket.funds.check_valid()?;
+    // Check that bucket funds are valid (specifically not over max_num_assets)
+    let config = CONFIG.load(deps.storage)?;
+    new_bucket.funds.check_valid(config.max_num_assets)?;
 
     // Save the updated bucket
-    //BUCKETS.save(deps.storage, (sender.clone(), &bucket_id), &new_bucket)?;
-    BUCKETS.update(deps.storage, (sender.clone(), bucket_id), {
+    //bucketz().save(deps.storage, (sender.clone(), &bucket_id), &new_bucket)?;
+    bucketz().update(deps.storage, (sender.clone(), bucket_id), {
         |o| match o {
             Some(_) => Ok(new_bucket),
             None => Err(ContractError::GenericError("Error during storage update".to_string())),
@@ -139,15 +145,18 @@ pub fn execute_add_to_bucket_cw721(
     bucket_id: u64,
 ) -> Result<Response, ContractError> {
     // Ensure bucket exists & Sender is owner
-    let Some(the_bucket) = BUCKETS.may_load(deps.storage, (user_wallet.clone(), bucket_id))? else {
+    let Some(the_bucket) = bucketz().may_load(deps.storage, (user_wallet.clone(), bucket_id))? else {
         return Err(ContractError::NotFound { typ: "Bucket".to_string(), id: bucket_id.to_string() })
     };
 
     // Authorized check
-    if user_wallet != &the_bucket.owner {
+    if *user_wallet != the_bucket.owner {
         return Err(ContractError::Unauthorized {});
     }
 
+    // Offered Buckets can't change until the Offer is retracted
+    check_bucket_unlocked(deps.storage, bucket_id)?;
+
     // Create updated bucket
     let new_bucket: Bucket = {
         let old_funds = the_bucket.funds.clone();
@@ -160,11 +169,12 @@ pub fn execute_add_to_bucket_cw721(
         }
     }?;
 
-    // Check that bucket funds are valid (specifically not over 35)
-    new_bucket.funds.check_valid()?;
+    // Check that bucket funds are valid (specifically not over max_num_assets)
+    let config = CONFIG.load(deps.storage)?;
+    new_bucket.funds.check_valid(config.max_num_assets)?;
 
     // Save updated bucket
-    BUCKETS.update(deps.storage, (user_wallet.clone(), bucket_id), {
+    bucketz().update(deps.storage, (user_wallet.clone(), bucket_id), {
         |o| match o {
             Some(_) => Ok(new_bucket),
             None => Err(ContractError::GenericError("Error during storage update".to_string())),
@@ -178,24 +188,28 @@ pub fn execute_add_to_bucket_cw721(
 
 pub fn execute_withdraw_bucket(
     deps: DepsMut,
-    env: &Env,
     user: &Addr,
     bucket_id: u64,
+    destination: Option<WithdrawDestinationUnvalidated>,
 ) -> Result<Response, ContractError> {
     // Get Bucket
-    let the_bucket: Bucket = BUCKETS.load(deps.storage, (user.clone(), bucket_id))?;
+    let the_bucket: Bucket = bucketz().load(deps.storage, (user.clone(), bucket_id))?;
 
     // Check sender is owner redundant
-    if &the_bucket.owner != user {
+    if the_bucket.owner != *user {
         return Err(ContractError::Unauthorized {});
     }
 
+    // Offered Buckets can't change until the Offer is retracted
+    check_bucket_unlocked(deps.storage, bucket_id)?;
+
     // Create Send Msgs
-    // (fee_amount is added when Bucket is used to buy a Listing)
-    let msgs = the_bucket.withdraw_msgs(env.contract.address.clone())?;
+    // (fee_amount was credited to the Fee Ledger when Bucket was used to buy a Listing)
+    let destination = destination.map(|d| d.validate(&deps)).transpose()?;
+    let msgs = the_bucket.withdraw_msgs(destination.as_ref())?;
 
     // Remove Bucket
-    BUCKETS.remove(deps.storage, (user.clone(), bucket_id));
+    bucketz().remove(deps.storage, (user.clone(), bucket_id))?;
 
     Ok(Response::new()
         .add_attribute("action", "empty_bucket")
@@ -249,25 +263,31 @@ pub fn execute_create_listing(
     }
 
     // Validate ask
-    let valid_ask: GenericBalance = createlistingmsg.ask.validate(&deps)?;
+    let config = CONFIG.load(deps.storage)?;
+    let valid_ask: GenericBalance = createlistingmsg.ask.validate(&deps, config.max_num_assets)?;
+
+    let listing = Listing {
+        creator: user_address.clone(),
+        id: listing_id,
+        finalized_time: None,
+        expiration_time: None,
+        status: Status::BeingPrepared,
+        claimant: None,
+        whitelisted_buyer: wl_buyer,
+        for_sale: GenericBalance::from_balance(funds_sent),
+        ask: valid_ask,
+        partial_fill: createlistingmsg.partial_fill,
+        auction: createlistingmsg.auction.map(Auction::from),
+        approved_nfts: None,
+        flat_fee: None,
+        fee_amount: None,
+    };
+
+    // Partial fill & auction settings must fit what's for sale & the ask
+    listing.check_mode()?;
 
     // Save listing
-    listingz().save(
-        deps.storage,
-        (user_address, listing_id),
-        &Listing {
-            creator: user_address.clone(),
-            id: listing_id,
-            finalized_time: None,
-            expiration_time: None,
-            status: Status::BeingPrepared,
-            claimant: None,
-            whitelisted_buyer: wl_buyer,
-            for_sale: GenericBalance::from_balance(funds_sent),
-            ask: valid_ask,
-            fee_amount: None,
-        },
-    )?;
+    listingz().save(deps.storage, (user_address, listing_id), &listing)?;
 
     // Mark this ID as used
     LISTING_ID_USED.save(deps.storage, listing_id, &true)?;
@@ -316,24 +336,30 @@ pub fn execute_create_listing_cw721(
     }
 
     // Validate ask
-    let valid_ask: GenericBalance = createlistingmsg.ask.validate(&deps)?;
+    let config = CONFIG.load(deps.storage)?;
+    let valid_ask: GenericBalance = createlistingmsg.ask.validate(&deps, config.max_num_assets)?;
+
+    let listing = Listing {
+        creator: user_wallet.clone(),
+        id: listing_id,
+        finalized_time: None,
+        expiration_time: None,
+        status: Status::BeingPrepared,
+        claimant: None,
+        whitelisted_buyer: wl_buyer,
+        for_sale: GenericBalance::from_nft(nft),
+        ask: valid_ask,
+        partial_fill: createlistingmsg.partial_fill,
+        auction: createlistingmsg.auction.map(Auction::from),
+        approved_nfts: None,
+        flat_fee: None,
+        fee_amount: None,
+    };
 
-    listingz().save(
-        deps.storage,
-        (user_wallet, listing_id),
-        &Listing {
-            creator: user_wallet.clone(),
-            id: listing_id,
-            finalized_time: None,
-            expiration_time: None,
-            status: Status::BeingPrepared,
-            claimant: None,
-            whitelisted_buyer: wl_buyer,
-            for_sale: GenericBalance::from_nft(nft),
-            ask: valid_ask,
-            fee_amount: None,
-        },
-    )?;
+    // Partial fill & auction settings must fit what's for sale & the ask
+    listing.check_mode()?;
+
+    listingz().save(deps.storage, (user_wallet, listing_id), &listing)?;
 
     // Mark this listing_id as used
     LISTING_ID_USED.save(deps.storage, listing_id, &true)?;
@@ -359,7 +385,7 @@ pub fn execute_change_ask(
     };
 
     // Ensure sender is creator
-    if user_sender != &listing.creator {
+    if *user_sender != listing.creator {
         return Err(ContractError::Unauthorized {});
     }
 
@@ -379,7 +405,8 @@ pub fn execute_change_ask(
     }
 
     // Validate ask
-    let valid_ask: GenericBalance = new_ask.validate(&deps)?;
+    let config = CONFIG.load(deps.storage)?;
+    let valid_ask: GenericBalance = new_ask.validate(&deps, config.max_num_assets)?;
 
     listingz().replace(
         deps.storage,
@@ -414,7 +441,7 @@ pub fn execute_add_to_listing(
     };
 
     // Ensure sender is Creator
-    if user_sender != &listing.creator {
+    if *user_sender != listing.creator {
         return Err(ContractError::Unauthorized {});
     }
 
@@ -441,16 +468,17 @@ pub fn execute_add_to_listing(
         }
     }?;
 
-    // Check that new listing doesn't have over MAX_NUM_ASSETS
-    let _ = new_listing
+    // Check that new listing doesn't have over max_num_assets
+    let max_num_assets = CONFIG.load(deps.storage)?.max_num_assets;
+    new_listing
         .for_sale
         .native.len()
         .checked_add(new_listing.for_sale.cw20.len())
         .and_then(|v| v.checked_add(new_listing.for_sale.nfts.len()))
-        .ok_or_else(|| ContractError::GenericError(format!("Listing cannot contain over {} items", MAX_NUM_ASSETS)))
+        .ok_or_else(|| ContractError::GenericError(format!("Listing cannot contain over {} items", max_num_assets)))
         .and_then(|v| {
-            if v as u32 > MAX_NUM_ASSETS {
-                return Err(ContractError::GenericError(format!("Listing cannot contain over {} items", MAX_NUM_ASSETS)));
+            if v as u32 > max_num_assets {
+                return Err(ContractError::GenericError(format!("Listing cannot contain over {} items", max_num_assets)));
             }
             Ok(())
         })?;
@@ -482,7 +510,7 @@ pub fn execute_add_to_listing_cw721(
     };
 
     // Ensure sender is Creator
-    if user_wallet != &old_listing.creator {
+    if *user_wallet != old_listing.creator {
         return Err(ContractError::Unauthorized {});
     }
 
@@ -508,8 +536,9 @@ pub fn execute_add_to_listing_cw721(
         }
     }?;
 
-    // Check that new_listings for_sale is valid (over MAX_NUM_ASSETS or duplicate NFTs)
-    new_listing.for_sale.check_valid()?;
+    // Check that new_listings for_sale is valid (over max_num_assets or duplicate NFTs)
+    let config = CONFIG.load(deps.storage)?;
+    new_listing.for_sale.check_valid(config.max_num_assets)?;
 
     // Replace old listing with new listing
     listingz().replace(
@@ -538,7 +567,7 @@ pub fn execute_finalize(
     };
 
     // Ensure sender is creator
-    if sender != &listing.creator {
+    if *sender != listing.creator {
         return Err(ContractError::Unauthorized {});
     }
 
@@ -557,15 +586,41 @@ pub fn execute_finalize(
         return Err(ContractError::Unauthorized {});
     }
 
-    // max expiration is 1209600 seconds <14 days>
-    // min expiration is 600 seconds <10 minutes>
-    if !(600..=1_209_600).contains(&seconds) {
+    // Tokens added or ask changed since creation must still fit
+    // the partial fill or auction settings
+    listing.check_mode()?;
+
+    // Expiration must be within Config bounds
+    // (default min 600 seconds <10 minutes> | default max 1209600 seconds <14 days>)
+    let config = CONFIG.load(deps.storage)?;
+    if !(config.min_expiration_seconds..=config.max_expiration_seconds).contains(&seconds) {
         return Err(ContractError::InvalidExpiration {});
     }
 
     let finalized_at = env.block.time;
     let expiration = env.block.time.plus_seconds(seconds);
 
+    // NFTs listed by approval must still be approved, until at least the expiration
+    for nft in listing.approved() {
+        if let Expiration::AtTime(lapses) = check_approved(deps.as_ref(), env, sender, nft)? {
+            if lapses < expiration {
+                return Err(ContractError::GenericError(format!(
+                    "Approval for NFT {} #{} lapses before the Listing expires",
+                    nft.contract_address, nft.token_id
+                )));
+            }
+        }
+    }
+
+    // The winning bid of an English or sealed bid auction pays the flat fee set now,
+    // not whatever the fee schedule holds when it's settled
+    let flat_fee = match listing.auction {
+        Some(Auction::English(_) | Auction::SealedBid(_)) => {
+            FEE_SCHEDULE.load(deps.storage)?.flat_fee
+        }
+        _ => None,
+    };
+
     listingz().replace(
         deps.storage,
         (sender, listing_id),
@@ -573,6 +628,7 @@ pub fn execute_finalize(
             finalized_time: Some(finalized_at),
             expiration_time: Some(expiration),
             status: Status::FinalizedReady,
+            flat_fee,
             ..listing.clone()
         }),
         Some(&listing),
@@ -591,6 +647,7 @@ pub fn execute_delete_listing(
     env: &Env,
     sender: Addr,
     listing_id: u64,
+    destination: Option<WithdrawDestinationUnvalidated>,
 ) -> Result<Response, ContractError> {
     // Check listing exists, sender is owner & get listing
     let Some(listing) = listingz().may_load(deps.storage, (&sender, listing_id))? else {
@@ -621,14 +678,366 @@ pub fn execute_delete_listing(
         }
     }
 
-    // Delete listing & send funds back to user
-    let msgs = send_tokens_cosmos(&listing.creator, &listing.for_sale)?;
+    // Auctions with a bid can only be settled
+    if listing.has_bid() {
+        return Err(ContractError::GenericError("Auction has a bid, use SettleAuction".to_string()));
+    }
+
+    // Delete listing & send funds back to user or `destination`, approved NFTs never left
+    let msgs = match destination.map(|d| d.validate(&deps)).transpose()? {
+        Some(destination) => destination.msgs(&listing.escrowed())?,
+        None => send_tokens_cosmos(&listing.creator, &listing.escrowed())?,
+    };
 
     listingz().remove(deps.storage, (&sender, listing_id))?;
 
     Ok(Response::new().add_attribute("Remove listing", listing_id.to_string()).add_messages(msgs))
 }
 
+/// Default number of Listings returned by one `ProcessExpired`
+pub const DEFAULT_EXPIRED_LIMIT: u32 = 10;
+/// Most Listings returned by one `ProcessExpired`
+pub const MAX_EXPIRED_LIMIT: u32 = 30;
+
+/// Removes up to `limit` expired Listings, oldest expiration first
+/// - Returns messages sending each escrowed `for_sale` back to its creator
+/// - Purchased Listings are skipped, they are withdrawn by the buyer
+/// - Auctions with a bid are skipped, they are settled by `SettleAuction`
+pub fn process_expired(
+    storage: &mut dyn Storage,
+    env: &Env,
+    limit: Option<u32>,
+) -> Result<(Vec<CosmosMsg>, Vec<u64>), ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_EXPIRED_LIMIT).clamp(1, MAX_EXPIRED_LIMIT) as usize;
+
+    // Unfinalized, purchased & bid on Listings are indexed at 0,
+    // so no more than `limit` Listings are ever read
+    let expired = listingz()
+        .idx
+        .returnable
+        .prefix_range_raw(
+            storage,
+            Some(PrefixBound::inclusive(1_u64)),
+            Some(PrefixBound::inclusive(env.block.time.seconds())),
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|entry| entry.map(|(_pk, listing)| listing))
+        .collect::<StdResult<Vec<Listing>>>()?;
+
+    let mut msgs = vec![];
+    let mut listing_ids = vec![];
+
+    for listing in expired {
+        msgs.extend(send_tokens_cosmos(&listing.creator, &listing.escrowed())?);
+        listingz().remove(storage, (&listing.creator, listing.id))?;
+        listing_ids.push(listing.id);
+    }
+
+    Ok((msgs, listing_ids))
+}
+
+/// Anyone can call this, returns expired Listings to their creators
+/// - Pays `Config.expiry_bounty` per Listing, capped at the pending fees in that denom
+/// - Errors if there are no expired Listings
+pub fn execute_process_expired(
+    deps: DepsMut,
+    env: &Env,
+    caller: &Addr,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let (msgs, listing_ids) = process_expired(deps.storage, env, limit)?;
+
+    if listing_ids.is_empty() {
+        return Err(ContractError::GenericError("No expired Listings to process".to_string()));
+    }
+
+    let mut res = Response::new()
+        .add_attribute("action", "process_expired")
+        .add_attribute("listings_returned", listing_ids.len().to_string())
+        .add_messages(msgs);
+
+    // Bounty comes out of pending fees, never out of escrowed assets
+    if let Some(bounty) = CONFIG.load(deps.storage)?.expiry_bounty {
+        let asset = FeeAsset::Native(bounty.denom.clone());
+        if let Some(mut totals) = FEE_LEDGER.may_load(deps.storage, asset.key())? {
+            let due = bounty.amount.saturating_mul(Uint128::from(listing_ids.len() as u64));
+            let paid = due.min(totals.pending);
+
+            if !paid.is_zero() {
+                totals.pending -= paid;
+                FEE_LEDGER.save(deps.storage, asset.key(), &totals)?;
+
+                let paid = coin(paid.u128(), bounty.denom);
+                res = res
+                    .add_attribute("bounty", paid.to_string())
+                    .add_message(BankMsg::Send {
+                        to_address: caller.to_string(),
+                        amount: vec![paid],
+                    });
+            }
+        }
+    }
+
+    Ok(res)
+}
+
+/// Lists NFTs that stay in the creator's wallet under an `Approve` to this contract
+/// - Same rules as AddToListing, the creator must own each NFT & have approved it
+/// - Errors if an NFT is already in one of the creator's Listings (expired ones
+///   must be deleted first), so an NFT is only ever for sale once
+pub fn execute_add_approved_nfts(
+    deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    listing_id: u64,
+    nfts: Vec<NftUnverified>,
+) -> Result<Response, ContractError> {
+    let Some(listing): Option<Listing> =
+        listingz().may_load(deps.storage, (sender, listing_id))?
+    else {
+        return Err(ContractError::NotFound {
+            typ: "Listing".to_string(),
+            id: listing_id.to_string(),
+        });
+    };
+
+    if *sender != listing.creator || listing.claimant.is_some() {
+        return Err(ContractError::Unauthorized {});
+    }
+    if listing.status != Status::BeingPrepared {
+        return Err(ContractError::AlreadyFinalized {});
+    }
+
+    let max_num_assets = CONFIG.load(deps.storage)?.max_num_assets;
+    let nfts = GenericBalanceUnvalidated {
+        native: vec![],
+        cw20: vec![],
+        nfts,
+    }
+    .validate(&deps, max_num_assets)?
+    .nfts;
+
+    for nft in &nfts {
+        check_approved(deps.as_ref(), env, &listing.creator, nft)?;
+
+        let key = (nft.contract_address.clone(), nft.token_id.clone());
+        for listed_in in listingz().idx.nft.ids(deps.storage, key, None) {
+            let listed_in = listed_in?;
+            if listingz().has(deps.storage, (sender, listed_in)) {
+                return Err(ContractError::GenericError(format!(
+                    "NFT {} #{} is already in Listing {listed_in}",
+                    nft.contract_address, nft.token_id
+                )));
+            }
+        }
+    }
+
+    let mut new_listing = listing.clone();
+    new_listing.for_sale.nfts.extend(nfts.clone());
+    new_listing.for_sale.check_valid(max_num_assets)?;
+    new_listing.approved_nfts = Some([listing.approved(), &nfts].concat());
+    new_listing.check_mode()?;
+
+    listingz().replace(deps.storage, (sender, listing_id), Some(&new_listing), Some(&listing))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_approved_nfts")
+        .add_attribute("listing", listing_id.to_string()))
+}
+
+/// Errors if `owner` no longer holds `nft` or its approval to this contract has lapsed,
+/// otherwise returns when the approval expires
+fn check_approved(
+    deps: Deps,
+    env: &Env,
+    owner: &Addr,
+    nft: &Nft,
+) -> Result<Expiration, ContractError> {
+    nft.approval(&deps.querier, owner, &env.contract.address)
+        .map(|approval| approval.expires)
+        .ok_or_else(|| {
+            ContractError::GenericError(format!(
+                "NFT {} #{} was moved or its approval lapsed",
+                nft.contract_address, nft.token_id
+            ))
+        })
+}
+
+/// Messages pulling a Listing's NFTs listed by approval from its creator
+/// - Errors if any was moved or its approval lapsed
+fn pull_approved(
+    deps: Deps,
+    env: &Env,
+    listing: &Listing,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    for nft in listing.approved() {
+        check_approved(deps, env, &listing.creator, nft)?;
+    }
+
+    pull_msgs(
+        deps,
+        env,
+        &listing.creator,
+        &GenericBalance {
+            native: vec![],
+            cw20: vec![],
+            nfts: listing.approved().to_vec(),
+        },
+    )
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Deposits
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+pub fn execute_deposit_to_listing(
+    deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    funds: &[Coin],
+    listing_id: u64,
+    cw20: Vec<Cw20CoinUnverified>,
+    nfts: Vec<NftUnverified>,
+) -> Result<Response, ContractError> {
+    let Some(listing): Option<Listing> =
+        listingz().may_load(deps.storage, (sender, listing_id))?
+    else {
+        return Err(ContractError::NotFound {
+            typ: "Listing".to_string(),
+            id: listing_id.to_string(),
+        });
+    };
+
+    // Same rules as AddToListing
+    if *sender != listing.creator || listing.claimant.is_some() {
+        return Err(ContractError::Unauthorized {});
+    }
+    if listing.status != Status::BeingPrepared {
+        return Err(ContractError::AlreadyFinalized {});
+    }
+
+    let max_num_assets = CONFIG.load(deps.storage)?.max_num_assets;
+    let deposit = GenericBalanceUnvalidated {
+        native: funds.to_vec(),
+        cw20,
+        nfts,
+    }
+    .validate(&deps, max_num_assets)?;
+
+    let pull_msgs = pull_msgs(deps.as_ref(), env, sender, &deposit)?;
+
+    let mut new_listing = listing.clone();
+    new_listing.for_sale.add_balance(deposit);
+    new_listing.for_sale.check_valid(max_num_assets)?;
+
+    listingz().replace(deps.storage, (sender, listing_id), Some(&new_listing), Some(&listing))?;
+
+    Ok(Response::new()
+        .add_messages(pull_msgs)
+        .add_attribute("action", "deposit_to_listing")
+        .add_attribute("listing", listing_id.to_string()))
+}
+
+pub fn execute_deposit_to_bucket(
+    deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    funds: &[Coin],
+    bucket_id: u64,
+    cw20: Vec<Cw20CoinUnverified>,
+    nfts: Vec<NftUnverified>,
+) -> Result<Response, ContractError> {
+    let Some(mut bucket) = bucketz().may_load(deps.storage, (sender.clone(), bucket_id))? else {
+        return Err(ContractError::NotFound {
+            typ: "Bucket".to_string(),
+            id: bucket_id.to_string(),
+        });
+    };
+
+    // Same rules as AddToBucket
+    if *sender != bucket.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+    check_bucket_unlocked(deps.storage, bucket_id)?;
+
+    let max_num_assets = CONFIG.load(deps.storage)?.max_num_assets;
+    let deposit = GenericBalanceUnvalidated {
+        native: funds.to_vec(),
+        cw20,
+        nfts,
+    }
+    .validate(&deps, max_num_assets)?;
+
+    let pull_msgs = pull_msgs(deps.as_ref(), env, sender, &deposit)?;
+
+    bucket.funds.add_balance(deposit);
+    bucket.funds.check_valid(max_num_assets)?;
+
+    bucketz().save(deps.storage, (sender.clone(), bucket_id), &bucket)?;
+
+    Ok(Response::new()
+        .add_messages(pull_msgs)
+        .add_attribute("action", "deposit_to_bucket")
+        .add_attribute("bucket_id", bucket_id.to_string()))
+}
+
+/// Messages pulling the CW20s & NFTs in `assets` from `owner` to this contract,
+/// through allowances & approvals `owner` has given it (Natives are ignored)
+/// - Errors if `owner` doesn't own one of the NFTs, so an approval given by
+///   someone else can't be used to pull it
+fn pull_msgs(
+    deps: Deps,
+    env: &Env,
+    owner: &Addr,
+    assets: &GenericBalance,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let contract = env.contract.address.to_string();
+    let mut pull_msgs: Vec<CosmosMsg> = vec![];
+
+    for cw20 in &assets.cw20 {
+        pull_msgs.push(
+            WasmMsg::Execute {
+                contract_addr: cw20.address.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                    owner: owner.to_string(),
+                    recipient: contract.clone(),
+                    amount: cw20.amount,
+                })?,
+                funds: vec![],
+            }
+            .into(),
+        );
+    }
+
+    for nft in &assets.nfts {
+        let owner_of: OwnerOfResponse = deps.querier.query_wasm_smart(
+            &nft.contract_address,
+            &Cw721QueryMsg::OwnerOf {
+                token_id: nft.token_id.clone(),
+                include_expired: None,
+            },
+        )?;
+        if owner_of.owner != *owner {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        pull_msgs.push(
+            WasmMsg::Execute {
+                contract_addr: nft.contract_address.to_string(),
+                msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+                    recipient: contract.clone(),
+                    token_id: nft.token_id.clone(),
+                })?,
+                funds: vec![],
+            }
+            .into(),
+        );
+    }
+
+    Ok(pull_msgs)
+}
+
 //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // Purchasing
 //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -636,11 +1045,13 @@ pub fn execute_buy_listing(
     deps: DepsMut,
     env: &Env,
     buyer: &Addr,
+    funds: &[Coin],
     listing_id: u64,
     bucket_id: u64,
+    quantity: Option<Uint128>,
 ) -> Result<Response, ContractError> {
     // Get bucket (will error if no bucket found)
-    let the_bucket: Bucket = match BUCKETS.load(deps.storage, (buyer.clone(), bucket_id)) {
+    let mut the_bucket: Bucket = match bucketz().load(deps.storage, (buyer.clone(), bucket_id)) {
         Ok(buck) => Ok(buck),
         Err(_) => Err(ContractError::LoadBucketError {}),
     }?;
@@ -651,12 +1062,260 @@ pub fn execute_buy_listing(
     };
 
     // Check that sender is bucket owner (redundant check)
-    if buyer != &the_bucket.owner {
+    if *buyer != the_bucket.owner {
         return Err(ContractError::Unauthorized {});
     }
 
-    // Check that bucket contains required purchase price
-    genbal_cmp(&the_bucket.funds, &the_listing.ask)?;
+    // Offered Buckets can't change until the Offer is retracted
+    check_bucket_unlocked(deps.storage, bucket_id)?;
+
+    let Quote {
+        price,
+        sold,
+        remaining,
+    } = quote(env, buyer, &the_listing, quantity)?;
+
+    // Dutch auctions take the current price out of a Bucket of the ask asset,
+    // the rest is refunded to the buyer
+    let mut surplus = None;
+    if matches!(the_listing.auction, Some(Auction::Dutch(_))) {
+        let amount = price.single_fungible().unwrap_or_default();
+        let held = the_bucket.funds.single_fungible().unwrap_or_default();
+
+        if held < amount || the_bucket.funds != price.with_amount(held) {
+            return Err(ContractError::GenericError(format!(
+                "Bucket must hold at least {amount} of the ask asset"
+            )));
+        }
+
+        surplus = (held > amount).then(|| price.with_amount(held - amount));
+        the_bucket.funds = price;
+    } else {
+        // Check that bucket contains required purchase price
+        genbal_cmp(&the_bucket.funds, &price)?;
+    }
+
+    // A CW20 flat fee can't be sent in, so it's pulled through an allowance
+    let mut fee_funds = GenericBalance {
+        native: funds.to_vec(),
+        cw20: vec![],
+        nfts: vec![],
+    };
+    let mut fee_pull_msgs = vec![];
+    let flat_fee = FEE_SCHEDULE.load(deps.storage)?.flat_fee;
+    if let Some(due) = flat_fee_due(deps.as_ref(), flat_fee.as_ref(), &sold, &the_bucket.funds)? {
+        if matches!(due.asset, FeeAsset::Cw20(_)) {
+            if !funds.is_empty() {
+                return Err(ContractError::GenericError(
+                    "Flat fee is pulled in CW20, do not send funds".to_string(),
+                ));
+            }
+            fee_funds = due.to_balance();
+            fee_pull_msgs = pull_msgs(deps.as_ref(), env, buyer, &fee_funds)?;
+        }
+    }
+
+    let mut res = settle_sale(
+        deps,
+        env,
+        buyer,
+        FlatFeeFrom::Funds(&fee_funds),
+        the_listing,
+        Payment::Bucket(the_bucket),
+        sold,
+        remaining,
+    )?;
+
+    if let Some(surplus) = surplus {
+        res = res.add_messages(send_tokens_cosmos(buyer, &surplus)?);
+    }
+
+    res = res.add_messages(fee_pull_msgs);
+
+    Ok(res.add_attribute("action", "buy_listing"))
+}
+
+/// Buys a whole Listing with the funds sent, no Bucket needed
+/// - `funds` must cover the price & the flat fee (if any), the rest is refunded
+/// - Purchased assets are sent to the buyer & the payment to the seller right away
+pub fn execute_buy_listing_with_funds(
+    deps: DepsMut,
+    env: &Env,
+    buyer: &Addr,
+    funds: &Balance,
+    listing_id: u64,
+) -> Result<Response, ContractError> {
+    let Some((_pk, the_listing)): Option<(_, Listing)> =
+        listingz().idx.id.item(deps.storage, listing_id)?
+    else {
+        return Err(ContractError::NotFound {
+            typ: "Listing".to_string(),
+            id: listing_id.to_string(),
+        });
+    };
+
+    let quote = quote(env, buyer, &the_listing, None)?;
+    let res =
+        buy_with_funds(deps, env, buyer, GenericBalance::from_balance(funds), the_listing, quote)?;
+
+    Ok(res.add_attribute("action", "buy_listing_with_funds"))
+}
+
+/// Buys a whole Listing with Natives sent, pulling the CW20s & NFTs in the price
+/// from the buyer through allowances & approvals given to this contract
+/// - Each NFT must be owned by the buyer
+/// - `funds` must cover the Natives in the price & the flat fee (if any), the rest is refunded
+pub fn execute_buy_listing_with_allowances(
+    deps: DepsMut,
+    env: &Env,
+    buyer: &Addr,
+    funds: &[Coin],
+    listing_id: u64,
+) -> Result<Response, ContractError> {
+    let Some((_pk, the_listing)): Option<(_, Listing)> =
+        listingz().idx.id.item(deps.storage, listing_id)?
+    else {
+        return Err(ContractError::NotFound {
+            typ: "Listing".to_string(),
+            id: listing_id.to_string(),
+        });
+    };
+
+    let quote = quote(env, buyer, &the_listing, None)?;
+
+    let pull_msgs = pull_msgs(deps.as_ref(), env, buyer, &quote.price)?;
+
+    let brought = GenericBalance {
+        native: funds.to_vec(),
+        cw20: quote.price.cw20.clone(),
+        nfts: quote.price.nfts.clone(),
+    };
+
+    let res = buy_with_funds(deps, env, buyer, brought, the_listing, quote)?;
+
+    // Pulls run before the payouts that spend them
+    Ok(Response::new()
+        .add_messages(pull_msgs)
+        .add_submessages(res.messages)
+        .add_attributes(res.attributes)
+        .add_attribute("action", "buy_listing_with_allowances"))
+}
+
+/// Settles a quoted purchase for part of `brought`, refunding the rest
+/// - The flat fee (if any) is taken out of `brought`, a CW20 one not
+///   brought is pulled through an allowance
+fn buy_with_funds(
+    deps: DepsMut,
+    env: &Env,
+    buyer: &Addr,
+    brought: GenericBalance,
+    the_listing: Listing,
+    quote: Quote,
+) -> Result<Response, ContractError> {
+    let Quote {
+        price,
+        sold,
+        remaining,
+    } = quote;
+
+    let mut surplus = brought.checked_sub(&price)?;
+
+    let mut fee_funds = GenericBalance {
+        native: vec![],
+        cw20: vec![],
+        nfts: vec![],
+    };
+    let mut fee_pull_msgs = vec![];
+    let flat_fee = FEE_SCHEDULE.load(deps.storage)?.flat_fee;
+    if let Some(due) = flat_fee_due(deps.as_ref(), flat_fee.as_ref(), &sold, &price)? {
+        fee_funds = due.to_balance();
+        surplus = match (surplus.checked_sub(&fee_funds), &due.asset) {
+            (Ok(rest), _) => rest,
+            (Err(_), FeeAsset::Cw20(_)) => {
+                fee_pull_msgs = pull_msgs(deps.as_ref(), env, buyer, &fee_funds)?;
+                surplus
+            }
+            (Err(_), FeeAsset::Native(_)) => {
+                return Err(ContractError::FlatFeeRequired(due.to_string()));
+            }
+        };
+    }
+
+    let res = settle_sale(
+        deps,
+        env,
+        buyer,
+        FlatFeeFrom::Funds(&fee_funds),
+        the_listing,
+        Payment::Funds(price),
+        sold,
+        remaining,
+    )?;
+
+    Ok(res.add_messages(send_tokens_cosmos(buyer, &surplus)?).add_messages(fee_pull_msgs))
+}
+
+/// What a purchase costs & what it buys
+struct Quote {
+    price: GenericBalance,
+    sold: GenericBalance,
+    /// What's left for sale after (partial fills only)
+    remaining: Option<GenericBalance>,
+}
+
+/// Prices `quantity` (partial fills) or all of a Listing that `buyer` can buy right now
+fn quote(
+    env: &Env,
+    buyer: &Addr,
+    the_listing: &Listing,
+    quantity: Option<Uint128>,
+) -> Result<Quote, ContractError> {
+    // Only Dutch auctions can be bought outright, others are sold to the highest bid
+    if the_listing.auction.is_some() && !matches!(the_listing.auction, Some(Auction::Dutch(_))) {
+        return Err(ContractError::NotPurchasable {});
+    }
+
+    // Part of the Listing being bought, & what's left for sale after (partial fills only)
+    let quote = match (&the_listing.partial_fill, quantity) {
+        (None, None) => Quote {
+            // Price right now, only differs from `ask` for Dutch auctions
+            price: the_listing.current_ask(env.block.time),
+            sold: the_listing.for_sale.clone(),
+            remaining: None,
+        },
+        (None, Some(_)) => {
+            return Err(ContractError::GenericError(
+                "Listing cannot be partially filled".to_string(),
+            ));
+        }
+        (Some(partial_fill), quantity) => {
+            let available =
+                the_listing.for_sale.single_fungible().ok_or(ContractError::NotPurchasable {})?;
+            let quantity = quantity.unwrap_or(available);
+
+            if quantity.is_zero() || quantity > available {
+                return Err(ContractError::GenericError(format!(
+                    "Quantity must be between 1 and {available}"
+                )));
+            }
+
+            // The last piece can be under min_fill
+            if quantity < partial_fill.min_fill && quantity != available {
+                return Err(ContractError::GenericError(format!(
+                    "Quantity must be at least {}",
+                    partial_fill.min_fill
+                )));
+            }
+
+            let remaining = available - quantity;
+            Quote {
+                price: partial_fill.price(&the_listing.ask, quantity)?,
+                sold: the_listing.for_sale.with_amount(quantity),
+                remaining: (!remaining.is_zero())
+                    .then(|| the_listing.for_sale.with_amount(remaining)),
+            }
+        }
+    };
 
     // Check that listing is ready for purchase
     if the_listing.status != Status::FinalizedReady {
@@ -664,7 +1323,7 @@ pub fn execute_buy_listing(
     }
 
     // Check that the user buying is whitelisted
-    if !the_listing.whitelisted_buyer.clone().map_or(true, |wl| wl == buyer.clone()) {
+    if the_listing.whitelisted_buyer.as_ref().is_some_and(|wl| wl != buyer) {
         return Err(ContractError::Unauthorized {});
     }
 
@@ -680,14 +1339,90 @@ pub fn execute_buy_listing(
         }
     }
 
-    // Load current fee denom
-    let fee_denom: FeeDenom = FEE_DENOM.load(deps.storage)?;
+    Ok(quote)
+}
+
+/// Amount of `flat_fee` (if any) due on a trade of `sold` for `payment`
+fn flat_fee_due(
+    deps: Deps,
+    flat_fee: Option<&FlatFee>,
+    sold: &GenericBalance,
+    payment: &GenericBalance,
+) -> Result<Option<FeeCoin>, ContractError> {
+    let fee_schedule: FeeSchedule = FEE_SCHEDULE.load(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    let (l_fee_coin, _) = calc_fee_coin(&fee_schedule, config.fee_bps, sold)?;
+    let (b_fee_coin, _) = calc_fee_coin(&fee_schedule, config.fee_bps, payment)?;
+
+    Ok(flat_fee.and_then(|f| f.due(&[&l_fee_coin, &b_fee_coin])))
+}
+
+/// What's left of each side of a trade after fees & royalties
+struct Settlement {
+    /// Royalty payments & attributes
+    res: Response,
+    /// Fee taken out of what was sold
+    sold_fee: Option<FeeCoin>,
+    /// What was sold, less fee & royalties
+    buyer_gets: GenericBalance,
+    /// Fee taken out of the payment
+    payment_fee: Option<FeeCoin>,
+    /// The payment, less fee & royalties
+    seller_gets: GenericBalance,
+}
+
+/// Where the flat fee (if any) of a trade comes from
+enum FlatFeeFrom<'a> {
+    /// Sent in or pulled from the buyer, must be exactly what the fee schedule has due
+    Funds(&'a GenericBalance),
+    /// Taken out of what the seller gets, capped at what the payment holds in that denom
+    /// - Charged at the flat fee stored on the Offer or auction, not the fee schedule's
+    Payment(Option<&'a FlatFee>),
+}
+
+/// Takes fees & royalties out of both sides of a trade of `sold` for `payment`
+/// - Fees are credited to the Fee Ledger
+/// - Flat fee (if any) is sent in or taken out of the payment, see `FlatFeeFrom`
+fn settle_trade(
+    deps: DepsMut,
+    flat_fee_from: FlatFeeFrom,
+    sold: &GenericBalance,
+    payment: &GenericBalance,
+) -> Result<Settlement, ContractError> {
+    // Load current fee schedule & config
+    let fee_schedule: FeeSchedule = FEE_SCHEDULE.load(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
 
     // Calculate Fee amount for Listing (paid by Listing Buyer on withdraw)
-    let (l_fee_coin, mut l_balance) = calc_fee_coin(&fee_denom, &the_listing.for_sale)?;
+    let (l_fee_coin, mut l_balance) = calc_fee_coin(&fee_schedule, config.fee_bps, sold)?;
 
     // Calculate Fee amount for Bucket (paid by Listing Seller on withdraw)
-    let (b_fee_coin, mut b_balance) = calc_fee_coin(&fee_denom, &the_bucket.funds)?;
+    let (b_fee_coin, mut b_balance) = calc_fee_coin(&fee_schedule, config.fee_bps, payment)?;
+
+    let flat_fee = match flat_fee_from {
+        FlatFeeFrom::Funds(_) => fee_schedule.flat_fee.as_ref(),
+        FlatFeeFrom::Payment(stored) => stored,
+    };
+    let mut flat_fee_coin = flat_fee.and_then(|f| f.due(&[&l_fee_coin, &b_fee_coin]));
+
+    match (flat_fee_from, &flat_fee_coin) {
+        (FlatFeeFrom::Funds(funds), None) if !funds.native.is_empty() => {
+            return Err(ContractError::GenericError("No flat fee due, do not send funds".to_string()));
+        }
+        (FlatFeeFrom::Funds(funds), Some(due)) if *funds != due.to_balance() => {
+            return Err(ContractError::FlatFeeRequired(due.to_string()));
+        }
+        (FlatFeeFrom::Payment(_), Some(due)) => {
+            let held = due.asset.amount_in(&b_balance).unwrap_or_default();
+            let taken = due.amount.min(held);
+            flat_fee_coin = (!taken.is_zero()).then(|| FeeCoin::new(taken, due.asset.clone()));
+            if let Some(taken) = &flat_fee_coin {
+                b_balance = b_balance.checked_sub(&taken.to_balance())?;
+            }
+        }
+        _ => {}
+    }
 
     // On the NFTs that the seller is selling, the Seller should pay royalties
     // out of the proceeds they get from the sale
@@ -696,7 +1431,7 @@ pub fn execute_buy_listing(
     // on the assets they're purchasing
 
     // NFT contracts that seller is selling (duplicates removed)
-    let seller_nft_contracts = the_listing.for_sale.nfts
+    let seller_nft_contracts = sold.nfts
         .iter()
         .map(|nft| nft.contract_address.to_string())
         .collect::<BTreeSet<_>>()
@@ -704,7 +1439,7 @@ pub fn execute_buy_listing(
         .collect::<Vec<String>>();
 
     // NFT contracts that buyer is paying with (duplicates removed)
-    let buyer_nft_contracts = the_bucket.funds.nfts
+    let buyer_nft_contracts = payment.nfts
         .iter()
         .map(|nft| nft.contract_address.to_string())
         .collect::<BTreeSet<_>>()
@@ -732,7 +1467,7 @@ pub fn execute_buy_listing(
             )?;
 
             // Get Royalty Cosmos Msgs & subtract royalty amounts from bucket
-            let (royalty_msgs, bips_paid) = b_balance.royalties(royalty_responses)?;
+            let (royalty_msgs, bips_paid) = b_balance.royalties(royalty_responses, config.max_royalty_bps)?;
 
             res = res.add_attribute("Total bips paid by seller from sale proceeds", bips_paid.to_string());
 
@@ -754,7 +1489,7 @@ pub fn execute_buy_listing(
             )?;
 
             // Get Royalty CosmosMSgs & subtract royalty amounts from Listing
-            let (royalty_msgs, bips_paid) = l_balance.royalties(royalty_responses)?;
+            let (royalty_msgs, bips_paid) = l_balance.royalties(royalty_responses, config.max_royalty_bps)?;
 
             res = res.add_attribute("Total bips paid by buyer from purchased assets", bips_paid.to_string());
 
@@ -765,54 +1500,155 @@ pub fn execute_buy_listing(
         }
     };
 
-    // Delete Old Listing
-    listingz().remove(deps.storage, (&the_listing.creator, listing_id))?;
-    // Save new Listing with
-    // - Listing Buyer in key & creator
-    // - Community Pool fee added
-    // - Any NFT royalty payments removed
-    listingz().save(
-        deps.storage,
-        (buyer, listing_id),
-        &Listing {
-            creator: buyer.clone(),
-            claimant: Some(buyer.clone()),
-            status: Status::Closed,
-            fee_amount: l_fee_coin,
-            for_sale: final_listing_balance,
-            ..the_listing
-        },
-    )?;
+    // Credit fees to the Fee Ledger, sent out later by SweepFees
+    for fee in l_fee_coin.iter().chain(b_fee_coin.iter()).chain(flat_fee_coin.iter()) {
+        accrue_fee(deps.storage, fee)?;
+    }
 
-    // Delete Old Bucket
-    BUCKETS.remove(deps.storage, (buyer.clone(), bucket_id));
-    // Save new Bucket with
-    // - Listing Seller in key & owner
-    // - Community Pool fee added
-    // - Any NFT royalty payments removed
-    BUCKETS.save(
-        deps.storage,
-        (the_listing.creator.clone(), bucket_id),
-        &Bucket {
-            owner: the_listing.creator,
-            funds: final_bucket_balance,
-            fee_amount: b_fee_coin,
-        },
-    )?;
+    if let Some(flat_fee) = flat_fee_coin {
+        res = res.add_attribute("flat_fee", flat_fee.to_string());
+    }
 
-    res = res
-        .add_attribute("action", "buy_listing")
-        .add_attribute("bucket_used", bucket_id.to_string())
-        .add_attribute("listing_purchased:", listing_id.to_string());
+    Ok(Settlement {
+        res,
+        sold_fee: l_fee_coin,
+        buyer_gets: final_listing_balance,
+        payment_fee: b_fee_coin,
+        seller_gets: final_bucket_balance,
+    })
+}
+
+/// What a Listing is paid with
+enum Payment {
+    /// Goes to the seller to withdraw
+    Bucket(Bucket),
+    /// Already held by this contract, sent to the seller right away
+    Funds(GenericBalance),
+}
+
+/// Trades `sold` (all or part of `the_listing`) for `payment`
+/// - Fees are credited to the Fee Ledger, royalties are paid out of both sides
+/// - A Bucket goes to the seller, Funds are sent to the seller
+/// - `sold` goes to `buyer` as a Closed Listing to withdraw,
+///   or is sent right away for partial fill Listings & Funds
+/// - Flat fee (if any) comes from `flat_fee_from`
+/// - NFTs listed by approval are pulled from the seller first
+#[allow(clippy::too_many_arguments)]
+fn settle_sale(
+    mut deps: DepsMut,
+    env: &Env,
+    buyer: &Addr,
+    flat_fee_from: FlatFeeFrom,
+    the_listing: Listing,
+    payment: Payment,
+    sold: GenericBalance,
+    remaining: Option<GenericBalance>,
+) -> Result<Response, ContractError> {
+    let listing_id = the_listing.id;
+    let paid = match &payment {
+        Payment::Bucket(bucket) => &bucket.funds,
+        Payment::Funds(funds) => funds,
+    };
+
+    // NFTs listed by approval are pulled from the seller before anything is sent
+    let pulls = pull_approved(deps.as_ref(), env, &the_listing)?;
+
+    let Settlement {
+        mut res,
+        sold_fee: l_fee_coin,
+        buyer_gets: final_listing_balance,
+        payment_fee: b_fee_coin,
+        seller_gets: final_bucket_balance,
+    } = settle_trade(deps.branch(), flat_fee_from, &sold, paid)?;
+    res.messages.splice(0..0, pulls.into_iter().map(SubMsg::new));
+
+    if the_listing.partial_fill.is_some() || matches!(payment, Payment::Funds(_)) {
+        // Partial fills & purchases with Funds are sent to the buyer right away,
+        // the rest of a partial fill stays listed until bought or expired
+        res = res.add_messages(send_tokens_cosmos(buyer, &final_listing_balance)?);
+
+        match remaining {
+            Some(for_sale) => {
+                listingz().replace(
+                    deps.storage,
+                    (&the_listing.creator, listing_id),
+                    Some(&Listing {
+                        for_sale,
+                        ..the_listing.clone()
+                    }),
+                    Some(&the_listing),
+                )?;
+            }
+            None => {
+                listingz().remove(deps.storage, (&the_listing.creator, listing_id))?;
+            }
+        }
+
+        if the_listing.partial_fill.is_some() {
+            res = res
+                .add_attribute("quantity", sold.single_fungible().unwrap_or_default().to_string());
+        }
+    } else {
+        // Delete Old Listing
+        listingz().remove(deps.storage, (&the_listing.creator, listing_id))?;
+        // Save new Listing with
+        // - Listing Buyer in key & creator
+        // - Community Pool fee added
+        // - Any NFT royalty payments removed
+        listingz().save(
+            deps.storage,
+            (buyer, listing_id),
+            &Listing {
+                creator: buyer.clone(),
+                claimant: Some(buyer.clone()),
+                status: Status::Closed,
+                fee_amount: l_fee_coin,
+                for_sale: final_listing_balance,
+                approved_nfts: None,
+                flat_fee: None,
+                ..the_listing.clone()
+            },
+        )?;
+    }
+
+    match payment {
+        Payment::Bucket(the_bucket) => {
+            let bucket_id = the_bucket.id;
+            // Delete Old Bucket
+            bucketz().remove(deps.storage, (buyer.clone(), bucket_id))?;
+            // Save new Bucket with
+            // - Listing Seller in key & owner
+            // - Community Pool fee added
+            // - Any NFT royalty payments removed
+            bucketz().save(
+                deps.storage,
+                (the_listing.creator.clone(), bucket_id),
+                &Bucket {
+                    owner: the_listing.creator,
+                    id: bucket_id,
+                    funds: final_bucket_balance,
+                    fee_amount: b_fee_coin,
+                },
+            )?;
+
+            res = res.add_attribute("bucket_used", bucket_id.to_string());
+        }
+        Payment::Funds(_) => {
+            res =
+                res.add_messages(send_tokens_cosmos(&the_listing.creator, &final_bucket_balance)?);
+        }
+    }
+
+    res = res.add_attribute("listing_purchased:", listing_id.to_string());
 
     Ok(res)
 }
 
 pub fn execute_withdraw_purchased(
     deps: DepsMut,
-    env: &Env,
     withdrawer: &Addr,
     listing_id: u64,
+    destination: Option<WithdrawDestinationUnvalidated>,
 ) -> Result<Response, ContractError> {
     // Get listing
     let Some((_pk, the_listing)): Option<(_, Listing)> = listingz().idx.id.item(deps.storage, listing_id)? else {
@@ -823,7 +1659,7 @@ pub fn execute_withdraw_purchased(
     let listing_claimant = the_listing.claimant.clone().ok_or(ContractError::Unauthorized {})?;
 
     // Check that withdrawer is the claimant
-    if withdrawer != &listing_claimant {
+    if *withdrawer != listing_claimant {
         return Err(ContractError::Unauthorized {});
     };
 
@@ -835,10 +1671,1292 @@ pub fn execute_withdraw_purchased(
     // Delete Listing
     listingz().remove(deps.storage, (&listing_claimant, listing_id))?;
 
-    let withdraw_msgs = the_listing.withdraw_msgs(env.contract.address.clone())?;
+    let destination = destination.map(|d| d.validate(&deps)).transpose()?;
+    let withdraw_msgs = the_listing.withdraw_msgs(destination.as_ref())?;
 
     Ok(Response::new()
         .add_attribute("Action", "withdraw_purchased")
         .add_attribute("listing_id", listing_id.to_string())
         .add_messages(withdraw_msgs))
 }
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Offers
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Errors unless `listing` can be bought right now
+fn check_purchasable(env: &Env, listing: &Listing) -> Result<(), ContractError> {
+    if listing.status != Status::FinalizedReady || listing.claimant.is_some() || listing.auction.is_some() {
+        return Err(ContractError::NotPurchasable {});
+    }
+
+    if listing.expiration_time.is_some_and(|exp| env.block.time > exp) {
+        return Err(ContractError::Expired {});
+    }
+
+    Ok(())
+}
+
+/// Locks a Bucket as an Offer on a Listing for `seconds`
+/// - Expiration must be within the same Config bounds as Listings
+pub fn execute_make_offer(
+    deps: DepsMut,
+    env: &Env,
+    bidder: &Addr,
+    listing_id: u64,
+    bucket_id: u64,
+    seconds: u64,
+) -> Result<Response, ContractError> {
+    // Ensure bucket exists & bidder is owner
+    let Some(the_bucket) = bucketz().may_load(deps.storage, (bidder.clone(), bucket_id))? else {
+        return Err(ContractError::NotFound { typ: "Bucket".to_string(), id: bucket_id.to_string() });
+    };
+
+    // A Bucket backs one Offer at a time
+    check_bucket_unlocked(deps.storage, bucket_id)?;
+
+    let Some((_pk, the_listing)) = listingz().idx.id.item(deps.storage, listing_id)? else {
+        return Err(ContractError::NotFound { typ: "Listing".to_string(), id: listing_id.to_string() });
+    };
+
+    if the_listing.creator == *bidder {
+        return Err(ContractError::GenericError("Cannot make an offer on your own Listing".to_string()));
+    }
+
+    check_purchasable(env, &the_listing)?;
+
+    // Only the whitelisted buyer can make offers on a private Listing
+    if the_listing.whitelisted_buyer.as_ref().is_some_and(|wl| wl != bidder) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Flat fee is fixed now, accepting later charges this amount
+    let flat_fee = FEE_SCHEDULE.load(deps.storage)?.flat_fee;
+    check_offer_covers_flat_fee(deps.as_ref(), flat_fee.as_ref(), &the_listing, &the_bucket)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    if !(config.min_expiration_seconds..=config.max_expiration_seconds).contains(&seconds) {
+        return Err(ContractError::InvalidExpiration {});
+    }
+
+    let expiration = env.block.time.plus_seconds(seconds);
+
+    offerz().save(
+        deps.storage,
+        bucket_id,
+        &Offer {
+            bidder: bidder.clone(),
+            bucket_id,
+            listing_id,
+            expiration_time: expiration,
+            flat_fee,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "make_offer")
+        .add_attribute("listing_id", listing_id.to_string())
+        .add_attribute("bucket_id", bucket_id.to_string())
+        .add_attribute("expiration", expiration.to_string()))
+}
+
+/// Removes an Offer & unlocks its Bucket
+/// - Bidder can retract at any time, including after expiration or a sale
+pub fn execute_retract_offer(
+    deps: DepsMut,
+    bidder: &Addr,
+    bucket_id: u64,
+) -> Result<Response, ContractError> {
+    let Some(offer) = offerz().may_load(deps.storage, bucket_id)? else {
+        return Err(ContractError::NotFound { typ: "Offer".to_string(), id: bucket_id.to_string() });
+    };
+
+    if offer.bidder != *bidder {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    offerz().remove(deps.storage, bucket_id)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "retract_offer")
+        .add_attribute("listing_id", offer.listing_id.to_string())
+        .add_attribute("bucket_id", bucket_id.to_string()))
+}
+
+/// Sells the Listing to the bidder for the offered Bucket,
+/// settled the same as `BuyListing`
+/// - Flat fee (if any) stored on the Offer is taken out of the offered Bucket
+pub fn execute_accept_offer(
+    deps: DepsMut,
+    env: &Env,
+    seller: &Addr,
+    listing_id: u64,
+    bucket_id: u64,
+) -> Result<Response, ContractError> {
+    let Some(offer) = offerz().may_load(deps.storage, bucket_id)? else {
+        return Err(ContractError::NotFound { typ: "Offer".to_string(), id: bucket_id.to_string() });
+    };
+
+    if offer.listing_id != listing_id {
+        return Err(ContractError::GenericError("Offer is not for this Listing".to_string()));
+    }
+
+    if env.block.time > offer.expiration_time {
+        return Err(ContractError::OfferExpired {});
+    }
+
+    let Some(the_listing) = listingz().may_load(deps.storage, (seller, listing_id))? else {
+        return Err(ContractError::NotFound { typ: "Listing".to_string(), id: listing_id.to_string() });
+    };
+
+    check_purchasable(env, &the_listing)?;
+
+    // Locked Bucket, so it still covers the flat fee checked when the Offer was made
+    let the_bucket = bucketz().load(deps.storage, (offer.bidder.clone(), bucket_id))?;
+
+    offerz().remove(deps.storage, bucket_id)?;
+
+    // The whole Listing is sold, including what's left of a partial fill Listing
+    let sold = the_listing.for_sale.clone();
+    let res = settle_sale(
+        deps,
+        env,
+        &offer.bidder,
+        FlatFeeFrom::Payment(offer.flat_fee.as_ref()),
+        the_listing,
+        Payment::Bucket(the_bucket),
+        sold,
+        None,
+    )?;
+
+    Ok(res.add_attribute("action", "accept_offer"))
+}
+
+/// Errors if the Bucket can't pay `flat_fee` (if any) due on buying the Listing
+fn check_offer_covers_flat_fee(
+    deps: Deps,
+    flat_fee: Option<&FlatFee>,
+    the_listing: &Listing,
+    the_bucket: &Bucket,
+) -> Result<(), ContractError> {
+    if let Some(due) = flat_fee_due(deps, flat_fee, &the_listing.for_sale, &the_bucket.funds)? {
+        if due.asset.amount_in(&the_bucket.funds).unwrap_or_default() < due.amount {
+            return Err(ContractError::GenericError(format!(
+                "Offered Bucket must hold the flat fee of {due}"
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Errors if the Bucket backs an Offer on a Listing that can still be bought
+/// - An Offer on a Listing that's been sold or removed no longer locks its Bucket,
+///   it is removed instead
+fn check_bucket_unlocked(storage: &mut dyn Storage, bucket_id: u64) -> Result<(), ContractError> {
+    let Some(offer) = offerz().may_load(storage, bucket_id)? else {
+        return Ok(());
+    };
+
+    let open = listingz()
+        .idx
+        .id
+        .item(storage, offer.listing_id)?
+        .is_some_and(|(_pk, listing)| listing.claimant.is_none());
+    if open {
+        return Err(ContractError::BucketLocked {});
+    }
+
+    offerz().remove(storage, bucket_id)?;
+
+    Ok(())
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Auctions
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Places a bid on an auctioned Listing, escrowing `funds`
+/// - `funds` must be the Listing's ask asset
+/// - First bid must meet the reserve (`Listing.ask`), later bids must beat
+///   the highest by `min_increment`
+/// - The previous highest bidder is refunded
+/// - A bid in the last `extension_seconds` pushes the end back
+pub fn execute_place_bid(
+    deps: DepsMut,
+    env: &Env,
+    bidder: &Addr,
+    funds: &Balance,
+    listing_id: u64,
+) -> Result<Response, ContractError> {
+    let Some((_pk, the_listing)) = listingz().idx.id.item(deps.storage, listing_id)? else {
+        return Err(ContractError::NotFound {
+            typ: "Listing".to_string(),
+            id: listing_id.to_string(),
+        });
+    };
+
+    let Some(Auction::English(english)) = the_listing.auction.clone() else {
+        return Err(ContractError::GenericError("Listing is not an English auction".to_string()));
+    };
+
+    if the_listing.status != Status::FinalizedReady || the_listing.claimant.is_some() {
+        return Err(ContractError::NotPurchasable {});
+    }
+
+    // Finalized Listings always have an expiration
+    let Some(end) = the_listing.expiration_time else {
+        return Err(ContractError::NotPurchasable {});
+    };
+    if env.block.time > end {
+        return Err(ContractError::Expired {});
+    }
+
+    if the_listing.creator == *bidder {
+        return Err(ContractError::GenericError("Cannot bid on your own Listing".to_string()));
+    }
+
+    // Only the whitelisted buyer can bid on a private Listing
+    if the_listing.whitelisted_buyer.as_ref().is_some_and(|wl| wl != bidder) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Bid must be a single amount of the ask asset
+    let bid = GenericBalance::from_balance(funds);
+    let amount = bid.single_fungible().unwrap_or_default();
+    if amount.is_zero() || bid != the_listing.ask.with_amount(amount) {
+        return Err(ContractError::GenericError("Bid must be the Listing's ask asset".to_string()));
+    }
+
+    let min_bid = match &english.highest_bid {
+        None => the_listing.ask.single_fungible().unwrap_or_default(),
+        Some(highest) => highest.amount.saturating_add(english.min_increment),
+    };
+    if amount < min_bid {
+        return Err(ContractError::GenericError(format!("Bid must be at least {min_bid}")));
+    }
+
+    let mut res = Response::new()
+        .add_attribute("action", "place_bid")
+        .add_attribute("listing_id", listing_id.to_string())
+        .add_attribute("bid", amount.to_string());
+
+    // Refund the outbid bidder
+    if let Some(outbid) = &english.highest_bid {
+        res = res.add_messages(send_tokens_cosmos(
+            &outbid.bidder,
+            &the_listing.ask.with_amount(outbid.amount),
+        )?);
+    }
+
+    // Anti-sniping, a late bid pushes the end back
+    let extended = env.block.time.plus_seconds(english.extension_seconds);
+    let expiration_time = if extended > end {
+        res = res.add_attribute("extended_to", extended.to_string());
+        extended
+    } else {
+        end
+    };
+
+    listingz().replace(
+        deps.storage,
+        (&the_listing.creator, listing_id),
+        Some(&Listing {
+            expiration_time: Some(expiration_time),
+            auction: Some(Auction::English(EnglishAuction {
+                highest_bid: Some(Bid {
+                    bidder: bidder.clone(),
+                    amount,
+                }),
+                ..english
+            })),
+            ..the_listing.clone()
+        }),
+        Some(&the_listing),
+    )?;
+
+    Ok(res)
+}
+
+/// Commits a hidden bid on a sealed bid auction, escrowing `funds` as the deposit
+/// - `funds` must be the Listing's ask asset & at least the reserve
+/// - One commitment per bidder, only during the bid phase
+/// - No more than `Config.max_sealed_bids` commitments escrowed on the Listing
+pub fn execute_commit_bid(
+    deps: DepsMut,
+    env: &Env,
+    bidder: &Addr,
+    funds: &Balance,
+    listing_id: u64,
+    commitment: Binary,
+) -> Result<Response, ContractError> {
+    let Some((_pk, the_listing)) = listingz().idx.id.item(deps.storage, listing_id)? else {
+        return Err(ContractError::NotFound {
+            typ: "Listing".to_string(),
+            id: listing_id.to_string(),
+        });
+    };
+
+    let Some(Auction::SealedBid(sealed)) = the_listing.auction.clone() else {
+        return Err(ContractError::GenericError("Listing is not a sealed bid auction".to_string()));
+    };
+
+    if the_listing.status != Status::FinalizedReady || the_listing.claimant.is_some() {
+        return Err(ContractError::NotPurchasable {});
+    }
+
+    // Bid phase ends at expiration
+    let Some(end) = the_listing.expiration_time else {
+        return Err(ContractError::NotPurchasable {});
+    };
+    if env.block.time > end {
+        return Err(ContractError::Expired {});
+    }
+
+    if the_listing.creator == *bidder {
+        return Err(ContractError::GenericError("Cannot bid on your own Listing".to_string()));
+    }
+
+    // Only the whitelisted buyer can bid on a private Listing
+    if the_listing.whitelisted_buyer.as_ref().is_some_and(|wl| wl != bidder) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // sha256 hash
+    if commitment.len() != 32 {
+        return Err(ContractError::GenericError("Commitment must be a sha256 hash".to_string()));
+    }
+
+    if SEALED_BIDS.has(deps.storage, (listing_id, bidder)) {
+        return Err(ContractError::GenericError("Bid already committed".to_string()));
+    }
+
+    let max_sealed_bids = CONFIG.load(deps.storage)?.max_sealed_bids;
+    if sealed.commits >= max_sealed_bids {
+        return Err(ContractError::GenericError(format!(
+            "Listing already has the max of {max_sealed_bids} commitments"
+        )));
+    }
+
+    // Commitments are bound to the bidder, a copied one could never be revealed
+    if SEALED_BID_COMMITMENTS.has(deps.storage, (listing_id, commitment.as_slice())) {
+        return Err(ContractError::GenericError(
+            "Commitment already submitted by another bidder".to_string(),
+        ));
+    }
+
+    // Deposit must be a single amount of the ask asset, at least the reserve
+    let deposit = GenericBalance::from_balance(funds);
+    let amount = deposit.single_fungible().unwrap_or_default();
+    if amount.is_zero() || deposit != the_listing.ask.with_amount(amount) {
+        return Err(ContractError::GenericError(
+            "Deposit must be the Listing's ask asset".to_string(),
+        ));
+    }
+    if the_listing.ask.single_fungible().is_some_and(|reserve| amount < reserve) {
+        return Err(ContractError::GenericError(
+            "Deposit must be at least the reserve".to_string(),
+        ));
+    }
+
+    SEALED_BID_COMMITMENTS.save(deps.storage, (listing_id, commitment.as_slice()), bidder)?;
+    SEALED_BID_TERMS.save(
+        deps.storage,
+        listing_id,
+        &SealedBidTerms {
+            seller: the_listing.creator.clone(),
+            ask: the_listing.ask.clone(),
+            forfeit_unrevealed: sealed.forfeit_unrevealed,
+            reveal_end: end.plus_seconds(sealed.reveal_seconds),
+        },
+    )?;
+    SEALED_BIDS.save(
+        deps.storage,
+        (listing_id, bidder),
+        &SealedBid {
+            bidder: bidder.clone(),
+            commitment,
+            deposit: amount,
+            revealed: false,
+        },
+    )?;
+
+    listingz().replace(
+        deps.storage,
+        (&the_listing.creator, listing_id),
+        Some(&Listing {
+            auction: Some(Auction::SealedBid(SealedBidAuction {
+                commits: sealed.commits + 1,
+                ..sealed
+            })),
+            ..the_listing.clone()
+        }),
+        Some(&the_listing),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "commit_bid")
+        .add_attribute("listing_id", listing_id.to_string())
+        .add_attribute("deposit", amount.to_string()))
+}
+
+/// Reveals a sealed bid during the reveal phase
+/// - `amount` & `salt` must hash to the commitment
+/// - `amount` must be at least the reserve & no more than the deposit
+/// - Deposits that aren't the highest reveal are refunded right away
+pub fn execute_reveal_bid(
+    deps: DepsMut,
+    env: &Env,
+    bidder: &Addr,
+    listing_id: u64,
+    amount: Uint128,
+    salt: &str,
+) -> Result<Response, ContractError> {
+    let Some((_pk, the_listing)) = listingz().idx.id.item(deps.storage, listing_id)? else {
+        return Err(ContractError::NotFound {
+            typ: "Listing".to_string(),
+            id: listing_id.to_string(),
+        });
+    };
+
+    let Some(Auction::SealedBid(sealed)) = the_listing.auction.clone() else {
+        return Err(ContractError::GenericError("Listing is not a sealed bid auction".to_string()));
+    };
+
+    // Reveal phase runs for `reveal_seconds` after the bid phase
+    let Some(end) = the_listing.expiration_time else {
+        return Err(ContractError::NotPurchasable {});
+    };
+    if env.block.time <= end || env.block.time > end.plus_seconds(sealed.reveal_seconds) {
+        return Err(ContractError::GenericError("Not in the reveal phase".to_string()));
+    }
+
+    let Some(sealed_bid) = SEALED_BIDS.may_load(deps.storage, (listing_id, bidder))? else {
+        return Err(ContractError::NotFound {
+            typ: "Sealed bid".to_string(),
+            id: listing_id.to_string(),
+        });
+    };
+
+    if sealed_bid.revealed {
+        return Err(ContractError::GenericError("Bid already revealed".to_string()));
+    }
+
+    if sealed_bid.commitment != SealedBidAuction::commitment(listing_id, bidder, amount, salt) {
+        return Err(ContractError::GenericError("Reveal does not match commitment".to_string()));
+    }
+
+    let reserve = the_listing.ask.single_fungible().unwrap_or_default();
+    if amount < reserve || amount > sealed_bid.deposit {
+        return Err(ContractError::GenericError(format!(
+            "Bid must be between {reserve} and the deposit of {}",
+            sealed_bid.deposit
+        )));
+    }
+
+    let mut res = Response::new()
+        .add_attribute("action", "reveal_bid")
+        .add_attribute("listing_id", listing_id.to_string())
+        .add_attribute("bid", amount.to_string());
+
+    let mut commits = sealed.commits;
+    let highest_bid = match &sealed.highest_bid {
+        // Earliest reveal wins ties, the lower bid is refunded
+        Some(highest) if amount <= highest.amount => {
+            remove_sealed_bid(deps.storage, listing_id, &sealed_bid);
+            commits -= 1;
+            res = res.add_messages(send_tokens_cosmos(
+                bidder,
+                &the_listing.ask.with_amount(sealed_bid.deposit),
+            )?);
+            sealed.highest_bid.clone()
+        }
+        // New highest, the outbid deposit is refunded
+        outbid => {
+            if let Some(outbid) = outbid {
+                let outbid_deposit =
+                    SEALED_BIDS.load(deps.storage, (listing_id, &outbid.bidder))?;
+                remove_sealed_bid(deps.storage, listing_id, &outbid_deposit);
+                commits -= 1;
+                res = res.add_messages(send_tokens_cosmos(
+                    &outbid.bidder,
+                    &the_listing.ask.with_amount(outbid_deposit.deposit),
+                )?);
+            }
+            SEALED_BIDS.save(
+                deps.storage,
+                (listing_id, bidder),
+                &SealedBid {
+                    revealed: true,
+                    ..sealed_bid
+                },
+            )?;
+            Some(Bid {
+                bidder: bidder.clone(),
+                amount,
+            })
+        }
+    };
+
+    listingz().replace(
+        deps.storage,
+        (&the_listing.creator, listing_id),
+        Some(&Listing {
+            auction: Some(Auction::SealedBid(SealedBidAuction {
+                commits,
+                highest_bid,
+                ..sealed
+            })),
+            ..the_listing.clone()
+        }),
+        Some(&the_listing),
+    )?;
+
+    Ok(res)
+}
+
+/// Removes a sealed bid & its commitment
+fn remove_sealed_bid(storage: &mut dyn Storage, listing_id: u64, sealed_bid: &SealedBid) {
+    SEALED_BIDS.remove(storage, (listing_id, &sealed_bid.bidder));
+    SEALED_BID_COMMITMENTS.remove(storage, (listing_id, sealed_bid.commitment.as_slice()));
+}
+
+/// Removes a sealed bid & its commitment, and the auction's terms with the last one
+fn remove_sealed_bid_and_terms(
+    storage: &mut dyn Storage,
+    listing_id: u64,
+    sealed_bid: &SealedBid,
+) -> StdResult<()> {
+    remove_sealed_bid(storage, listing_id, sealed_bid);
+
+    let none_left =
+        SEALED_BIDS.prefix(listing_id).keys(storage, None, None, Order::Ascending).next().is_none();
+    if none_left {
+        SEALED_BID_TERMS.remove(storage, listing_id);
+    }
+
+    Ok(())
+}
+
+/// Sends an unrevealed deposit of a sealed bid auction back to its bidder,
+/// or to the seller if the auction forfeits unrevealed deposits
+/// - Anyone can call this for any bidder, once the reveal phase has ended
+/// - The winning (revealed) deposit is settled by `SettleAuction`
+pub fn execute_claim_deposit(
+    deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    listing_id: u64,
+    bidder: Option<String>,
+) -> Result<Response, ContractError> {
+    let bidder = match bidder {
+        Some(bidder) => deps.api.addr_validate(&bidder)?,
+        None => sender.clone(),
+    };
+
+    let Some(terms) = SEALED_BID_TERMS.may_load(deps.storage, listing_id)? else {
+        return Err(ContractError::NotFound {
+            typ: "Sealed bid".to_string(),
+            id: listing_id.to_string(),
+        });
+    };
+
+    if env.block.time <= terms.reveal_end {
+        return Err(ContractError::NotExpired {
+            x: terms.reveal_end.seconds().to_string(),
+        });
+    }
+
+    let Some(sealed_bid) = SEALED_BIDS.may_load(deps.storage, (listing_id, &bidder))? else {
+        return Err(ContractError::NotFound {
+            typ: "Sealed bid".to_string(),
+            id: listing_id.to_string(),
+        });
+    };
+
+    // Other reveals were refunded when outbid, only the winner's is left
+    if sealed_bid.revealed {
+        return Err(ContractError::GenericError(
+            "Winning deposit is settled by SettleAuction".to_string(),
+        ));
+    }
+
+    remove_sealed_bid_and_terms(deps.storage, listing_id, &sealed_bid)?;
+
+    // Unsettled Listing no longer escrows this deposit
+    if let Some((_pk, the_listing)) = listingz().idx.id.item(deps.storage, listing_id)? {
+        if let Some(Auction::SealedBid(sealed)) = the_listing.auction.clone() {
+            listingz().replace(
+                deps.storage,
+                (&the_listing.creator, listing_id),
+                Some(&Listing {
+                    auction: Some(Auction::SealedBid(SealedBidAuction {
+                        commits: sealed.commits - 1,
+                        ..sealed
+                    })),
+                    ..the_listing.clone()
+                }),
+                Some(&the_listing),
+            )?;
+        }
+    }
+
+    let to = if terms.forfeit_unrevealed {
+        &terms.seller
+    } else {
+        &bidder
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "claim_deposit")
+        .add_attribute("listing_id", listing_id.to_string())
+        .add_attribute("bidder", bidder.to_string())
+        .add_attribute("forfeited", terms.forfeit_unrevealed.to_string())
+        .add_messages(send_tokens_cosmos(to, &terms.ask.with_amount(sealed_bid.deposit))?))
+}
+
+/// Anyone can call this once an English auction has ended,
+/// or a sealed bid auction's reveal phase has ended
+/// - No bids: `for_sale` goes back to the creator
+/// - Otherwise the winning bid is settled like a sale, `for_sale` is sent
+///   to the winner & the bid (less fee & royalties) to the creator
+/// - Only the winning sealed bid deposit is handled here, its surplus is refunded.
+///   Unrevealed deposits are claimed with `ClaimDeposit`
+/// - Flat fee (if any) stored when the auction was finalized is taken out of the
+///   winning bid, up to what the bid holds in that denom, so settling never needs
+///   funds & can't get stuck
+pub fn execute_settle_auction(
+    mut deps: DepsMut,
+    env: &Env,
+    listing_id: u64,
+) -> Result<Response, ContractError> {
+    let Some((_pk, the_listing)) = listingz().idx.id.item(deps.storage, listing_id)? else {
+        return Err(ContractError::NotFound {
+            typ: "Listing".to_string(),
+            id: listing_id.to_string(),
+        });
+    };
+
+    if the_listing.status != Status::FinalizedReady {
+        return Err(ContractError::NotPurchasable {});
+    }
+
+    let Some(end) = the_listing.expiration_time else {
+        return Err(ContractError::NotPurchasable {});
+    };
+
+    // Sealed bid auctions end after the reveal phase
+    let (winner, ends) = match &the_listing.auction {
+        Some(Auction::English(english)) => (english.highest_bid.clone(), end),
+        Some(Auction::SealedBid(sealed)) => {
+            (sealed.highest_bid.clone(), end.plus_seconds(sealed.reveal_seconds))
+        }
+        _ => {
+            return Err(ContractError::GenericError(
+                "Listing is not an English or sealed bid auction".to_string(),
+            ));
+        }
+    };
+
+    if env.block.time <= ends {
+        return Err(ContractError::NotExpired {
+            x: ends.seconds().to_string(),
+        });
+    }
+
+    listingz().remove(deps.storage, (&the_listing.creator, listing_id))?;
+
+    let Some(winner) = winner else {
+        return Ok(Response::new()
+            .add_attribute("action", "settle_auction")
+            .add_attribute("listing_id", listing_id.to_string())
+            .add_attribute("winner", "none")
+            .add_messages(send_tokens_cosmos(&the_listing.creator, &the_listing.for_sale)?));
+    };
+
+    // Winning sealed bid's deposit surplus is refunded
+    let mut deposit_msgs = vec![];
+    if let Some(Auction::SealedBid(_)) = &the_listing.auction {
+        let sealed_bid = SEALED_BIDS.load(deps.storage, (listing_id, &winner.bidder))?;
+        remove_sealed_bid_and_terms(deps.storage, listing_id, &sealed_bid)?;
+
+        let surplus = sealed_bid.deposit - winner.amount;
+        if !surplus.is_zero() {
+            let refund = the_listing.ask.with_amount(surplus);
+            deposit_msgs = send_tokens_cosmos(&winner.bidder, &refund)?;
+        }
+    }
+
+    let winning_bid = the_listing.ask.with_amount(winner.amount);
+
+    let Settlement {
+        res,
+        buyer_gets,
+        seller_gets,
+        ..
+    } = settle_trade(
+        deps.branch(),
+        FlatFeeFrom::Payment(the_listing.flat_fee.as_ref()),
+        &the_listing.for_sale,
+        &winning_bid,
+    )?;
+
+    Ok(res
+        .add_attribute("action", "settle_auction")
+        .add_attribute("listing_id", listing_id.to_string())
+        .add_attribute("winner", winner.bidder.to_string())
+        .add_attribute("winning_bid", winner.amount.to_string())
+        .add_messages(send_tokens_cosmos(&winner.bidder, &buyer_gets)?)
+        .add_messages(send_tokens_cosmos(&the_listing.creator, &seller_gets)?)
+        .add_messages(deposit_msgs))
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Collection Offers
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Escrows `funds` as a bid for `quantity` NFTs from `collection`
+/// - `funds` are split evenly into the price paid per NFT
+/// - Expiration must be within the same Config bounds as Listings
+#[allow(clippy::too_many_arguments)]
+pub fn execute_create_collection_offer(
+    deps: DepsMut,
+    env: &Env,
+    bidder: &Addr,
+    funds: &Balance,
+    offer_id: u64,
+    collection: &str,
+    quantity: u32,
+    seconds: u64,
+) -> Result<Response, ContractError> {
+    // Check ID value
+    max(offer_id)?;
+
+    // Error if funds contains duplicates or 0 balances
+    funds.normalized_check()?;
+
+    // If offer_id has been used, it cannot be used again
+    if COLLECTION_OFFER_ID_USED.has(deps.storage, offer_id) {
+        return Err(ContractError::IdAlreadyExists {});
+    }
+
+    let collection = deps
+        .api
+        .addr_validate(collection)
+        .map_err(|_e| ContractError::GenericError("Invalid collection".to_string()))?;
+
+    if quantity == 0 {
+        return Err(ContractError::GenericError("Quantity cannot be 0".to_string()));
+    }
+
+    // Price per NFT, funds must split evenly
+    let price = {
+        let quantity = Uint128::from(quantity);
+        let mut price = GenericBalance::from_balance(funds);
+        let amounts = price
+            .native
+            .iter_mut()
+            .map(|n| &mut n.amount)
+            .chain(price.cw20.iter_mut().map(|c| &mut c.amount));
+        for amount in amounts {
+            if !(*amount % quantity).is_zero() {
+                return Err(ContractError::GenericError(
+                    "Funds must split evenly across quantity".to_string(),
+                ));
+            }
+            *amount /= quantity;
+        }
+        price
+    };
+
+    let config = CONFIG.load(deps.storage)?;
+    if !(config.min_expiration_seconds..=config.max_expiration_seconds).contains(&seconds) {
+        return Err(ContractError::InvalidExpiration {});
+    }
+
+    let expiration = env.block.time.plus_seconds(seconds);
+
+    collection_offerz().save(
+        deps.storage,
+        offer_id,
+        &CollectionOffer {
+            id: offer_id,
+            bidder: bidder.clone(),
+            collection: collection.clone(),
+            price,
+            quantity,
+            filled: 0,
+            expiration_time: expiration,
+        },
+    )?;
+
+    // Mark this ID as used
+    COLLECTION_OFFER_ID_USED.save(deps.storage, offer_id, &true)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_collection_offer")
+        .add_attribute("offer_id", offer_id.to_string())
+        .add_attribute("collection", collection.to_string())
+        .add_attribute("quantity", quantity.to_string()))
+}
+
+/// Sells `nft` into a Collection Offer
+/// - Seller gets `price` less the fee & royalties, bidder gets the NFT, both right away
+/// - There is no flat fee, nothing can be sent in along with an NFT
+pub fn execute_fill_collection_offer(
+    deps: DepsMut,
+    env: &Env,
+    seller: &Addr,
+    nft: Nft,
+    offer_id: u64,
+) -> Result<Response, ContractError> {
+    let Some(offer) = collection_offerz().may_load(deps.storage, offer_id)? else {
+        return Err(ContractError::NotFound { typ: "Collection Offer".to_string(), id: offer_id.to_string() });
+    };
+
+    if nft.contract_address != offer.collection {
+        return Err(ContractError::GenericError("NFT is not from the offer's collection".to_string()));
+    }
+
+    if offer.bidder == *seller {
+        return Err(ContractError::GenericError("Cannot fill your own offer".to_string()));
+    }
+
+    if env.block.time > offer.expiration_time {
+        return Err(ContractError::OfferExpired {});
+    }
+
+    // Fee on the price (paid by the seller out of the proceeds)
+    let fee_schedule: FeeSchedule = FEE_SCHEDULE.load(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
+    let (fee_coin, mut proceeds) = calc_fee_coin(&fee_schedule, config.fee_bps, &offer.price)?;
+
+    if let Some(fee) = &fee_coin {
+        accrue_fee(deps.storage, fee)?;
+    }
+
+    // Seller pays the collection's royalties out of the proceeds
+    let Some(royalty_reg): Option<Addr> = ROYALTY_REGISTRY.load(deps.storage)? else {
+        return Err(ContractError::GenericError("No royalty registry".to_string()));
+    };
+    let royalty_responses: Vec<Option<RoyaltyInfo>> = deps.querier.query_wasm_smart(
+        royalty_reg,
+        &RoyaltyQueryMsg::RoyaltyInfoMulti { nft_contracts: vec![offer.collection.to_string()] },
+    )?;
+    let (royalty_msgs, bips_paid) = proceeds.royalties(royalty_responses, config.max_royalty_bps)?;
+
+    let mut msgs = royalty_msgs;
+    msgs.extend(send_tokens_cosmos(seller, &proceeds)?);
+    msgs.extend(send_tokens_cosmos(&offer.bidder, &GenericBalance::from_nft(nft.clone()))?);
+
+    // Remove once every NFT is filled
+    if offer.quantity > 1 {
+        collection_offerz().save(
+            deps.storage,
+            offer_id,
+            &CollectionOffer {
+                quantity: offer.quantity - 1,
+                filled: offer.filled + 1,
+                ..offer.clone()
+            },
+        )?;
+    } else {
+        collection_offerz().remove(deps.storage, offer_id)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "fill_collection_offer")
+        .add_attribute("offer_id", offer_id.to_string())
+        .add_attribute("token_id", nft.token_id)
+        .add_attribute("remaining", (offer.quantity - 1).to_string())
+        .add_attribute("Total bips paid by seller from sale proceeds", bips_paid.to_string())
+        .add_messages(msgs))
+}
+
+/// Removes a Collection Offer & sends the unfilled escrow back to the bidder
+/// - Bidder can cancel at any time, including after expiration
+pub fn execute_cancel_collection_offer(
+    deps: DepsMut,
+    bidder: &Addr,
+    offer_id: u64,
+) -> Result<Response, ContractError> {
+    let Some(offer) = collection_offerz().may_load(deps.storage, offer_id)? else {
+        return Err(ContractError::NotFound { typ: "Collection Offer".to_string(), id: offer_id.to_string() });
+    };
+
+    if offer.bidder != *bidder {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let msgs = send_tokens_cosmos(bidder, &offer.escrow()?)?;
+
+    collection_offerz().remove(deps.storage, offer_id)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_collection_offer")
+        .add_attribute("offer_id", offer_id.to_string())
+        .add_messages(msgs))
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Fees
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Sends pending fees across the fee routes & zeroes them in the Fee Ledger
+/// - Sweeps every denom in the ledger if `denoms` is `None`
+/// - Denoms with nothing pending are skipped
+pub fn execute_sweep_fees(
+    deps: DepsMut,
+    env: &Env,
+    denoms: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
+    let denoms = match denoms {
+        Some(denoms) => denoms.into_iter().collect::<BTreeSet<String>>().into_iter().collect(),
+        None => FEE_LEDGER
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<String>>>()?,
+    };
+
+    let fee_routes = FEE_ROUTES.load(deps.storage)?;
+
+    let mut msgs = vec![];
+    let mut swept = vec![];
+
+    for denom in denoms {
+        let Some(mut totals) = FEE_LEDGER.may_load(deps.storage, &denom)? else {
+            continue;
+        };
+
+        if totals.pending.is_zero() {
+            continue;
+        }
+
+        let fee = FeeCoin::new(totals.pending, totals.asset.clone());
+        msgs.append(&mut fee_route_msgs(&fee, &fee_routes, &env.contract.address)?);
+        swept.push(fee.to_string());
+
+        totals.pending = Uint128::zero();
+        FEE_LEDGER.save(deps.storage, &denom, &totals)?;
+    }
+
+    if swept.is_empty() {
+        return Err(ContractError::GenericError("No fees to sweep".to_string()));
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "sweep_fees")
+        .add_attribute("swept", swept.join(","))
+        .add_messages(msgs))
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Ownership
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Errors unless `sender` is the current owner
+pub fn assert_owner(storage: &dyn Storage, sender: &Addr) -> Result<(), ContractError> {
+    match OWNER.load(storage)? {
+        Some(owner) if owner == *sender => Ok(()),
+        _ => Err(ContractError::Unauthorized {}),
+    }
+}
+
+pub fn execute_propose_owner(
+    deps: DepsMut,
+    sender: &Addr,
+    new_owner: &str,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, sender)?;
+
+    let valid_new_owner = deps.api.addr_validate(new_owner)?;
+
+    PENDING_OWNER.save(deps.storage, &Some(valid_new_owner.clone()))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "propose_owner")
+        .add_attribute("pending_owner", valid_new_owner))
+}
+
+pub fn execute_accept_ownership(deps: DepsMut, sender: &Addr) -> Result<Response, ContractError> {
+    // Only the pending owner can accept
+    if PENDING_OWNER.load(deps.storage)?.as_ref() != Some(sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    OWNER.save(deps.storage, &Some(sender.clone()))?;
+    PENDING_OWNER.save(deps.storage, &None)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "accept_ownership")
+        .add_attribute("owner", sender))
+}
+
+/// Ownership cannot be recovered after renouncing
+pub fn execute_renounce_ownership(deps: DepsMut, sender: &Addr) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, sender)?;
+
+    OWNER.save(deps.storage, &None)?;
+    PENDING_OWNER.save(deps.storage, &None)?;
+
+    Ok(Response::new().add_attribute("action", "renounce_ownership"))
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Config
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Owner only, updates any provided Config fields
+/// - Errors if the resulting Config is invalid
+#[allow(clippy::too_many_arguments)]
+pub fn execute_update_config(
+    deps: DepsMut,
+    sender: &Addr,
+    min_expiration_seconds: Option<u64>,
+    max_expiration_seconds: Option<u64>,
+    max_num_assets: Option<u32>,
+    max_royalty_bps: Option<u64>,
+    fee_bps: Option<u64>,
+    fee_cycle_seconds: Option<u64>,
+    expiry_bounty: Option<Coin>,
+    max_flat_fee: Option<Uint128>,
+    max_sealed_bids: Option<u32>,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, sender)?;
+
+    let old = CONFIG.load(deps.storage)?;
+
+    let new = Config {
+        min_expiration_seconds: min_expiration_seconds.unwrap_or(old.min_expiration_seconds),
+        max_expiration_seconds: max_expiration_seconds.unwrap_or(old.max_expiration_seconds),
+        max_num_assets: max_num_assets.unwrap_or(old.max_num_assets),
+        max_royalty_bps: max_royalty_bps.unwrap_or(old.max_royalty_bps),
+        fee_bps: fee_bps.unwrap_or(old.fee_bps),
+        fee_cycle_seconds: fee_cycle_seconds.unwrap_or(old.fee_cycle_seconds),
+        expiry_bounty: match expiry_bounty {
+            Some(bounty) if bounty.amount.is_zero() => None,
+            Some(bounty) => Some(bounty),
+            None => old.expiry_bounty,
+        },
+        max_flat_fee: max_flat_fee.unwrap_or(old.max_flat_fee),
+        max_sealed_bids: max_sealed_bids.unwrap_or(old.max_sealed_bids),
+    };
+
+    new.validate()?;
+
+    let flat_fee = FEE_SCHEDULE.load(deps.storage)?.flat_fee;
+    if flat_fee.is_some_and(|f| f.amount.amount > new.max_flat_fee) {
+        return Err(ContractError::InvalidConfig(
+            "max_flat_fee cannot be under the current flat fee".to_string(),
+        ));
+    }
+
+    CONFIG.save(deps.storage, &new)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+/// Owner only, replaces the fee schedule
+/// - Rotation restarts from the first denom
+/// - Only affects sales made after the update
+pub fn execute_update_fee_schedule(
+    deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    denoms: Vec<FeeDenomUnvalidated>,
+    mode: FeeMode,
+    any_fungible: Option<bool>,
+    flat_fee: Option<FlatFeeUnvalidated>,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, sender)?;
+
+    let fee_schedule = FeeSchedule::new(
+        FeeDenomUnvalidated::validate_all(denoms, &deps)?,
+        mode,
+        any_fungible.unwrap_or(false),
+        flat_fee.map(|f| f.validate(&deps)).transpose()?,
+        CONFIG.load(deps.storage)?.max_flat_fee,
+        env.block.time.seconds(),
+    )?;
+    let fee_routes = FEE_ROUTES.load(deps.storage)?;
+    fee_schedule.check_routes(&fee_routes)?;
+    check_pending_routes(deps.storage, &fee_routes)?;
+
+    FEE_SCHEDULE.save(deps.storage, &fee_schedule)?;
+
+    Ok(Response::new().add_attribute("action", "update_fee_schedule"))
+}
+
+/// Owner only, replaces where fees are sent
+/// - Applies to every fee swept after the update
+/// - Can't route to the Community Pool while a CW20 fee is pending
+pub fn execute_update_fee_routes(
+    deps: DepsMut,
+    sender: &Addr,
+    routes: Vec<FeeRouteUnvalidated>,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, sender)?;
+
+    let fee_routes = FeeRouteUnvalidated::validate_all(routes, &deps)?;
+    FEE_SCHEDULE.load(deps.storage)?.check_routes(&fee_routes)?;
+    check_pending_routes(deps.storage, &fee_routes)?;
+
+    FEE_ROUTES.save(deps.storage, &fee_routes)?;
+
+    Ok(Response::new().add_attribute("action", "update_fee_routes"))
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Batches
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Runs each Action as `sender`, all or nothing
+/// - Natives sent must be exactly the Actions' funds
+pub fn execute_batch(
+    mut deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    funds: &[Coin],
+    actions: Vec<Action>,
+) -> Result<Response, ContractError> {
+    if actions.is_empty() || actions.len() > MAX_BATCH_ACTIONS {
+        return Err(ContractError::GenericError(format!(
+            "Batch must have between 1 and {MAX_BATCH_ACTIONS} Actions"
+        )));
+    }
+
+    let mut unspent = GenericBalance {
+        native: funds.to_vec(),
+        cw20: vec![],
+        nfts: vec![],
+    };
+
+    let mut res = Response::new()
+        .add_attribute("action", "batch")
+        .add_attribute("actions", actions.len().to_string());
+
+    for action in actions {
+        // Each Action's funds come out of what was sent with the Batch
+        unspent = unspent
+            .checked_sub(&GenericBalance {
+                native: action.funds().to_vec(),
+                cw20: vec![],
+                nfts: vec![],
+            })
+            .map_err(|_e| {
+                ContractError::GenericError(
+                    "Natives sent do not cover the Actions' funds".to_string(),
+                )
+            })?;
+
+        let action_res = match action {
+            Action::CreateListing {
+                listing_id,
+                create_msg,
+                funds,
+            } => execute_create_listing(
+                deps.branch(),
+                sender,
+                &Balance::from(funds),
+                create_msg,
+                listing_id,
+            ),
+            Action::AddToListing {
+                listing_id,
+                funds,
+            } => execute_add_to_listing(deps.branch(), Balance::from(funds), sender, listing_id),
+            Action::ChangeAsk {
+                listing_id,
+                new_ask,
+            } => execute_change_ask(deps.branch(), sender, listing_id, new_ask),
+            Action::Finalize {
+                listing_id,
+                seconds,
+            } => execute_finalize(deps.branch(), env, sender, listing_id, seconds),
+            Action::DeleteListing {
+                listing_id,
+                destination,
+            } => execute_delete_listing(
+                deps.branch(),
+                env,
+                sender.clone(),
+                listing_id,
+                destination,
+            ),
+            Action::CreateBucket {
+                bucket_id,
+                funds,
+            } => execute_create_bucket(deps.branch(), &Balance::from(funds), sender, bucket_id),
+            Action::AddToBucket {
+                bucket_id,
+                funds,
+            } => execute_add_to_bucket(deps.branch(), Balance::from(funds), sender, bucket_id),
+            Action::RemoveBucket {
+                bucket_id,
+                destination,
+            } => execute_withdraw_bucket(deps.branch(), sender, bucket_id, destination),
+            Action::DepositToListing {
+                listing_id,
+                cw20,
+                nfts,
+                funds,
+            } => execute_deposit_to_listing(
+                deps.branch(),
+                env,
+                sender,
+                &funds,
+                listing_id,
+                cw20,
+                nfts,
+            ),
+            Action::AddApprovedNfts {
+                listing_id,
+                nfts,
+            } => execute_add_approved_nfts(deps.branch(), env, sender, listing_id, nfts),
+            Action::DepositToBucket {
+                bucket_id,
+                cw20,
+                nfts,
+                funds,
+            } => execute_deposit_to_bucket(
+                deps.branch(),
+                env,
+                sender,
+                &funds,
+                bucket_id,
+                cw20,
+                nfts,
+            ),
+            Action::BuyListing {
+                listing_id,
+                bucket_id,
+                quantity,
+                funds,
+            } => execute_buy_listing(
+                deps.branch(),
+                env,
+                sender,
+                &funds,
+                listing_id,
+                bucket_id,
+                quantity,
+            ),
+            Action::WithdrawPurchased {
+                listing_id,
+                destination,
+            } => execute_withdraw_purchased(deps.branch(), sender, listing_id, destination),
+        }?;
+
+        res = res
+            .add_submessages(action_res.messages)
+            .add_attributes(action_res.attributes)
+            .add_events(action_res.events);
+    }
+
+    if !unspent.native.is_empty() {
+        return Err(ContractError::GenericError(
+            "Natives sent must all be used by the Actions".to_string(),
+        ));
+    }
+
+    Ok(res)
+}
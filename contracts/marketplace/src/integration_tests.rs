@@ -1,10 +1,10 @@
 #![cfg(test)]
 pub use self::create_contract::*;
 pub use self::create_users::*;
-pub use self::init_contracts::init_all_contracts;
+pub use self::init_contracts::{init_all_contracts, list_nft};
 pub use crate::integration_tests_imports::*;
 pub use cw721_base::helpers::Cw721Contract;
-pub use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+pub use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
 use royalties::RoyaltyInfo;
 use royalties::msg::{
     ExecuteMsg as RoyaltyExecuteMsg,
@@ -47,7 +47,9 @@ pub mod create_contract {
             crate::contract::execute,
             crate::contract::instantiate,
             crate::contract::query,
-        ).with_reply(crate::contract::reply);
+        ).with_sudo(crate::contract::sudo)
+        .with_reply(crate::contract::reply)
+        .with_migrate(crate::contract::migrate);
 
         Box::new(contract)
     }
@@ -63,9 +65,109 @@ pub mod create_contract {
     }
 }
 
+/// Stand-in for a deployed v0.3.0 Fuzion Market
+///
+/// Writes state using the v0.3.0 layout so that it can be migrated
+pub mod legacy_contract {
+    use crate::integration_tests::{Contract, ContractWrapper, Empty};
+    use crate::migrations::v0_3_0;
+    use crate::state::{BUCKET_ID_USED, LISTING_ID_USED, ROYALTY_REGISTRY};
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult};
+    use cw2::set_contract_version;
+
+    #[cw_serde]
+    pub struct LegacyInstantiateMsg {
+        pub contract_name: String,
+        pub contract_version: String,
+        pub royalty_registry: String,
+    }
+
+    #[cw_serde]
+    pub enum LegacyExecuteMsg {
+        SeedListing(v0_3_0::Listing),
+        SeedBucket {
+            bucket_id: u64,
+            bucket: v0_3_0::Bucket,
+        },
+    }
+
+    pub fn instantiate(
+        deps: DepsMut,
+        env: Env,
+        _info: MessageInfo,
+        msg: LegacyInstantiateMsg,
+    ) -> StdResult<Response> {
+        set_contract_version(deps.storage, msg.contract_name, msg.contract_version)?;
+        LISTING_ID_USED.save(deps.storage, 0, &true)?;
+        BUCKET_ID_USED.save(deps.storage, 0, &true)?;
+        v0_3_0::FEE_DENOM.save(deps.storage, &v0_3_0::FeeDenom::JUNO(env.block.time.seconds()))?;
+        let registry = deps.api.addr_validate(&msg.royalty_registry)?;
+        ROYALTY_REGISTRY.save(deps.storage, &Some(registry))?;
+        Ok(Response::new())
+    }
+
+    pub fn execute(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: LegacyExecuteMsg,
+    ) -> StdResult<Response> {
+        match msg {
+            LegacyExecuteMsg::SeedListing(listing) => {
+                v0_3_0::listingz().save(deps.storage, (&listing.creator, listing.id), &listing)?;
+                LISTING_ID_USED.save(deps.storage, listing.id, &true)?;
+            }
+            LegacyExecuteMsg::SeedBucket {
+                bucket_id,
+                bucket,
+            } => {
+                v0_3_0::BUCKETS.save(deps.storage, (bucket.owner.clone(), bucket_id), &bucket)?;
+                BUCKET_ID_USED.save(deps.storage, bucket_id, &true)?;
+            }
+        }
+        Ok(Response::new())
+    }
+
+    pub fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        Err(StdError::generic_err("Legacy contract has no queries"))
+    }
+
+    pub fn legacy_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(execute, instantiate, query))
+    }
+}
+
+/// Stand-in for a contract that receives fees (e.g. staking rewards)
+///
+/// Accepts any execute message & keeps the funds sent with it
+pub mod fee_sink_contract {
+    use crate::integration_tests::{Contract, ContractWrapper, Empty};
+    use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult};
+
+    pub fn instantiate(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    pub fn execute(_deps: DepsMut, _env: Env, info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+        if info.funds.is_empty() {
+            return Err(StdError::generic_err("No fees sent"));
+        }
+        Ok(Response::new())
+    }
+
+    pub fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        Err(StdError::generic_err("Fee sink has no queries"))
+    }
+
+    pub fn fee_sink_contract() -> Box<dyn Contract<Empty>> {
+        Box::new(ContractWrapper::new(execute, instantiate, query))
+    }
+}
+
 pub mod create_users {
     use super::VALID_NATIVE;
-    use cosmwasm_std::Addr;
+    use cosmwasm_std::{Addr, Uint128};
     use cw_multi_test::App;
     use std::borrow::BorrowMut;
 
@@ -90,6 +192,18 @@ pub mod create_users {
 
         router
     }
+
+    pub fn native_balance(router: &App, addr: &Addr) -> Result<Uint128, anyhow::Error> {
+        Ok(router.wrap().query_balance(addr.to_string(), VALID_NATIVE)?.amount)
+    }
+
+    /// Moves the chain forward `seconds` (& 10 blocks)
+    pub fn advance(router: &mut App, seconds: u64) {
+        router.update_block(|current_blockinfo| {
+            current_blockinfo.height += 10;
+            current_blockinfo.time = current_blockinfo.time.plus_seconds(seconds);
+        });
+    }
 }
 
 pub mod init_contracts {
@@ -163,7 +277,23 @@ pub mod init_contracts {
         let jv_id = router.store_code(fuzionmarket_contract());
         let royalty_id = router.store_code(royalty_contract());
         let msg = InstantiateMsg {
-            royalty_code_id: royalty_id
+            royalty_code_id: royalty_id,
+            owner: None,
+            config: None,
+            fee_denoms: vec![
+                FeeDenomUnvalidated {
+                    asset: FeeAssetUnvalidated::Native(VALID_NATIVE.to_string()),
+                    fee_bps: None,
+                },
+                FeeDenomUnvalidated {
+                    asset: FeeAssetUnvalidated::Native("uusdcx".to_string()),
+                    fee_bps: None,
+                },
+            ],
+            fee_mode: FeeMode::Rotation,
+            fee_any_fungible: None,
+            flat_fee: None,
+            fee_routes: None,
         };
 
         let addr =
@@ -174,6 +304,7 @@ pub mod init_contracts {
         addr
     }
 
+    #[allow(clippy::type_complexity)]
     pub fn init_all_contracts(
         router: &mut App,
         contract_admin: &User,
@@ -348,6 +479,48 @@ pub mod init_contracts {
 
         Ok((jvone, jvtwo, jvtre, neonpeepz, shittykittyz, fuzionmarket))
     }
+
+    /// `seller` sends `token_id` to create a Listing with `create_msg`,
+    /// then finalizes it for `seconds` (if given)
+    #[allow(clippy::too_many_arguments)]
+    pub fn list_nft(
+        router: &mut App,
+        seller: &Addr,
+        nft_contract: &Cw721Contract<Empty, Empty>,
+        fuzionmarket: &Addr,
+        listing_id: u64,
+        token_id: &str,
+        create_msg: CreateListingMsg,
+        seconds: Option<u64>,
+    ) -> Result<AppResponse, anyhow::Error> {
+        let res = router.execute_contract(
+            seller.clone(),
+            nft_contract.addr(),
+            &cw721_base::ExecuteMsg::<Option<Empty>, Empty>::SendNft {
+                contract: fuzionmarket.to_string(),
+                token_id: token_id.to_string(),
+                msg: to_binary(&ReceiveNftMsg::CreateListingCw721 {
+                    listing_id,
+                    create_msg,
+                })?,
+            },
+            &[],
+        )?;
+
+        let Some(seconds) = seconds else {
+            return Ok(res);
+        };
+
+        router.execute_contract(
+            seller.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::Finalize {
+                listing_id,
+                seconds,
+            },
+            &[],
+        )
+    }
 }
 
 pub mod create_valid_listing {
@@ -383,6 +556,8 @@ pub mod create_valid_listing {
             ask: valid_ask_price,
             //whitelisted_purchasers: None,
             whitelisted_buyer: None,
+            partial_fill: None,
+            auction: None,
         };
 
         crate::msg::ExecuteMsg::CreateListing {
@@ -391,6 +566,7 @@ pub mod create_valid_listing {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_valid_ask(
         listing_id: u64,
         juno_amt: Option<u128>,
@@ -464,6 +640,8 @@ pub mod create_valid_listing {
         let cm = CreateListingMsg {
             ask: valid_ask_price,
             whitelisted_buyer,
+            partial_fill: None,
+            auction: None,
         };
 
         crate::msg::ExecuteMsg::CreateListing {
@@ -499,6 +677,8 @@ pub mod create_valid_listing {
         CreateListingMsg {
             ask: ask_price,
             whitelisted_buyer,
+            partial_fill: None,
+            auction: None,
         }
     }
 }
@@ -1365,6 +1545,7 @@ fn remove_a_listing() -> Result<(), anyhow::Error> {
 
     let remove_john_1 = crate::msg::ExecuteMsg::DeleteListing {
         listing_id: 5,
+        destination: None,
     };
 
     let res: Result<AppResponse> =
@@ -1421,7 +1602,8 @@ fn remove_a_listing() -> Result<(), anyhow::Error> {
 
     let q = crate::msg::QueryMsg::GetListingsByOwner {
         owner: john.address.clone().to_string(),
-        page_num: 1,
+        start_after: None,
+        limit: None,
     };
 
     let res: crate::query::MultiListingResponse =
@@ -1731,6 +1913,7 @@ fn finalize_a_listing() -> Result<(), anyhow::Error> {
     //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     let cant_remove = crate::msg::ExecuteMsg::DeleteListing {
         listing_id: 7,
+        destination: None,
     };
     let res: Result<AppResponse> =
         router.execute_contract(john.address.clone(), fuzionmarket.clone(), &cant_remove, &[]);
@@ -1742,6 +1925,7 @@ fn finalize_a_listing() -> Result<(), anyhow::Error> {
     //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     let cant_refund = crate::msg::ExecuteMsg::DeleteListing {
         listing_id: 7,
+        destination: None,
     };
     let res: Result<AppResponse> =
         router.execute_contract(john.address.clone(), fuzionmarket.clone(), &cant_refund, &[]);
@@ -1958,6 +2142,7 @@ fn expiration_checks() -> Result<(), anyhow::Error> {
     });
     let fail_refund = crate::msg::ExecuteMsg::DeleteListing {
         listing_id: 9,
+        destination: None,
     };
     let res: Result<AppResponse> =
         router.execute_contract(john.address.clone(), fuzionmarket.clone(), &fail_refund, &[]);
@@ -2012,6 +2197,7 @@ fn expiration_checks() -> Result<(), anyhow::Error> {
     //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     let success_refund = crate::msg::ExecuteMsg::DeleteListing {
         listing_id: 9,
+        destination: None,
     };
     let res: Result<AppResponse> =
         router.execute_contract(john.address.clone(), fuzionmarket.clone(), &success_refund, &[]);
@@ -2023,7 +2209,8 @@ fn expiration_checks() -> Result<(), anyhow::Error> {
 
     let q = crate::msg::QueryMsg::GetListingsByOwner {
         owner: john.address.clone().to_string(),
-        page_num: 1,
+        start_after: None,
+        limit: None,
     };
     let res: crate::query::MultiListingResponse =
         router.wrap().query_wasm_smart(fuzionmarket, &q).unwrap();
@@ -2225,6 +2412,7 @@ fn create_bucket() -> Result<(), anyhow::Error> {
 
     let remove = crate::msg::ExecuteMsg::RemoveBucket {
         bucket_id: 3,
+        destination: None,
     };
     let res: Result<AppResponse> =
         router.execute_contract(sam.address.clone(), fuzionmarket.clone(), &remove, &[]);
@@ -2257,7 +2445,8 @@ fn create_bucket() -> Result<(), anyhow::Error> {
     //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     let q = crate::msg::QueryMsg::GetBuckets {
         bucket_owner: john.address.clone().to_string(),
-        page_num: 1,
+        start_after: None,
+        limit: None,
     };
     let res: crate::query::MultiBucketResponse =
         router.wrap().query_wasm_smart(fuzionmarket, &q).unwrap();
@@ -2332,6 +2521,8 @@ fn marketplace_sale() -> Result<(), anyhow::Error> {
     let cl = CreateListingMsg {
         ask: ask_price,
         whitelisted_buyer: Some(sam.address.to_string()),
+        partial_fill: None,
+        auction: None,
     };
     let clm = crate::msg::ExecuteMsg::CreateListing {
         listing_id: 1,
@@ -2455,6 +2646,7 @@ fn marketplace_sale() -> Result<(), anyhow::Error> {
     let buy_msg = crate::msg::ExecuteMsg::BuyListing {
         listing_id: 1,
         bucket_id: 1,
+        quantity: None,
     };
     let res: Result<AppResponse> =
         router.execute_contract(sam.address.clone(), fuzionmarket.clone(), &buy_msg, &[]);
@@ -2463,6 +2655,7 @@ fn marketplace_sale() -> Result<(), anyhow::Error> {
     // Remove bucket
     let rem = crate::msg::ExecuteMsg::RemoveBucket {
         bucket_id: 1,
+        destination: None,
     };
     let res: Result<AppResponse> =
         router.execute_contract(sam.address.clone(), fuzionmarket.clone(), &rem, &[]);
@@ -2506,6 +2699,7 @@ fn marketplace_sale() -> Result<(), anyhow::Error> {
     let buy_msg = crate::msg::ExecuteMsg::BuyListing {
         listing_id: 1,
         bucket_id: 2,
+        quantity: None,
     };
     let res: Result<AppResponse> =
         router.execute_contract(sam.address.clone(), fuzionmarket.clone(), &buy_msg, &[]);
@@ -2514,6 +2708,7 @@ fn marketplace_sale() -> Result<(), anyhow::Error> {
     // Remove bucket
     let rem = crate::msg::ExecuteMsg::RemoveBucket {
         bucket_id: 2,
+        destination: None,
     };
     let res: Result<AppResponse> =
         router.execute_contract(sam.address.clone(), fuzionmarket.clone(), &rem, &[]);
@@ -2557,6 +2752,7 @@ fn marketplace_sale() -> Result<(), anyhow::Error> {
     let buy_msg = crate::msg::ExecuteMsg::BuyListing {
         listing_id: 1,
         bucket_id: 3,
+        quantity: None,
     };
     let res: Result<AppResponse> =
         router.execute_contract(sam.address.clone(), fuzionmarket.clone(), &buy_msg, &[]);
@@ -2565,6 +2761,7 @@ fn marketplace_sale() -> Result<(), anyhow::Error> {
     // Remove bucket
     let rem = crate::msg::ExecuteMsg::RemoveBucket {
         bucket_id: 3,
+        destination: None,
     };
     let res: Result<AppResponse> =
         router.execute_contract(sam.address.clone(), fuzionmarket.clone(), &rem, &[]);
@@ -2607,6 +2804,7 @@ fn marketplace_sale() -> Result<(), anyhow::Error> {
     let buy_msg = crate::msg::ExecuteMsg::BuyListing {
         listing_id: 1,
         bucket_id: 4,
+        quantity: None,
     };
     let res: Result<AppResponse> =
         router.execute_contract(sam.address.clone(), fuzionmarket.clone(), &buy_msg, &[]);
@@ -2615,6 +2813,7 @@ fn marketplace_sale() -> Result<(), anyhow::Error> {
     // Remove bucket
     let rem = crate::msg::ExecuteMsg::RemoveBucket {
         bucket_id: 4,
+        destination: None,
     };
     let res: Result<AppResponse> =
         router.execute_contract(sam.address.clone(), fuzionmarket.clone(), &rem, &[]);
@@ -2643,6 +2842,7 @@ fn marketplace_sale() -> Result<(), anyhow::Error> {
     let buy_msg = crate::msg::ExecuteMsg::BuyListing {
         listing_id: 1,
         bucket_id: 5,
+        quantity: None,
     };
     let res: Result<AppResponse> =
         router.execute_contract(max.address.clone(), fuzionmarket.clone(), &buy_msg, &[]);
@@ -2688,6 +2888,7 @@ fn marketplace_sale() -> Result<(), anyhow::Error> {
     let buy_msg = crate::msg::ExecuteMsg::BuyListing {
         listing_id: 1,
         bucket_id: 6,
+        quantity: None,
     };
     let res: Result<AppResponse> =
         router.execute_contract(sam.address.clone(), fuzionmarket.clone(), &buy_msg, &[]);
@@ -2700,6 +2901,7 @@ fn marketplace_sale() -> Result<(), anyhow::Error> {
 
     let rem = crate::msg::ExecuteMsg::RemoveBucket {
         bucket_id: 6,
+        destination: None,
     };
     let res: Result<AppResponse> =
         router.execute_contract(sam.address.clone(), fuzionmarket.clone(), &rem, &[]);
@@ -2770,6 +2972,7 @@ fn marketplace_sale() -> Result<(), anyhow::Error> {
     //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     let remove = crate::msg::ExecuteMsg::DeleteListing {
         listing_id: 1,
+        destination: None,
     };
     let res: Result<AppResponse> =
         router.execute_contract(john.address.clone(), fuzionmarket.clone(), &remove, &[]);
@@ -2806,6 +3009,7 @@ fn marketplace_sale() -> Result<(), anyhow::Error> {
 
     let refund = crate::msg::ExecuteMsg::DeleteListing {
         listing_id: 1,
+        destination: None,
     };
     let res: Result<AppResponse> =
         router.execute_contract(john.address.clone(), fuzionmarket.clone(), &refund, &[]);
@@ -2816,6 +3020,7 @@ fn marketplace_sale() -> Result<(), anyhow::Error> {
     //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     let remove_edge = crate::msg::ExecuteMsg::WithdrawPurchased {
         listing_id: 1,
+        destination: None,
     };
     let res: Result<AppResponse> =
         router.execute_contract(john.address.clone(), fuzionmarket.clone(), &remove_edge, &[]);
@@ -2958,6 +3163,8 @@ fn cant_buy_expired() -> Result<(), anyhow::Error> {
         ask: ask_price,
         //whitelisted_purchasers: None,
         whitelisted_buyer: None,
+        partial_fill: None,
+        auction: None,
     };
     let clm = crate::msg::ExecuteMsg::CreateListing {
         listing_id: 1,
@@ -3085,6 +3292,7 @@ fn cant_buy_expired() -> Result<(), anyhow::Error> {
     let buy_msg = crate::msg::ExecuteMsg::BuyListing {
         listing_id: 1,
         bucket_id: 1,
+        quantity: None,
     };
     let res: Result<AppResponse> =
         router.execute_contract(sam.address.clone(), fuzionmarket, &buy_msg, &[]);
@@ -3383,6 +3591,8 @@ fn royalties_are_sent() -> Result<(), anyhow::Error> {
             nfts: vec![]
         },
         whitelisted_buyer: None,
+        partial_fill: None,
+        auction: None,
     };
 
     let cmsg_nft = to_binary(&crate::msg::ReceiveNftMsg::CreateListingCw721 {
@@ -3447,7 +3657,7 @@ fn royalties_are_sent() -> Result<(), anyhow::Error> {
     //~~~~~~~~~~~~~~~~~~~~~~~~~
     // Execute the trade
     //~~~~~~~~~~~~~~~~~~~~~~~~~
-    let buy_msg = ExecuteMsg::BuyListing { listing_id: 1, bucket_id: 1 };
+    let buy_msg = ExecuteMsg::BuyListing { listing_id: 1, bucket_id: 1, quantity: None };
     let _res: AppResponse = 
         router.execute_contract(max.address.clone(), fuzionmarket.clone(), &buy_msg, &[]).unwrap();
 
@@ -3483,6 +3693,8 @@ fn royalties_are_sent() -> Result<(), anyhow::Error> {
             }]
         },
         whitelisted_buyer: None,
+        partial_fill: None,
+        auction: None,
     };
 
     let cmsg = ExecuteMsg::CreateListing { 
@@ -3546,7 +3758,7 @@ fn royalties_are_sent() -> Result<(), anyhow::Error> {
     //~~~~~~~~~~~~~~~~~~~~~~~~~
     // Execute the trade
     //~~~~~~~~~~~~~~~~~~~~~~~~~
-    let buy_msg = ExecuteMsg::BuyListing { listing_id: 2, bucket_id: 2 };
+    let buy_msg = ExecuteMsg::BuyListing { listing_id: 2, bucket_id: 2, quantity: None };
     let _res: AppResponse = 
         router.execute_contract(john.address.clone(), fuzionmarket.clone(), &buy_msg, &[]).unwrap();
 
@@ -3627,6 +3839,8 @@ fn no_duplicate_royalties_listing() -> Result<(), anyhow::Error> {
             nfts: vec![]
         },
         whitelisted_buyer: None,
+        partial_fill: None,
+        auction: None,
     };
 
     let cmsg_nft = to_binary(&crate::msg::ReceiveNftMsg::CreateListingCw721 {
@@ -3702,7 +3916,7 @@ fn no_duplicate_royalties_listing() -> Result<(), anyhow::Error> {
     //~~~~~~~~~~~~~~~~~~~~~~~~~
     // Execute the trade
     //~~~~~~~~~~~~~~~~~~~~~~~~~
-    let buy_msg = ExecuteMsg::BuyListing { listing_id: 1, bucket_id: 1 };
+    let buy_msg = ExecuteMsg::BuyListing { listing_id: 1, bucket_id: 1, quantity: None };
     let _res: AppResponse = 
         router.execute_contract(max.address.clone(), fuzionmarket.clone(), &buy_msg, &[]).unwrap();
 
@@ -3788,6 +4002,8 @@ fn no_duplicate_royalties_ask() -> Result<(), anyhow::Error> {
             ]
         },
         whitelisted_buyer: None,
+        partial_fill: None,
+        auction: None,
     };
 
     let cmsg = ExecuteMsg::CreateListing { 
@@ -3860,7 +4076,7 @@ fn no_duplicate_royalties_ask() -> Result<(), anyhow::Error> {
     //~~~~~~~~~~~~~~~~~~~~~~~~~
     // Execute the trade
     //~~~~~~~~~~~~~~~~~~~~~~~~~
-    let buy_msg = ExecuteMsg::BuyListing { listing_id: 1, bucket_id: 1 };
+    let buy_msg = ExecuteMsg::BuyListing { listing_id: 1, bucket_id: 1, quantity: None };
     let _res: AppResponse = 
         router.execute_contract(john.address.clone(), fuzionmarket.clone(), &buy_msg, &[]).unwrap();
 
@@ -3943,6 +4159,8 @@ fn royalty_nft_both_sides() -> Result<(), anyhow::Error> {
             }]
         },
         whitelisted_buyer: None,
+        partial_fill: None,
+        auction: None,
     };
 
     let cmsg_nft = to_binary(&crate::msg::ReceiveNftMsg::CreateListingCw721 {
@@ -4040,7 +4258,7 @@ fn royalty_nft_both_sides() -> Result<(), anyhow::Error> {
     //~~~~~~~~~~~~~~~~~~~~~~~~~
     // Execute the trade
     //~~~~~~~~~~~~~~~~~~~~~~~~~
-    let buy_msg = ExecuteMsg::BuyListing { listing_id: 1, bucket_id: 1 };
+    let buy_msg = ExecuteMsg::BuyListing { listing_id: 1, bucket_id: 1, quantity: None };
     let _res: AppResponse = 
         router.execute_contract(sam.address.clone(), fuzionmarket.clone(), &buy_msg, &[]).unwrap();
 
@@ -4136,6 +4354,8 @@ fn multiple_collections_with_royalties() -> Result<(), anyhow::Error> {
             }]
         },
         whitelisted_buyer: None,
+        partial_fill: None,
+        auction: None,
     };
 
     let cmsg_nft = to_binary(&crate::msg::ReceiveNftMsg::CreateListingCw721 {
@@ -4253,7 +4473,7 @@ fn multiple_collections_with_royalties() -> Result<(), anyhow::Error> {
     //~~~~~~~~~~~~~~~~~~~~~~~~~
     // Execute the trade
     //~~~~~~~~~~~~~~~~~~~~~~~~~
-    let buy_msg = ExecuteMsg::BuyListing { listing_id: 1, bucket_id: 1 };
+    let buy_msg = ExecuteMsg::BuyListing { listing_id: 1, bucket_id: 1, quantity: None };
     let _res: AppResponse = 
         router.execute_contract(sam.address.clone(), fuzionmarket.clone(), &buy_msg, &[]).unwrap();
 
@@ -4364,6 +4584,8 @@ fn over_fifty_percent_listing() -> Result<(), anyhow::Error> {
             nfts: vec![]
         },
         whitelisted_buyer: None,
+        partial_fill: None,
+        auction: None,
     };
 
     let cmsg_nft = to_binary(&crate::msg::ReceiveNftMsg::CreateListingCw721 {
@@ -4437,7 +4659,7 @@ fn over_fifty_percent_listing() -> Result<(), anyhow::Error> {
     //~~~~~~~~~~~~~~~~~~~~~~~~~
     // Try execute trade
     //~~~~~~~~~~~~~~~~~~~~~~~~~
-    let buy_msg = ExecuteMsg::BuyListing { listing_id: 1, bucket_id: 1 };
+    let buy_msg = ExecuteMsg::BuyListing { listing_id: 1, bucket_id: 1, quantity: None };
     let res: Result<AppResponse> = 
         router.execute_contract(sam.address.clone(), fuzionmarket.clone(), &buy_msg, &[]);
 
@@ -4456,7 +4678,7 @@ fn over_fifty_percent_listing() -> Result<(), anyhow::Error> {
     // Ensure both can withdraw refund 
     //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-    let refund_bucket = crate::msg::ExecuteMsg::RemoveBucket { bucket_id: 1 };
+    let refund_bucket = crate::msg::ExecuteMsg::RemoveBucket { bucket_id: 1, destination: None };
     let _res: AppResponse =
         router.execute_contract(sam.address.clone(), fuzionmarket.clone(), &refund_bucket, &[]).unwrap();
 
@@ -4465,7 +4687,7 @@ fn over_fifty_percent_listing() -> Result<(), anyhow::Error> {
         current_blockinfo.time = current_blockinfo.time.plus_seconds(10_000);
     });
 
-    let refund_listing = crate::msg::ExecuteMsg::DeleteListing { listing_id: 1 };
+    let refund_listing = crate::msg::ExecuteMsg::DeleteListing { listing_id: 1, destination: None };
     let _res: AppResponse =
         router.execute_contract(john.address.clone(), fuzionmarket.clone(), &refund_listing, &[]).unwrap();
 
@@ -4565,6 +4787,8 @@ fn over_fifty_percent_bucket() -> Result<(), anyhow::Error> {
             nfts: ask_nfts
         },
         whitelisted_buyer: None,
+        partial_fill: None,
+        auction: None,
     };
 
     let cmsg = cw20_base::msg::ExecuteMsg::Send {
@@ -4637,7 +4861,7 @@ fn over_fifty_percent_bucket() -> Result<(), anyhow::Error> {
     //~~~~~~~~~~~~~~~~~~~~~~~~~
     // Try execute trade
     //~~~~~~~~~~~~~~~~~~~~~~~~~
-    let buy_msg = ExecuteMsg::BuyListing { listing_id: 1, bucket_id: 1 };
+    let buy_msg = ExecuteMsg::BuyListing { listing_id: 1, bucket_id: 1, quantity: None };
     let res: Result<AppResponse> = 
         router.execute_contract(john.address.clone(), fuzionmarket.clone(), &buy_msg, &[]);
 
@@ -4656,7 +4880,7 @@ fn over_fifty_percent_bucket() -> Result<(), anyhow::Error> {
     // Ensure both can withdraw refund 
     //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-    let refund_bucket = crate::msg::ExecuteMsg::RemoveBucket { bucket_id: 1 };
+    let refund_bucket = crate::msg::ExecuteMsg::RemoveBucket { bucket_id: 1, destination: None };
     let _res: AppResponse =
         router.execute_contract(john.address.clone(), fuzionmarket.clone(), &refund_bucket, &[]).unwrap();
 
@@ -4665,7 +4889,7 @@ fn over_fifty_percent_bucket() -> Result<(), anyhow::Error> {
         current_blockinfo.time = current_blockinfo.time.plus_seconds(10_000);
     });
 
-    let refund_listing = crate::msg::ExecuteMsg::DeleteListing { listing_id: 1 };
+    let refund_listing = crate::msg::ExecuteMsg::DeleteListing { listing_id: 1, destination: None };
     let _res: AppResponse =
         router.execute_contract(sam.address.clone(), fuzionmarket.clone(), &refund_listing, &[]).unwrap();
 
@@ -4677,3 +4901,5773 @@ fn over_fifty_percent_bucket() -> Result<(), anyhow::Error> {
 
 
 
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Ownership
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// <X> Instantiator is owner by default
+// <X> Only owner can propose, only pending owner can accept
+// <X> New proposal replaces old proposal
+// <X> Renounced ownership can't be used or recovered
+#[test]
+fn ownership_transfer() -> Result<(), anyhow::Error> {
+    use anyhow::Result;
+    use cw_multi_test::AppResponse;
+    use crate::query::OwnershipResponse;
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+
+    // Instantiate all contracts
+    let (_jvone, _jvtwo, _jvtre, _neonpeepz, _shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+
+    let ownership: OwnershipResponse =
+        router.wrap().query_wasm_smart(fuzionmarket.clone(), &QueryMsg::GetOwnership {})?;
+    ensure!(
+        ownership.owner == Some(contract_admin.address.clone()) && ownership.pending_owner.is_none(),
+        here("Instantiator should be owner", line!(), column!())
+    );
+
+    // John can't propose
+    let propose_john = ExecuteMsg::ProposeOwner {
+        new_owner: john.address.to_string(),
+    };
+    let res: Result<AppResponse> =
+        router.execute_contract(john.address.clone(), fuzionmarket.clone(), &propose_john, &[]);
+    ensure!(res.is_err(), here("Non owner proposed", line!(), column!()));
+
+    // Admin proposes John, then replaces with Sam
+    router.execute_contract(contract_admin.address.clone(), fuzionmarket.clone(), &propose_john, &[])?;
+    let propose_sam = ExecuteMsg::ProposeOwner {
+        new_owner: sam.address.to_string(),
+    };
+    router.execute_contract(contract_admin.address.clone(), fuzionmarket.clone(), &propose_sam, &[])?;
+
+    // John can no longer accept
+    let accept = ExecuteMsg::AcceptOwnership {};
+    let res: Result<AppResponse> =
+        router.execute_contract(john.address.clone(), fuzionmarket.clone(), &accept, &[]);
+    ensure!(res.is_err(), here("Replaced proposal accepted", line!(), column!()));
+
+    // Owner is unchanged until Sam accepts
+    let ownership: OwnershipResponse =
+        router.wrap().query_wasm_smart(fuzionmarket.clone(), &QueryMsg::GetOwnership {})?;
+    ensure!(
+        ownership.owner == Some(contract_admin.address.clone())
+            && ownership.pending_owner == Some(sam.address.clone()),
+        here("Pending owner not set", line!(), column!())
+    );
+
+    router.execute_contract(sam.address.clone(), fuzionmarket.clone(), &accept, &[])?;
+    let ownership: OwnershipResponse =
+        router.wrap().query_wasm_smart(fuzionmarket.clone(), &QueryMsg::GetOwnership {})?;
+    ensure!(
+        ownership.owner == Some(sam.address.clone()) && ownership.pending_owner.is_none(),
+        here("Sam should be owner", line!(), column!())
+    );
+
+    // Old owner can't renounce
+    let renounce = ExecuteMsg::RenounceOwnership {};
+    let res: Result<AppResponse> =
+        router.execute_contract(contract_admin.address.clone(), fuzionmarket.clone(), &renounce, &[]);
+    ensure!(res.is_err(), here("Old owner renounced", line!(), column!()));
+
+    // Pending proposal is cleared on renounce
+    router.execute_contract(sam.address.clone(), fuzionmarket.clone(), &propose_john, &[])?;
+    router.execute_contract(sam.address.clone(), fuzionmarket.clone(), &renounce, &[])?;
+
+    let res: Result<AppResponse> =
+        router.execute_contract(john.address.clone(), fuzionmarket.clone(), &accept, &[]);
+    ensure!(res.is_err(), here("Accepted after renounce", line!(), column!()));
+    let res: Result<AppResponse> =
+        router.execute_contract(sam.address.clone(), fuzionmarket.clone(), &propose_john, &[]);
+    ensure!(res.is_err(), here("Proposed after renounce", line!(), column!()));
+
+    let ownership: OwnershipResponse =
+        router.wrap().query_wasm_smart(fuzionmarket, &QueryMsg::GetOwnership {})?;
+    ensure!(
+        ownership.owner.is_none() && ownership.pending_owner.is_none(),
+        here("Ownership should be renounced", line!(), column!())
+    );
+
+    Ok(())
+}
+
+// <X> Owner can't delete, withdraw, or buy with another user's Listing or Bucket
+#[test]
+fn owner_cant_touch_escrow() -> Result<(), anyhow::Error> {
+    use anyhow::Result;
+    use cw_multi_test::AppResponse;
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+
+    // Instantiate all contracts
+    let (jvone, jvtwo, _jvtre, _neonpeepz, _shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+
+    // John lists 10 JVONE for 10 JVTWO, Sam creates a bucket with 10 JVTWO
+    let create_listing = to_binary(&ReceiveMsg::CreateListingCw20 {
+        listing_id: 1,
+        create_msg: CreateListingMsg {
+            ask: GenericBalanceUnvalidated {
+                native: vec![],
+                cw20: vec![Cw20CoinUnverified {
+                    address: jvtwo.addr().to_string(),
+                    amount: Uint128::from(10u32),
+                }],
+                nfts: vec![],
+            },
+            whitelisted_buyer: None,
+            partial_fill: None,
+            auction: None,
+        },
+    })?;
+    router.execute_contract(
+        john.address.clone(),
+        jvone.addr(),
+        &cw20_base::msg::ExecuteMsg::Send {
+            contract: fuzionmarket.to_string(),
+            amount: Uint128::from(10u32),
+            msg: create_listing,
+        },
+        &[],
+    )?;
+    router.execute_contract(
+        sam.address.clone(),
+        jvtwo.addr(),
+        &cw20_base::msg::ExecuteMsg::Send {
+            contract: fuzionmarket.to_string(),
+            amount: Uint128::from(10u32),
+            msg: to_binary(&ReceiveMsg::CreateBucketCw20 {
+                bucket_id: 1,
+            })?,
+        },
+        &[],
+    )?;
+    router.execute_contract(
+        john.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::Finalize {
+            listing_id: 1,
+            seconds: 10000,
+        },
+        &[],
+    )?;
+
+    let owner_msgs = [
+        ExecuteMsg::DeleteListing {
+            listing_id: 1,
+            destination: None,
+        },
+        ExecuteMsg::RemoveBucket {
+            bucket_id: 1,
+            destination: None,
+        },
+        ExecuteMsg::BuyListing {
+            listing_id: 1,
+            bucket_id: 1,
+            quantity: None,
+        },
+        ExecuteMsg::ChangeAsk {
+            listing_id: 1,
+            new_ask: GenericBalanceUnvalidated {
+                native: vec![],
+                cw20: vec![],
+                nfts: vec![],
+            },
+        },
+    ];
+    for msg in owner_msgs {
+        let res: Result<AppResponse> =
+            router.execute_contract(contract_admin.address.clone(), fuzionmarket.clone(), &msg, &[]);
+        ensure!(res.is_err(), here("Owner touched escrow", line!(), column!()));
+    }
+
+    assert_eq!(jvone.balance(&router.wrap(), fuzionmarket.clone()), Ok(Uint128::from(10u32)));
+    assert_eq!(jvtwo.balance(&router.wrap(), fuzionmarket), Ok(Uint128::from(10u32)));
+
+    Ok(())
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Config
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// <X> Default Config & cw2 version are returned
+// <X> Only owner can update Config
+// <X> Invalid Config is rejected
+// <X> Expiration bounds, max assets & fee cycle are read from Config
+#[test]
+fn update_config() -> Result<(), anyhow::Error> {
+    use anyhow::Result;
+    use cw_multi_test::AppResponse;
+    use crate::query::ConfigResponse;
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+
+    // Instantiate all contracts
+    let (jvone, jvtwo, _jvtre, _neonpeepz, _shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+
+    let res: ConfigResponse =
+        router.wrap().query_wasm_smart(fuzionmarket.clone(), &QueryMsg::GetConfig {})?;
+    ensure!(res.config == Config::default(), here("Default config", line!(), column!()));
+    ensure!(
+        res.contract_name == crate::contract::CONTRACT_NAME
+            && res.contract_version == crate::contract::CONTRACT_VERSION,
+        here("Config version", line!(), column!())
+    );
+
+    let update = |
+        min_expiration_seconds: Option<u64>,
+        max_expiration_seconds: Option<u64>,
+        max_num_assets: Option<u32>,
+        fee_bps: Option<u64>,
+        fee_cycle_seconds: Option<u64>,
+    | ExecuteMsg::UpdateConfig {
+        min_expiration_seconds,
+        max_expiration_seconds,
+        max_num_assets,
+        max_royalty_bps: None,
+        fee_bps,
+        fee_cycle_seconds,
+        expiry_bounty: None,
+        max_flat_fee: None,
+        max_sealed_bids: None,
+    };
+
+    // Non owner can't update
+    let res: Result<AppResponse> = router.execute_contract(
+        john.address.clone(),
+        fuzionmarket.clone(),
+        &update(None, Some(5_000), None, None, None),
+        &[],
+    );
+    ensure!(res.is_err(), here("Non owner updated config", line!(), column!()));
+
+    // Invalid configs
+    let invalid = [
+        update(Some(0), None, None, None, None),
+        update(Some(10_000), Some(5_000), None, None, None),
+        update(None, None, Some(0), None, None),
+        update(None, None, None, Some(1_001), None),
+        update(None, None, None, None, Some(0)),
+    ];
+    for msg in invalid {
+        let res: Result<AppResponse> =
+            router.execute_contract(contract_admin.address.clone(), fuzionmarket.clone(), &msg, &[]);
+        ensure!(res.is_err(), here("Invalid config accepted", line!(), column!()));
+    }
+
+    // Max expiration 5000 seconds | Max 1 asset | Fee cycle 1 day
+    router.execute_contract(
+        contract_admin.address.clone(),
+        fuzionmarket.clone(),
+        &update(None, Some(5_000), Some(1), None, Some(86_400)),
+        &[],
+    )?;
+    let res: ConfigResponse =
+        router.wrap().query_wasm_smart(fuzionmarket.clone(), &QueryMsg::GetConfig {})?;
+    ensure!(
+        res.config
+            == Config {
+                max_expiration_seconds: 5_000,
+                max_num_assets: 1,
+                fee_cycle_seconds: 86_400,
+                ..Config::default()
+            },
+        here("Config not updated", line!(), column!())
+    );
+
+    // John lists 10 JVONE for 10 JVTWO
+    let create_listing = to_binary(&ReceiveMsg::CreateListingCw20 {
+        listing_id: 1,
+        create_msg: CreateListingMsg {
+            ask: GenericBalanceUnvalidated {
+                native: vec![],
+                cw20: vec![Cw20CoinUnverified {
+                    address: jvtwo.addr().to_string(),
+                    amount: Uint128::from(10u32),
+                }],
+                nfts: vec![],
+            },
+            whitelisted_buyer: None,
+            partial_fill: None,
+            auction: None,
+        },
+    })?;
+    router.execute_contract(
+        john.address.clone(),
+        jvone.addr(),
+        &cw20_base::msg::ExecuteMsg::Send {
+            contract: fuzionmarket.to_string(),
+            amount: Uint128::from(10u32),
+            msg: create_listing,
+        },
+        &[],
+    )?;
+
+    // Can't add a second asset
+    let res: Result<AppResponse> = router.execute_contract(
+        john.address.clone(),
+        jvtwo.addr(),
+        &cw20_base::msg::ExecuteMsg::Send {
+            contract: fuzionmarket.to_string(),
+            amount: Uint128::from(10u32),
+            msg: to_binary(&ReceiveMsg::AddToListingCw20 {
+                listing_id: 1,
+            })?,
+        },
+        &[],
+    );
+    ensure!(res.is_err(), here("Added over max_num_assets", line!(), column!()));
+
+    // Can't change ask to 2 assets
+    let res: Result<AppResponse> = router.execute_contract(
+        john.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::ChangeAsk {
+            listing_id: 1,
+            new_ask: GenericBalanceUnvalidated {
+                native: coins(10, VALID_NATIVE),
+                cw20: vec![Cw20CoinUnverified {
+                    address: jvtwo.addr().to_string(),
+                    amount: Uint128::from(10u32),
+                }],
+                nfts: vec![],
+            },
+        },
+        &[],
+    );
+    ensure!(res.is_err(), here("Ask over max_num_assets", line!(), column!()));
+
+    // Can't finalize over 5000 seconds
+    let res: Result<AppResponse> = router.execute_contract(
+        john.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::Finalize {
+            listing_id: 1,
+            seconds: 10_000,
+        },
+        &[],
+    );
+    ensure!(res.is_err(), here("Finalized over max expiration", line!(), column!()));
+
+    let res: Result<AppResponse> = router.execute_contract(
+        john.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::Finalize {
+            listing_id: 1,
+            seconds: 5_000,
+        },
+        &[],
+    );
+    ensure!(res.is_ok(), here("Finalize within max expiration", line!(), column!()));
+
+    // Fee cycle is 1 day
+    let fee_cycle = ExecuteMsg::FeeCycle {};
+    router.update_block(|b| b.time = b.time.plus_seconds(86_401));
+    let res: Result<AppResponse> =
+        router.execute_contract(max.address.clone(), fuzionmarket.clone(), &fee_cycle, &[]);
+    ensure!(res.is_ok(), here("Fee cycle after 1 day", line!(), column!()));
+
+    let res: Result<AppResponse> =
+        router.execute_contract(max.address.clone(), fuzionmarket, &fee_cycle, &[]);
+    ensure!(res.is_err(), here("Fee cycle before 1 day", line!(), column!()));
+
+    Ok(())
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Fee Schedule
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// <X> Rotation schedule rotates every fee_cycle_seconds & wraps
+// <X> Only owner can update the schedule
+// <X> Priority schedule doesn't rotate
+// <X> Priority schedule charges first denom found at that denom's rate
+#[test]
+fn fee_schedule_rotation_and_priority() -> Result<(), anyhow::Error> {
+    use anyhow::Result;
+    use cw_multi_test::AppResponse;
+    use crate::query::{FeeDenomResponse, FeeScheduleResponse, MultiListingResponse};
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+
+    // Instantiate all contracts
+    let (_jvone, jvtwo, _jvtre, _neonpeepz, _shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+    let router = give_natives(&john, &mut router);
+
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Rotation (ujunox <> uusdcx)
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    let schedule: FeeScheduleResponse =
+        router.wrap().query_wasm_smart(fuzionmarket.clone(), &QueryMsg::GetFeeSchedule {})?;
+    ensure!(
+        schedule.mode == FeeMode::Rotation
+            && schedule.current == Some(FeeAsset::Native(VALID_NATIVE.to_string()))
+            && schedule.denoms.iter().all(|d| d.fee_bps == 50)
+            && schedule.next_rotation == Some(schedule.last_rotation + 604_800),
+        here("Initial fee schedule", line!(), column!())
+    );
+
+    let fee_cycle = ExecuteMsg::FeeCycle {};
+    let res: Result<AppResponse> =
+        router.execute_contract(max.address.clone(), fuzionmarket.clone(), &fee_cycle, &[]);
+    ensure!(res.is_err(), here("Cycled before fee_cycle_seconds", line!(), column!()));
+
+    router.update_block(|b| b.time = b.time.plus_seconds(604_801));
+    router.execute_contract(max.address.clone(), fuzionmarket.clone(), &fee_cycle, &[])?;
+    let fee_denom: FeeDenomResponse =
+        router.wrap().query_wasm_smart(fuzionmarket.clone(), &QueryMsg::GetFeeDenom {})?;
+    ensure!(fee_denom.asset == Some(FeeAsset::Native("uusdcx".to_string())), here("Rotate to uusdcx", line!(), column!()));
+
+    router.update_block(|b| b.time = b.time.plus_seconds(604_801));
+    router.execute_contract(max.address.clone(), fuzionmarket.clone(), &fee_cycle, &[])?;
+    let fee_denom: FeeDenomResponse =
+        router.wrap().query_wasm_smart(fuzionmarket.clone(), &QueryMsg::GetFeeDenom {})?;
+    ensure!(
+        fee_denom.asset == Some(FeeAsset::Native(VALID_NATIVE.to_string())),
+        here("Rotation should wrap", line!(), column!())
+    );
+
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Priority (ufoo > ujunox @ 1%)
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    let priority = ExecuteMsg::UpdateFeeSchedule {
+        denoms: vec![
+            FeeDenomUnvalidated {
+                asset: FeeAssetUnvalidated::Native("ufoo".to_string()),
+                fee_bps: None,
+            },
+            FeeDenomUnvalidated {
+                asset: FeeAssetUnvalidated::Native(VALID_NATIVE.to_string()),
+                fee_bps: Some(100),
+            },
+        ],
+        mode: FeeMode::Priority,
+        any_fungible: None,
+        flat_fee: None,
+    };
+    let res: Result<AppResponse> =
+        router.execute_contract(john.address.clone(), fuzionmarket.clone(), &priority, &[]);
+    ensure!(res.is_err(), here("Non owner updated fee schedule", line!(), column!()));
+    router.execute_contract(contract_admin.address.clone(), fuzionmarket.clone(), &priority, &[])?;
+
+    let fee_denom: FeeDenomResponse =
+        router.wrap().query_wasm_smart(fuzionmarket.clone(), &QueryMsg::GetFeeDenom {})?;
+    ensure!(
+        fee_denom.asset == Some(FeeAsset::Native("ufoo".to_string())) && fee_denom.next_change.is_none(),
+        here("Priority fee denom", line!(), column!())
+    );
+
+    router.update_block(|b| b.time = b.time.plus_seconds(604_801));
+    let res: Result<AppResponse> =
+        router.execute_contract(max.address.clone(), fuzionmarket.clone(), &fee_cycle, &[]);
+    ensure!(res.is_err(), here("Priority schedule cycled", line!(), column!()));
+
+    // John lists 1000 ujunox for 10 JVTWO, Sam buys
+    router.execute_contract(
+        john.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::CreateListing {
+            listing_id: 1,
+            create_msg: CreateListingMsg {
+                ask: GenericBalanceUnvalidated {
+                    native: vec![],
+                    cw20: vec![Cw20CoinUnverified {
+                        address: jvtwo.addr().to_string(),
+                        amount: Uint128::from(10u32),
+                    }],
+                    nfts: vec![],
+                },
+                whitelisted_buyer: None,
+                partial_fill: None,
+                auction: None,
+            },
+        },
+        &coins(1_000, VALID_NATIVE),
+    )?;
+    router.execute_contract(
+        john.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::Finalize {
+            listing_id: 1,
+            seconds: 10_000,
+        },
+        &[],
+    )?;
+    router.execute_contract(
+        sam.address.clone(),
+        jvtwo.addr(),
+        &cw20_base::msg::ExecuteMsg::Send {
+            contract: fuzionmarket.to_string(),
+            amount: Uint128::from(10u32),
+            msg: to_binary(&ReceiveMsg::CreateBucketCw20 {
+                bucket_id: 1,
+            })?,
+        },
+        &[],
+    )?;
+    router.execute_contract(
+        sam.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::BuyListing {
+            listing_id: 1,
+            bucket_id: 1,
+            quantity: None,
+        },
+        &[],
+    )?;
+
+    // ufoo isn't in the Listing, so 1% of ujunox is the fee
+    let purchased: MultiListingResponse = router.wrap().query_wasm_smart(
+        fuzionmarket,
+        &QueryMsg::GetListingsByOwner {
+            owner: sam.address.to_string(),
+            start_after: None,
+            limit: None,
+        },
+    )?;
+    ensure!(
+        purchased.listings[0].fee_amount == Some(Coin::new(10, VALID_NATIVE).into())
+            && purchased.listings[0].for_sale.native == coins(990, VALID_NATIVE),
+        here("Priority fee", line!(), column!())
+    );
+
+    Ok(())
+}
+
+// <X> NFT for NFT swap pays no percentage fee, so the minimum flat fee is due in full
+// <X> Buyer must send in exactly the flat fee
+// <X> Flat fee is credited to the Fee Ledger
+// <X> Flat fee can't be over max_flat_fee & max_flat_fee can't go under the flat fee
+#[test]
+fn flat_fee_on_nft_swap() -> Result<(), anyhow::Error> {
+    use anyhow::Result;
+    use cw_multi_test::AppResponse;
+    use crate::query::{FeeLedgerResponse, FeeScheduleResponse};
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+
+    // Instantiate all contracts
+    let (_jvone, _jvtwo, _jvtre, neonpeepz, shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+    let router = give_natives(&sam, &mut router);
+
+    let flat_fee = FlatFeeUnvalidated {
+        asset: FeeAssetUnvalidated::Native(VALID_NATIVE.to_string()),
+        amount: Uint128::new(1_000),
+        mode: FlatFeeMode::Minimum,
+    };
+    router.execute_contract(
+        contract_admin.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::UpdateFeeSchedule {
+            denoms: vec![FeeDenomUnvalidated {
+                asset: FeeAssetUnvalidated::Native(VALID_NATIVE.to_string()),
+                fee_bps: None,
+            }],
+            mode: FeeMode::Rotation,
+            any_fungible: None,
+            flat_fee: Some(flat_fee.clone()),
+        },
+        &[],
+    )?;
+    let schedule: FeeScheduleResponse =
+        router.wrap().query_wasm_smart(fuzionmarket.clone(), &QueryMsg::GetFeeSchedule {})?;
+    let saved = FlatFee {
+        amount: Coin::new(1_000, VALID_NATIVE).into(),
+        mode: FlatFeeMode::Minimum,
+    };
+    ensure!(schedule.flat_fee == Some(saved), here("Flat fee not saved", line!(), column!()));
+
+    // Flat fee over max_flat_fee
+    let res: Result<AppResponse> = router.execute_contract(
+        contract_admin.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::UpdateFeeSchedule {
+            denoms: vec![],
+            mode: FeeMode::Rotation,
+            any_fungible: None,
+            flat_fee: Some(FlatFeeUnvalidated {
+                amount: Config::default().max_flat_fee + Uint128::one(),
+                ..flat_fee.clone()
+            }),
+        },
+        &[],
+    );
+    ensure!(res.is_err(), here("Flat fee over max_flat_fee", line!(), column!()));
+
+    // max_flat_fee under the current flat fee
+    let res: Result<AppResponse> = router.execute_contract(
+        contract_admin.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::UpdateConfig {
+            min_expiration_seconds: None,
+            max_expiration_seconds: None,
+            max_num_assets: None,
+            max_royalty_bps: None,
+            fee_bps: None,
+            fee_cycle_seconds: None,
+            expiry_bounty: None,
+            max_flat_fee: Some(Uint128::new(999)),
+            max_sealed_bids: None,
+        },
+        &[],
+    );
+    ensure!(res.is_err(), here("max_flat_fee under flat fee", line!(), column!()));
+
+    // John lists NeonPeepz #1 for ShittyKittyz #3
+    router.execute_contract(
+        john.address.clone(),
+        neonpeepz.addr(),
+        &cw721_base::ExecuteMsg::<Option<Empty>, Empty>::SendNft {
+            contract: fuzionmarket.to_string(),
+            token_id: "1".to_string(),
+            msg: to_binary(&ReceiveNftMsg::CreateListingCw721 {
+                listing_id: 1,
+                create_msg: CreateListingMsg {
+                    ask: GenericBalanceUnvalidated {
+                        native: vec![],
+                        cw20: vec![],
+                        nfts: vec![NftUnverified {
+                            contract_address: shittykittyz.addr().to_string(),
+                            token_id: "3".to_string(),
+                        }],
+                    },
+                    whitelisted_buyer: None,
+                    partial_fill: None,
+                    auction: None,
+                },
+            })?,
+        },
+        &[],
+    )?;
+    router.execute_contract(
+        john.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::Finalize {
+            listing_id: 1,
+            seconds: 10_000,
+        },
+        &[],
+    )?;
+
+    // Sam puts ShittyKittyz #3 in a Bucket
+    router.execute_contract(
+        sam.address.clone(),
+        shittykittyz.addr(),
+        &cw721_base::ExecuteMsg::<Option<Empty>, Empty>::SendNft {
+            contract: fuzionmarket.to_string(),
+            token_id: "3".to_string(),
+            msg: to_binary(&ReceiveNftMsg::CreateBucketCw721 {
+                bucket_id: 1,
+            })?,
+        },
+        &[],
+    )?;
+
+    let buy = ExecuteMsg::BuyListing {
+        listing_id: 1,
+        bucket_id: 1,
+        quantity: None,
+    };
+
+    let res: Result<AppResponse> =
+        router.execute_contract(sam.address.clone(), fuzionmarket.clone(), &buy, &[]);
+    ensure!(res.is_err(), here("Bought without flat fee", line!(), column!()));
+
+    let res: Result<AppResponse> =
+        router.execute_contract(sam.address.clone(), fuzionmarket.clone(), &buy, &coins(500, VALID_NATIVE));
+    ensure!(res.is_err(), here("Bought with partial flat fee", line!(), column!()));
+
+    let res: Result<AppResponse> =
+        router.execute_contract(sam.address.clone(), fuzionmarket.clone(), &buy, &coins(2_000, VALID_NATIVE));
+    ensure!(res.is_err(), here("Bought with excess flat fee", line!(), column!()));
+
+    router.execute_contract(sam.address.clone(), fuzionmarket.clone(), &buy, &coins(1_000, VALID_NATIVE))?;
+
+    let ledger: FeeLedgerResponse =
+        router.wrap().query_wasm_smart(fuzionmarket, &QueryMsg::GetFeeLedger {})?;
+    ensure!(
+        ledger.fees.len() == 1 && ledger.fees[0].pending == Uint128::from(1_000u32),
+        here("Flat fee not credited", line!(), column!())
+    );
+
+    Ok(())
+}
+
+// <X> CW20 fee denom is rejected while fees are routed to the Community Pool
+// <X> Fee is taken out of the CW20 paid to the seller
+// <X> CW20 fee is swept with Cw20ExecuteMsg::Transfer
+#[test]
+fn cw20_fee_asset() -> Result<(), anyhow::Error> {
+    use anyhow::Result;
+    use cw_multi_test::AppResponse;
+    use crate::query::FeeLedgerResponse;
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+    let treasury = create_users::fake_user("treasury".to_string());
+
+    // Instantiate all contracts
+    let (jvone, _jvtwo, _jvtre, neonpeepz, _shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+
+    // JVONE @ 10%
+    let cw20_schedule = ExecuteMsg::UpdateFeeSchedule {
+        denoms: vec![FeeDenomUnvalidated {
+            asset: FeeAssetUnvalidated::Cw20(jvone.addr().to_string()),
+            fee_bps: Some(1_000),
+        }],
+        mode: FeeMode::Priority,
+        any_fungible: None,
+        flat_fee: None,
+    };
+    let res: Result<AppResponse> =
+        router.execute_contract(contract_admin.address.clone(), fuzionmarket.clone(), &cw20_schedule, &[]);
+    ensure!(res.is_err(), here("CW20 fee with Community Pool route", line!(), column!()));
+
+    router.execute_contract(
+        contract_admin.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::UpdateFeeRoutes {
+            routes: vec![FeeRouteUnvalidated {
+                destination: FeeDestinationUnvalidated::Address(treasury.address.to_string()),
+                bps: 10_000,
+            }],
+        },
+        &[],
+    )?;
+    router.execute_contract(contract_admin.address.clone(), fuzionmarket.clone(), &cw20_schedule, &[])?;
+
+    // John lists NeonPeepz #1 for 100 JVONE, Sam buys
+    router.execute_contract(
+        john.address.clone(),
+        neonpeepz.addr(),
+        &cw721_base::ExecuteMsg::<Option<Empty>, Empty>::SendNft {
+            contract: fuzionmarket.to_string(),
+            token_id: "1".to_string(),
+            msg: to_binary(&ReceiveNftMsg::CreateListingCw721 {
+                listing_id: 1,
+                create_msg: CreateListingMsg {
+                    ask: GenericBalanceUnvalidated {
+                        native: vec![],
+                        cw20: vec![Cw20CoinUnverified {
+                            address: jvone.addr().to_string(),
+                            amount: Uint128::from(100u32),
+                        }],
+                        nfts: vec![],
+                    },
+                    whitelisted_buyer: None,
+                    partial_fill: None,
+                    auction: None,
+                },
+            })?,
+        },
+        &[],
+    )?;
+    router.execute_contract(
+        john.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::Finalize {
+            listing_id: 1,
+            seconds: 10_000,
+        },
+        &[],
+    )?;
+    router.execute_contract(
+        sam.address.clone(),
+        jvone.addr(),
+        &cw20_base::msg::ExecuteMsg::Send {
+            contract: fuzionmarket.to_string(),
+            amount: Uint128::from(100u32),
+            msg: to_binary(&ReceiveMsg::CreateBucketCw20 {
+                bucket_id: 1,
+            })?,
+        },
+        &[],
+    )?;
+    router.execute_contract(
+        sam.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::BuyListing {
+            listing_id: 1,
+            bucket_id: 1,
+            quantity: None,
+        },
+        &[],
+    )?;
+
+    // John gets 90 JVONE, 10 JVONE stays in the market until swept
+    router.execute_contract(
+        john.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::RemoveBucket {
+            bucket_id: 1,
+            destination: None,
+        },
+        &[],
+    )?;
+    assert_eq!(jvone.balance(&router.wrap(), john.address.clone()), Ok(Uint128::from(190u32)));
+    assert_eq!(jvone.balance(&router.wrap(), fuzionmarket.clone()), Ok(Uint128::from(10u32)));
+
+    let ledger: FeeLedgerResponse =
+        router.wrap().query_wasm_smart(fuzionmarket.clone(), &QueryMsg::GetFeeLedger {})?;
+    ensure!(
+        ledger.fees
+            == vec![FeeTotals {
+                asset: FeeAsset::Cw20(jvone.addr()),
+                pending: Uint128::from(10u32),
+                lifetime: Uint128::from(10u32),
+            }],
+        here("CW20 fee not credited", line!(), column!())
+    );
+
+    router.execute_contract(
+        max.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::SweepFees {
+            denoms: Some(vec![jvone.addr().to_string()]),
+        },
+        &[],
+    )?;
+    assert_eq!(jvone.balance(&router.wrap(), treasury.address.clone()), Ok(Uint128::from(10u32)));
+    assert_eq!(jvone.balance(&router.wrap(), fuzionmarket), Ok(Uint128::zero()));
+
+    Ok(())
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Fee Routing
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// <X> Fees go 100% to the Community Pool by default
+// <X> Only owner can update routes & invalid routes are rejected
+// <X> Fee is credited to the Fee Ledger on sale, not sent on withdraw
+// <X> Anyone can sweep, fee is split across an address & a contract
+// <X> Swept fees leave lifetime total untouched & can't be swept twice
+#[test]
+fn fee_routes_split_on_sweep() -> Result<(), anyhow::Error> {
+    use anyhow::Result;
+    use cw_multi_test::AppResponse;
+    use crate::query::{FeeLedgerResponse, FeeRoutesResponse};
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+    let treasury = create_users::fake_user("treasury".to_string());
+
+    // Instantiate all contracts
+    let (_jvone, jvtwo, _jvtre, _neonpeepz, _shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+    let router = give_natives(&john, &mut router);
+
+    let sink_id = router.store_code(fee_sink_contract::fee_sink_contract());
+    let fee_sink =
+        router.instantiate_contract(sink_id, contract_admin.address.clone(), &Empty {}, &[], "sink", None)?;
+
+    let routes: FeeRoutesResponse =
+        router.wrap().query_wasm_smart(fuzionmarket.clone(), &QueryMsg::GetFeeRoutes {})?;
+    ensure!(routes.routes == FeeRoute::default_routes(), here("Default routes", line!(), column!()));
+
+    // 60% Treasury | 40% Fee Sink
+    let sink_msg = to_binary(&Empty {})?;
+    let update = |treasury_bps: u64, sink_bps: u64| ExecuteMsg::UpdateFeeRoutes {
+        routes: vec![
+            FeeRouteUnvalidated {
+                destination: FeeDestinationUnvalidated::Address(treasury.address.to_string()),
+                bps: treasury_bps,
+            },
+            FeeRouteUnvalidated {
+                destination: FeeDestinationUnvalidated::Contract {
+                    contract_addr: fee_sink.to_string(),
+                    msg: sink_msg.clone(),
+                },
+                bps: sink_bps,
+            },
+        ],
+    };
+
+    let res: Result<AppResponse> =
+        router.execute_contract(john.address.clone(), fuzionmarket.clone(), &update(6_000, 4_000), &[]);
+    ensure!(res.is_err(), here("Non owner updated routes", line!(), column!()));
+
+    let res: Result<AppResponse> =
+        router.execute_contract(contract_admin.address.clone(), fuzionmarket.clone(), &update(6_000, 3_000), &[]);
+    ensure!(res.is_err(), here("Routes under 100% accepted", line!(), column!()));
+
+    router.execute_contract(contract_admin.address.clone(), fuzionmarket.clone(), &update(6_000, 4_000), &[])?;
+
+    // John lists 10_000 ujunox for 10 JVTWO, Sam buys
+    router.execute_contract(
+        john.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::CreateListing {
+            listing_id: 1,
+            create_msg: CreateListingMsg {
+                ask: GenericBalanceUnvalidated {
+                    native: vec![],
+                    cw20: vec![Cw20CoinUnverified {
+                        address: jvtwo.addr().to_string(),
+                        amount: Uint128::from(10u32),
+                    }],
+                    nfts: vec![],
+                },
+                whitelisted_buyer: None,
+                partial_fill: None,
+                auction: None,
+            },
+        },
+        &coins(10_000, VALID_NATIVE),
+    )?;
+    router.execute_contract(
+        john.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::Finalize {
+            listing_id: 1,
+            seconds: 10_000,
+        },
+        &[],
+    )?;
+    router.execute_contract(
+        sam.address.clone(),
+        jvtwo.addr(),
+        &cw20_base::msg::ExecuteMsg::Send {
+            contract: fuzionmarket.to_string(),
+            amount: Uint128::from(10u32),
+            msg: to_binary(&ReceiveMsg::CreateBucketCw20 {
+                bucket_id: 1,
+            })?,
+        },
+        &[],
+    )?;
+    router.execute_contract(
+        sam.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::BuyListing {
+            listing_id: 1,
+            bucket_id: 1,
+            quantity: None,
+        },
+        &[],
+    )?;
+
+    // 0.5% fee of 10_000 = 50, stays in the market until swept
+    router.execute_contract(
+        sam.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::WithdrawPurchased {
+            listing_id: 1,
+            destination: None,
+        },
+        &[],
+    )?;
+
+    ensure!(native_balance(router, &sam.address)? == Uint128::from(9_950u32), here("Sam balance", line!(), column!()));
+    ensure!(native_balance(router, &treasury.address)?.is_zero(), here("Fee sent on withdraw", line!(), column!()));
+    ensure!(native_balance(router, &fuzionmarket)? == Uint128::from(50u32), here("Fee not held", line!(), column!()));
+
+    let ledger: FeeLedgerResponse =
+        router.wrap().query_wasm_smart(fuzionmarket.clone(), &QueryMsg::GetFeeLedger {})?;
+    ensure!(
+        ledger.fees
+            == vec![FeeTotals {
+                asset: FeeAsset::Native(VALID_NATIVE.to_string()),
+                pending: Uint128::from(50u32),
+                lifetime: Uint128::from(50u32),
+            }],
+        here("Fee not credited to ledger", line!(), column!())
+    );
+
+    // Max sweeps | Treasury 30 | Fee Sink 20
+    router.execute_contract(
+        max.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::SweepFees {
+            denoms: None,
+        },
+        &[],
+    )?;
+
+    ensure!(native_balance(router, &treasury.address)? == Uint128::from(30u32), here("Treasury fee", line!(), column!()));
+    ensure!(native_balance(router, &fee_sink)? == Uint128::from(20u32), here("Fee sink fee", line!(), column!()));
+    ensure!(native_balance(router, &fuzionmarket)?.is_zero(), here("Market should be empty", line!(), column!()));
+
+    let ledger: FeeLedgerResponse =
+        router.wrap().query_wasm_smart(fuzionmarket.clone(), &QueryMsg::GetFeeLedger {})?;
+    ensure!(
+        ledger.fees[0].pending.is_zero() && ledger.fees[0].lifetime == Uint128::from(50u32),
+        here("Ledger after sweep", line!(), column!())
+    );
+
+    let res: Result<AppResponse> = router.execute_contract(
+        max.address.clone(),
+        fuzionmarket,
+        &ExecuteMsg::SweepFees {
+            denoms: Some(vec![VALID_NATIVE.to_string()]),
+        },
+        &[],
+    );
+    ensure!(res.is_err(), here("Swept twice", line!(), column!()));
+
+    Ok(())
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Expiry Crank
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// <X> Anyone can return expired Listings to their creators, up to `limit`
+// <X> Purchased & unfinalized Listings are never returned
+// <X> Caller is paid the bounty out of pending fees, capped at what's pending
+// <X> Sudo hook returns expired Listings without a bounty & doesn't error when idle
+#[test]
+fn process_expired_crank() -> Result<(), anyhow::Error> {
+    use crate::msg::SudoMsg;
+    use crate::query::{FeeLedgerResponse, SingleListingResponse};
+    use anyhow::Result;
+    use cw_multi_test::AppResponse;
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+
+    // Instantiate all contracts
+    let (_jvone, jvtwo, _jvtre, neonpeepz, _shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+    let router = give_natives(&john, &mut router);
+
+    let create =
+        |router: &mut App, listing_id: u64, amount: u128, seconds: Option<u64>| -> Result<()> {
+            router.execute_contract(
+                john.address.clone(),
+                fuzionmarket.clone(),
+                &ExecuteMsg::CreateListing {
+                    listing_id,
+                    create_msg: CreateListingMsg {
+                        ask: GenericBalanceUnvalidated {
+                            native: vec![],
+                            cw20: vec![Cw20CoinUnverified {
+                                address: jvtwo.addr().to_string(),
+                                amount: Uint128::from(10u32),
+                            }],
+                            nfts: vec![],
+                        },
+                        whitelisted_buyer: None,
+                        partial_fill: None,
+                        auction: None,
+                    },
+                },
+                &coins(amount, VALID_NATIVE),
+            )?;
+            if let Some(seconds) = seconds {
+                router.execute_contract(
+                    john.address.clone(),
+                    fuzionmarket.clone(),
+                    &ExecuteMsg::Finalize {
+                        listing_id,
+                        seconds,
+                    },
+                    &[],
+                )?;
+            }
+            Ok(())
+        };
+    let expire = |router: &mut App| {
+        router.update_block(|current_blockinfo| {
+            current_blockinfo.height += 100;
+            current_blockinfo.time = current_blockinfo.time.plus_seconds(600);
+        });
+    };
+    let pending = |router: &App| -> Result<Uint128> {
+        let ledger: FeeLedgerResponse =
+            router.wrap().query_wasm_smart(fuzionmarket.clone(), &QueryMsg::GetFeeLedger {})?;
+        Ok(ledger.fees.iter().map(|f| f.pending).sum())
+    };
+    let process = |router: &mut App| -> Result<AppResponse> {
+        router.execute_contract(
+            max.address.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::ProcessExpired {
+                limit: Some(2),
+            },
+            &[],
+        )
+    };
+
+    // 1: 10_000 ujunox, bought by Sam & not withdrawn | 0.5% fee of 10_000 = 50 pending
+    create(router, 1, 10_000, Some(600))?;
+    router.execute_contract(
+        sam.address.clone(),
+        jvtwo.addr(),
+        &cw20_base::msg::ExecuteMsg::Send {
+            contract: fuzionmarket.to_string(),
+            amount: Uint128::from(10u32),
+            msg: to_binary(&ReceiveMsg::CreateBucketCw20 {
+                bucket_id: 1,
+            })?,
+        },
+        &[],
+    )?;
+    router.execute_contract(
+        sam.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::BuyListing {
+            listing_id: 1,
+            bucket_id: 1,
+            quantity: None,
+        },
+        &[],
+    )?;
+    ensure!(pending(router)? == Uint128::from(50u32), here("Pending fee", line!(), column!()));
+
+    // 2, 3 & 4 expire, 2 holds NeonPeepz 1 | 5 is never finalized
+    create(router, 2, 100, None)?;
+    let add_nft: cw721_base::ExecuteMsg<Option<Empty>, Empty> = cw721_base::ExecuteMsg::SendNft {
+        contract: fuzionmarket.to_string(),
+        token_id: "1".to_string(),
+        msg: to_binary(&ReceiveNftMsg::AddToListingCw721 {
+            listing_id: 2,
+        })?,
+    };
+    router.execute_contract(john.address.clone(), neonpeepz.addr(), &add_nft, &[])?;
+    router.execute_contract(
+        john.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::Finalize {
+            listing_id: 2,
+            seconds: 600,
+        },
+        &[],
+    )?;
+    create(router, 3, 100, Some(600))?;
+    create(router, 4, 100, Some(600))?;
+    create(router, 5, 100, None)?;
+
+    // Nothing expired yet
+    ensure!(process(router).is_err(), here("Processed unexpired Listings", line!(), column!()));
+    expire(router);
+
+    // 20 ujunox per Listing
+    router.execute_contract(
+        contract_admin.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::UpdateConfig {
+            min_expiration_seconds: None,
+            max_expiration_seconds: None,
+            max_num_assets: None,
+            max_royalty_bps: None,
+            fee_bps: None,
+            fee_cycle_seconds: None,
+            expiry_bounty: Some(cosmwasm_std::coin(20, VALID_NATIVE)),
+            max_flat_fee: None,
+            max_sealed_bids: None,
+        },
+        &[],
+    )?;
+
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Crank returns 2 & 3, pays 40
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    let john_before = native_balance(router, &john.address)?;
+    process(router)?;
+
+    ensure!(
+        native_balance(router, &max.address)? == Uint128::from(40u32),
+        here("Bounty", line!(), column!())
+    );
+    ensure!(
+        pending(router)? == Uint128::from(10u32),
+        here("Bounty from pending fees", line!(), column!())
+    );
+    ensure!(
+        native_balance(router, &john.address)? == john_before + Uint128::from(200u32),
+        here("Listings returned", line!(), column!())
+    );
+    let owner = neonpeepz.owner_of(&router.wrap(), "1".to_string(), false)?.owner;
+    ensure!(owner == john.address, here("NFT returned", line!(), column!()));
+
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Sudo returns 4, no bounty
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    router.wasm_sudo(fuzionmarket.clone(), &SudoMsg::ClockEndBlock {})?;
+    ensure!(
+        native_balance(router, &john.address)? == john_before + Uint128::from(300u32),
+        here("Sudo returned Listing", line!(), column!())
+    );
+    ensure!(
+        native_balance(router, &max.address)? == Uint128::from(40u32),
+        here("Sudo paid bounty", line!(), column!())
+    );
+
+    // Only the purchased & unfinalized Listings are left
+    ensure!(process(router).is_err(), here("Processed with nothing expired", line!(), column!()));
+    router.wasm_sudo(fuzionmarket.clone(), &SudoMsg::ClockEndBlock {})?;
+    for listing_id in [1, 5] {
+        let res: Result<SingleListingResponse, _> = router.wrap().query_wasm_smart(
+            fuzionmarket.clone(),
+            &QueryMsg::GetListing {
+                listing_id,
+            },
+        );
+        ensure!(res.is_ok(), here(format!("Listing {listing_id} returned"), line!(), column!()));
+    }
+
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Bounty capped at pending fees
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    create(router, 6, 100, Some(600))?;
+    expire(router);
+    process(router)?;
+    ensure!(
+        native_balance(router, &max.address)? == Uint128::from(50u32),
+        here("Capped bounty", line!(), column!())
+    );
+    ensure!(pending(router)?.is_zero(), here("Pending not emptied", line!(), column!()));
+
+    Ok(())
+}
+
+// <X> Purchased Listings don't count toward `limit`, even when more than
+//     `MAX_EXPIRED_LIMIT` of them expire before a returnable Listing
+#[test]
+fn process_expired_skips_purchased() -> Result<(), anyhow::Error> {
+    use crate::execute::MAX_EXPIRED_LIMIT;
+    use crate::query::SingleListingResponse;
+    use anyhow::Result;
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+
+    // Instantiate all contracts
+    let (_jvone, _jvtwo, _jvtre, _neonpeepz, _shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+    let router = give_natives(&john, &mut router);
+    let router = give_natives(&sam, router);
+
+    let create = |router: &mut App, listing_id: u64, seconds: u64| -> Result<()> {
+        router.execute_contract(
+            john.address.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::CreateListing {
+                listing_id,
+                create_msg: CreateListingMsg {
+                    ask: GenericBalanceUnvalidated {
+                        native: coins(1_000, VALID_NATIVE),
+                        cw20: vec![],
+                        nfts: vec![],
+                    },
+                    whitelisted_buyer: None,
+                    partial_fill: None,
+                    auction: None,
+                },
+            },
+            &coins(100, VALID_NATIVE),
+        )?;
+        router.execute_contract(
+            john.address.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::Finalize {
+                listing_id,
+                seconds,
+            },
+            &[],
+        )?;
+        Ok(())
+    };
+
+    // Bought & not withdrawn, all expire before the last Listing
+    let purchased = u64::from(MAX_EXPIRED_LIMIT) + 5;
+    for listing_id in 1..=purchased {
+        create(router, listing_id, 600)?;
+        router.execute_contract(
+            sam.address.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::CreateBucket {
+                bucket_id: listing_id,
+            },
+            &coins(1_000, VALID_NATIVE),
+        )?;
+        router.execute_contract(
+            sam.address.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::BuyListing {
+                listing_id,
+                bucket_id: listing_id,
+                quantity: None,
+            },
+            &[],
+        )?;
+    }
+    let returnable = purchased + 1;
+    create(router, returnable, 1_200)?;
+
+    router.update_block(|current_blockinfo| {
+        current_blockinfo.height += 200;
+        current_blockinfo.time = current_blockinfo.time.plus_seconds(1_200);
+    });
+
+    let res = router.execute_contract(
+        max.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::ProcessExpired {
+            limit: Some(1),
+        },
+        &[],
+    )?;
+    ensure!(
+        res.events
+            .iter()
+            .flat_map(|e| e.attributes.iter())
+            .any(|a| a.key == "listings_returned" && a.value == "1"),
+        here("Returnable Listing not returned", line!(), column!())
+    );
+
+    let res: Result<SingleListingResponse, _> = router.wrap().query_wasm_smart(
+        fuzionmarket.clone(),
+        &QueryMsg::GetListing {
+            listing_id: returnable,
+        },
+    );
+    ensure!(res.is_err(), here("Returnable Listing still listed", line!(), column!()));
+
+    // Purchased Listings stay for their buyer
+    for listing_id in 1..=purchased {
+        let res: Result<SingleListingResponse, _> = router.wrap().query_wasm_smart(
+            fuzionmarket.clone(),
+            &QueryMsg::GetListing {
+                listing_id,
+            },
+        );
+        ensure!(res.is_ok(), here(format!("Listing {listing_id} returned"), line!(), column!()));
+    }
+
+    Ok(())
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Partial Fills
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// <X> Partial fill Listings must sell & ask for a single Native or CW20
+// <X> Bucket must hold exactly the price of `quantity`, rounded up
+// <X> Purchases under `min_fill` are rejected, unless they take the rest
+// <X> Bought part & fee are settled right away, the rest stays listed
+// <X> Listing is removed once it's sold out
+// <X> Quantity can't be given for a normal Listing
+#[test]
+fn partial_fills() -> Result<(), anyhow::Error> {
+    use crate::query::{FeeLedgerResponse, SingleListingResponse};
+    use anyhow::Result;
+    use cw_multi_test::AppResponse;
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+
+    // Instantiate all contracts
+    let (jvone, jvtwo, _jvtre, neonpeepz, _shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+    let router = give_natives(&sam, &mut router);
+    let router = give_natives(&max, router);
+
+    let ujunox_ask = |amount: u128| GenericBalanceUnvalidated {
+        native: coins(amount, VALID_NATIVE),
+        cw20: vec![],
+        nfts: vec![],
+    };
+    let create_cw20 = |router: &mut App,
+                       token: &Cw20Contract,
+                       listing_id: u64,
+                       amount: u128,
+                       ask: GenericBalanceUnvalidated,
+                       partial_fill: Option<PartialFill>|
+     -> Result<AppResponse> {
+        router.execute_contract(
+            john.address.clone(),
+            token.addr(),
+            &cw20_base::msg::ExecuteMsg::Send {
+                contract: fuzionmarket.to_string(),
+                amount: Uint128::from(amount),
+                msg: to_binary(&ReceiveMsg::CreateListingCw20 {
+                    listing_id,
+                    create_msg: CreateListingMsg {
+                        ask,
+                        whitelisted_buyer: None,
+                        partial_fill,
+                        auction: None,
+                    },
+                })?,
+            },
+            &[],
+        )
+    };
+    let buy = |router: &mut App,
+               buyer: &Addr,
+               listing_id: u64,
+               bucket_id: u64,
+               price: u128,
+               quantity: Option<u128>|
+     -> Result<AppResponse> {
+        router.execute_contract(
+            buyer.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::CreateBucket {
+                bucket_id,
+            },
+            &coins(price, VALID_NATIVE),
+        )?;
+        router.execute_contract(
+            buyer.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::BuyListing {
+                listing_id,
+                bucket_id,
+                quantity: quantity.map(Uint128::from),
+            },
+            &[],
+        )
+    };
+    let for_sale = |router: &App, listing_id: u64| -> Result<GenericBalance> {
+        let res: SingleListingResponse = router
+            .wrap()
+            .query_wasm_smart(fuzionmarket.clone(), &QueryMsg::GetListing { listing_id })?;
+        Ok(res.listing.for_sale)
+    };
+    let finalize = |router: &mut App, listing_id: u64| -> Result<AppResponse> {
+        router.execute_contract(
+            john.address.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::Finalize {
+                listing_id,
+                seconds: 10_000,
+            },
+            &[],
+        )
+    };
+
+    // NFT for sale or NFT ask can't be partially filled
+    let res = router.execute_contract(
+        john.address.clone(),
+        neonpeepz.addr(),
+        &cw721_base::ExecuteMsg::<Option<Empty>, Empty>::SendNft {
+            contract: fuzionmarket.to_string(),
+            token_id: "1".to_string(),
+            msg: to_binary(&ReceiveNftMsg::CreateListingCw721 {
+                listing_id: 1,
+                create_msg: CreateListingMsg {
+                    ask: ujunox_ask(100),
+                    whitelisted_buyer: None,
+                    partial_fill: Some(PartialFill {
+                        unit: Uint128::one(),
+                        min_fill: Uint128::one(),
+                    }),
+                    auction: None,
+                },
+            })?,
+        },
+        &[],
+    );
+    ensure!(res.is_err(), here("NFT partial fill Listing created", line!(), column!()));
+
+    let nft_ask = GenericBalanceUnvalidated {
+        native: vec![],
+        cw20: vec![],
+        nfts: vec![NftUnverified {
+            contract_address: neonpeepz.addr().to_string(),
+            token_id: "3".to_string(),
+        }],
+    };
+    let partial_fill = PartialFill {
+        unit: Uint128::from(10u32),
+        min_fill: Uint128::from(20u32),
+    };
+    let res = create_cw20(router, &jvone, 1, 60, nft_ask, Some(partial_fill.clone()));
+    ensure!(res.is_err(), here("NFT ask partial fill Listing created", line!(), column!()));
+
+    let res = create_cw20(
+        router,
+        &jvone,
+        1,
+        60,
+        ujunox_ask(1_000),
+        Some(PartialFill {
+            unit: Uint128::zero(),
+            min_fill: Uint128::one(),
+        }),
+    );
+    ensure!(res.is_err(), here("Partial fill with 0 unit created", line!(), column!()));
+
+    // 1: 60 JVONE @ 1_000 ujunox per 10 JVONE, at least 20 per purchase
+    create_cw20(router, &jvone, 1, 60, ujunox_ask(1_000), Some(partial_fill))?;
+
+    // Can't add a second asset & finalize
+    router.execute_contract(
+        john.address.clone(),
+        jvtwo.addr(),
+        &cw20_base::msg::ExecuteMsg::Send {
+            contract: fuzionmarket.to_string(),
+            amount: Uint128::from(5u32),
+            msg: to_binary(&ReceiveMsg::AddToListingCw20 {
+                listing_id: 1,
+            })?,
+        },
+        &[],
+    )?;
+    let res = finalize(router, 1);
+    ensure!(res.is_err(), here("Finalized with 2 assets for sale", line!(), column!()));
+    router.execute_contract(
+        john.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::DeleteListing {
+            listing_id: 1,
+            destination: None,
+        },
+        &[],
+    )?;
+
+    // 2: Same Listing again
+    create_cw20(
+        router,
+        &jvone,
+        2,
+        60,
+        ujunox_ask(1_000),
+        Some(PartialFill {
+            unit: Uint128::from(10u32),
+            min_fill: Uint128::from(20u32),
+        }),
+    )?;
+    finalize(router, 2)?;
+
+    // Wrong price & under min_fill fail
+    let res = buy(router, &sam.address, 2, 1, 1_000, Some(20));
+    ensure!(res.is_err(), here("Bought 20 JVONE for 1_000", line!(), column!()));
+    let res = buy(router, &sam.address, 2, 2, 1_000, Some(10));
+    ensure!(res.is_err(), here("Bought under min_fill", line!(), column!()));
+    let res = buy(router, &sam.address, 2, 3, 7_000, Some(70));
+    ensure!(res.is_err(), here("Bought more than listed", line!(), column!()));
+
+    // Sam buys 20 for 2_000 & gets them right away
+    buy(router, &sam.address, 2, 4, 2_000, Some(20))?;
+    assert_eq!(jvone.balance(&router.wrap(), sam.address.clone()), Ok(Uint128::from(120u32)));
+    ensure!(
+        for_sale(router, 2)?.cw20
+            == vec![Cw20CoinVerified {
+                address: jvone.addr(),
+                amount: Uint128::from(40u32),
+            }],
+        here("Rest not left listed", line!(), column!())
+    );
+
+    // John gets 2_000 less the 0.5% fee
+    router.execute_contract(
+        john.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::RemoveBucket {
+            bucket_id: 4,
+            destination: None,
+        },
+        &[],
+    )?;
+    ensure!(
+        router.wrap().query_balance(john.address.to_string(), VALID_NATIVE)?.amount
+            == Uint128::from(1_990u32),
+        here("Seller proceeds", line!(), column!())
+    );
+
+    // Max buys the rest without a quantity, Listing is removed
+    buy(router, &max.address, 2, 5, 4_000, None)?;
+    assert_eq!(jvone.balance(&router.wrap(), max.address.clone()), Ok(Uint128::from(140u32)));
+    ensure!(for_sale(router, 2).is_err(), here("Sold out Listing not removed", line!(), column!()));
+
+    let ledger: FeeLedgerResponse =
+        router.wrap().query_wasm_smart(fuzionmarket.clone(), &QueryMsg::GetFeeLedger {})?;
+    ensure!(
+        ledger.fees.iter().map(|f| f.pending).sum::<Uint128>() == Uint128::from(30u32),
+        here("Fees not credited", line!(), column!())
+    );
+
+    // 3: 5 JVTWO @ 1 ujunox per 2 JVTWO, at least 4 per purchase
+    create_cw20(
+        router,
+        &jvtwo,
+        3,
+        5,
+        ujunox_ask(1),
+        Some(PartialFill {
+            unit: Uint128::from(2u32),
+            min_fill: Uint128::from(4u32),
+        }),
+    )?;
+    finalize(router, 3)?;
+
+    // 4 JVTWO for 2, then the last 1 (under min_fill) for 1 (rounded up)
+    buy(router, &sam.address, 3, 6, 2, Some(4))?;
+    let res = buy(router, &sam.address, 3, 7, 1, Some(1));
+    ensure!(res.is_ok(), here("Last piece under min_fill", line!(), column!()));
+    assert_eq!(jvtwo.balance(&router.wrap(), sam.address.clone()), Ok(Uint128::from(105u32)));
+
+    // Quantity on a normal Listing
+    create_cw20(router, &jvone, 4, 10, ujunox_ask(100), None)?;
+    finalize(router, 4)?;
+    let res = buy(router, &sam.address, 4, 8, 100, Some(5));
+    ensure!(res.is_err(), here("Quantity on a normal Listing", line!(), column!()));
+    buy(router, &sam.address, 4, 9, 100, None)?;
+
+    Ok(())
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Offers
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// <X> Any Bucket can be offered on a live Listing, one Offer per Bucket
+// <X> Offered Bucket can't be added to, withdrawn or used to buy
+// <X> Offers are listed per Listing & per bidder, with the funds offered
+// <X> Only the bidder can retract, which unlocks the Bucket
+// <X> Only the creator can accept, settled with the same fee as BuyListing
+// <X> Expired Offers can't be accepted
+// <X> Offers on a removed or sold Listing no longer lock their Bucket
+// <X> Flat fee is taken out of the offered Bucket, which must hold it
+// <X> Flat fee is the one set when the Offer was made, raising it later has no effect
+#[test]
+fn offers() -> Result<(), anyhow::Error> {
+    use crate::query::MultiOfferResponse;
+    use anyhow::Result;
+    use cw_multi_test::AppResponse;
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+
+    // Instantiate all contracts
+    let (_jvone, jvtwo, _jvtre, neonpeepz, _shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+    let router = give_natives(&sam, &mut router);
+    let router = give_natives(&max, router);
+
+    let list = |router: &mut App, listing_id: u64, token_id: &str, finalize: bool| -> Result<AppResponse> {
+        let create_msg = CreateListingMsg {
+            ask: GenericBalanceUnvalidated {
+                native: vec![],
+                cw20: vec![Cw20CoinUnverified {
+                    address: jvtwo.addr().to_string(),
+                    amount: Uint128::from(100u32),
+                }],
+                nfts: vec![],
+            },
+            whitelisted_buyer: None,
+            partial_fill: None,
+            auction: None,
+        };
+        list_nft(
+            router,
+            &john.address,
+            &neonpeepz,
+            &fuzionmarket,
+            listing_id,
+            token_id,
+            create_msg,
+            finalize.then_some(10_000),
+        )
+    };
+    let offer = |router: &mut App,
+                 bidder: &Addr,
+                 listing_id: u64,
+                 bucket_id: u64,
+                 amount: Option<u128>|
+     -> Result<AppResponse> {
+        if let Some(amount) = amount {
+            router.execute_contract(
+                bidder.clone(),
+                fuzionmarket.clone(),
+                &ExecuteMsg::CreateBucket {
+                    bucket_id,
+                },
+                &coins(amount, VALID_NATIVE),
+            )?;
+        }
+        router.execute_contract(
+            bidder.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::MakeOffer {
+                listing_id,
+                bucket_id,
+                seconds: 600,
+            },
+            &[],
+        )
+    };
+    let exec = |router: &mut App, sender: &Addr, msg: ExecuteMsg| -> Result<AppResponse> {
+        router.execute_contract(sender.clone(), fuzionmarket.clone(), &msg, &[])
+    };
+    let by_listing = |router: &App, listing_id: u64| -> Result<MultiOfferResponse> {
+        Ok(router.wrap().query_wasm_smart(
+            fuzionmarket.clone(),
+            &QueryMsg::GetOffersByListing {
+                listing_id,
+                start_after: None,
+                limit: None,
+            },
+        )?)
+    };
+    let by_bidder = |router: &App, bidder: &Addr| -> Result<MultiOfferResponse> {
+        Ok(router.wrap().query_wasm_smart(
+            fuzionmarket.clone(),
+            &QueryMsg::GetOffersByBidder {
+                bidder: bidder.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )?)
+    };
+
+    // Can't offer on a Listing that isn't finalized
+    list(router, 9, "2", false)?;
+    let res = offer(router, &sam.address, 9, 9, Some(100));
+    ensure!(res.is_err(), here("Offer on unfinalized Listing", line!(), column!()));
+    exec(
+        router,
+        &john.address,
+        ExecuteMsg::DeleteListing {
+            listing_id: 9,
+            destination: None,
+        },
+    )?;
+    exec(
+        router,
+        &sam.address,
+        ExecuteMsg::RemoveBucket {
+            bucket_id: 9,
+            destination: None,
+        },
+    )?;
+
+    // 1: NeonPeepz #1 for 100 JVTWO
+    list(router, 1, "1", true)?;
+
+    // Sam offers 5_000 ujunox, Max offers 3_000 ujunox
+    offer(router, &sam.address, 1, 1, Some(5_000))?;
+    offer(router, &max.address, 1, 2, Some(3_000))?;
+
+    let res = offer(router, &sam.address, 1, 1, None);
+    ensure!(res.is_err(), here("Bucket offered twice", line!(), column!()));
+    let res = offer(router, &sam.address, 1, 2, None);
+    ensure!(res.is_err(), here("Offered another user's Bucket", line!(), column!()));
+
+    // Sam's Bucket is locked
+    let res = exec(
+        router,
+        &sam.address,
+        ExecuteMsg::RemoveBucket {
+            bucket_id: 1,
+            destination: None,
+        },
+    );
+    ensure!(res.is_err(), here("Offered Bucket withdrawn", line!(), column!()));
+    let res = router.execute_contract(
+        sam.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::AddToBucket {
+            bucket_id: 1,
+        },
+        &coins(1, VALID_NATIVE),
+    );
+    ensure!(res.is_err(), here("Offered Bucket added to", line!(), column!()));
+    let res = exec(
+        router,
+        &sam.address,
+        ExecuteMsg::BuyListing {
+            listing_id: 1,
+            bucket_id: 1,
+            quantity: None,
+        },
+    );
+    ensure!(res.is_err(), here("Offered Bucket used to buy", line!(), column!()));
+
+    let offers = by_listing(router, 1)?;
+    ensure!(
+        offers.offers.iter().map(|o| (o.offer.bidder.clone(), o.funds.native.clone())).collect::<Vec<_>>()
+            == vec![
+                (sam.address.clone(), coins(5_000, VALID_NATIVE)),
+                (max.address.clone(), coins(3_000, VALID_NATIVE)),
+            ],
+        here("Offers by Listing", line!(), column!())
+    );
+    ensure!(
+        by_bidder(router, &max.address)?.offers.iter().map(|o| o.offer.bucket_id).collect::<Vec<_>>() == vec![2],
+        here("Offers by bidder", line!(), column!())
+    );
+
+    // Only Max can retract Max's Offer, which unlocks the Bucket
+    let res = exec(
+        router,
+        &sam.address,
+        ExecuteMsg::RetractOffer {
+            bucket_id: 2,
+        },
+    );
+    ensure!(res.is_err(), here("Retracted another user's Offer", line!(), column!()));
+    exec(
+        router,
+        &max.address,
+        ExecuteMsg::RetractOffer {
+            bucket_id: 2,
+        },
+    )?;
+    exec(
+        router,
+        &max.address,
+        ExecuteMsg::RemoveBucket {
+            bucket_id: 2,
+            destination: None,
+        },
+    )?;
+    ensure!(by_listing(router, 1)?.offers.len() == 1, here("Retracted Offer listed", line!(), column!()));
+
+    // Only John can accept, for the Listing the Offer was made on
+    let res = exec(
+        router,
+        &max.address,
+        ExecuteMsg::AcceptOffer {
+            listing_id: 1,
+            bucket_id: 1,
+        },
+    );
+    ensure!(res.is_err(), here("Non-creator accepted", line!(), column!()));
+    let res = exec(
+        router,
+        &john.address,
+        ExecuteMsg::AcceptOffer {
+            listing_id: 2,
+            bucket_id: 1,
+        },
+    );
+    ensure!(res.is_err(), here("Accepted for another Listing", line!(), column!()));
+
+    exec(
+        router,
+        &john.address,
+        ExecuteMsg::AcceptOffer {
+            listing_id: 1,
+            bucket_id: 1,
+        },
+    )?;
+    ensure!(by_listing(router, 1)?.offers.is_empty(), here("Accepted Offer listed", line!(), column!()));
+
+    // Sam withdraws NeonPeepz #1, John gets 5_000 less the 0.5% fee
+    exec(
+        router,
+        &sam.address,
+        ExecuteMsg::WithdrawPurchased {
+            listing_id: 1,
+            destination: None,
+        },
+    )?;
+    ensure!(
+        neonpeepz.owner_of(&router.wrap(), "1".to_string(), false)?.owner == sam.address.as_str(),
+        here("NFT not withdrawn", line!(), column!())
+    );
+    exec(
+        router,
+        &john.address,
+        ExecuteMsg::RemoveBucket {
+            bucket_id: 1,
+            destination: None,
+        },
+    )?;
+    ensure!(
+        native_balance(router, &john.address)? == Uint128::from(4_975u32),
+        here("Seller proceeds", line!(), column!())
+    );
+
+    // 2: NeonPeepz #2, Sam's Offer expires before John accepts
+    list(router, 2, "2", true)?;
+    offer(router, &sam.address, 2, 3, Some(1_000))?;
+    router.update_block(|current_blockinfo| {
+        current_blockinfo.height += 100;
+        current_blockinfo.time = current_blockinfo.time.plus_seconds(601);
+    });
+    let res = exec(
+        router,
+        &john.address,
+        ExecuteMsg::AcceptOffer {
+            listing_id: 2,
+            bucket_id: 3,
+        },
+    );
+    ensure!(res.is_err(), here("Expired Offer accepted", line!(), column!()));
+    exec(
+        router,
+        &sam.address,
+        ExecuteMsg::RetractOffer {
+            bucket_id: 3,
+        },
+    )?;
+    exec(
+        router,
+        &sam.address,
+        ExecuteMsg::RemoveBucket {
+            bucket_id: 3,
+            destination: None,
+        },
+    )?;
+
+    // Max's Offer on 2 stops locking its Bucket once John removes 2
+    offer(router, &max.address, 2, 4, Some(1_000))?;
+    router.update_block(|current_blockinfo| {
+        current_blockinfo.height += 2_000;
+        current_blockinfo.time = current_blockinfo.time.plus_seconds(10_000);
+    });
+    exec(
+        router,
+        &john.address,
+        ExecuteMsg::DeleteListing {
+            listing_id: 2,
+            destination: None,
+        },
+    )?;
+    exec(
+        router,
+        &max.address,
+        ExecuteMsg::RemoveBucket {
+            bucket_id: 4,
+            destination: None,
+        },
+    )?;
+    ensure!(
+        by_bidder(router, &max.address)?.offers.is_empty(),
+        here("Stale Offer not removed", line!(), column!())
+    );
+
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // 100 ujunox flat fee, taken out of the offered Bucket
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    exec(
+        router,
+        &contract_admin.address,
+        ExecuteMsg::UpdateFeeSchedule {
+            denoms: vec![FeeDenomUnvalidated {
+                asset: FeeAssetUnvalidated::Native(VALID_NATIVE.to_string()),
+                fee_bps: None,
+            }],
+            mode: FeeMode::Rotation,
+            any_fungible: None,
+            flat_fee: Some(FlatFeeUnvalidated {
+                asset: FeeAssetUnvalidated::Native(VALID_NATIVE.to_string()),
+                amount: Uint128::new(100),
+                mode: FlatFeeMode::Always,
+            }),
+        },
+    )?;
+
+    // 3: NeonPeepz #2 again
+    list(router, 3, "2", true)?;
+
+    // A Bucket without ujunox can't pay the flat fee
+    router.execute_contract(
+        sam.address.clone(),
+        jvtwo.addr(),
+        &cw20_base::msg::ExecuteMsg::Send {
+            contract: fuzionmarket.to_string(),
+            amount: Uint128::from(100u32),
+            msg: to_binary(&ReceiveMsg::CreateBucketCw20 {
+                bucket_id: 5,
+            })?,
+        },
+        &[],
+    )?;
+    let res = offer(router, &sam.address, 3, 5, None);
+    ensure!(res.is_err(), here("Offer can't pay the flat fee", line!(), column!()));
+
+    // John accepts Max's 2_000 ujunox Offer without sending anything
+    offer(router, &max.address, 3, 6, Some(2_000))?;
+
+    // Flat fee raised to 1_500 ujunox after the Offer was made
+    exec(
+        router,
+        &contract_admin.address,
+        ExecuteMsg::UpdateFeeSchedule {
+            denoms: vec![FeeDenomUnvalidated {
+                asset: FeeAssetUnvalidated::Native(VALID_NATIVE.to_string()),
+                fee_bps: None,
+            }],
+            mode: FeeMode::Rotation,
+            any_fungible: None,
+            flat_fee: Some(FlatFeeUnvalidated {
+                asset: FeeAssetUnvalidated::Native(VALID_NATIVE.to_string()),
+                amount: Uint128::new(1_500),
+                mode: FlatFeeMode::Always,
+            }),
+        },
+    )?;
+    let john_before = native_balance(router, &john.address)?;
+    exec(
+        router,
+        &john.address,
+        ExecuteMsg::AcceptOffer {
+            listing_id: 3,
+            bucket_id: 6,
+        },
+    )?;
+    exec(
+        router,
+        &john.address,
+        ExecuteMsg::RemoveBucket {
+            bucket_id: 6,
+            destination: None,
+        },
+    )?;
+    ensure!(
+        native_balance(router, &john.address)? == john_before + Uint128::from(1_890u32),
+        here("Proceeds less 0.5% fee & the Offer's flat fee", line!(), column!())
+    );
+
+    Ok(())
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Collection Offers
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// <X> Native & CW20 funds are escrowed & split evenly into a price per NFT
+// <X> Only NFTs from the collection can fill, not by the bidder
+// <X> Seller is paid the price less the fee & royalties, bidder gets the NFT
+// <X> Filled quantity is tracked, offer stays open until every NFT is filled
+// <X> Expired offers can't be filled & are hidden from the collection query
+// <X> Only the bidder can cancel, unfilled escrow is returned
+#[test]
+fn collection_offers() -> Result<(), anyhow::Error> {
+    use crate::query::MultiCollectionOfferResponse;
+    use anyhow::Result;
+    use cw_multi_test::AppResponse;
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+    let payout = create_users::fake_user("payout".to_string());
+
+    // Instantiate all contracts
+    let (jvone, _jvtwo, _jvtre, neonpeepz, shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+    let router = give_natives(&sam, &mut router);
+
+    // NeonPeepz royalties @ 1%
+    let royalty_addr: Option<Addr> =
+        router.wrap().query_wasm_smart(fuzionmarket.clone(), &QueryMsg::GetRoyaltyAddr {})?;
+    router.execute_contract(
+        contract_admin.address.clone(),
+        royalty_addr.unwrap(),
+        &RoyaltyExecuteMsg::Register {
+            nft_contract: neonpeepz.addr().to_string(),
+            payout_addr: payout.address.to_string(),
+            bps: 100,
+        },
+        &[],
+    )?;
+
+    let create = |router: &mut App, offer_id: u64, quantity: u32, amount: u128| -> Result<AppResponse> {
+        router.execute_contract(
+            sam.address.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::CreateCollectionOffer {
+                offer_id,
+                collection: neonpeepz.addr().to_string(),
+                quantity,
+                seconds: 600,
+            },
+            &coins(amount, VALID_NATIVE),
+        )
+    };
+    let fill = |router: &mut App, nft: &Cw721Contract<Empty, Empty>, token_id: &str| -> Result<AppResponse> {
+        let owner = nft.owner_of(&router.wrap(), token_id.to_string(), false)?.owner;
+        router.execute_contract(
+            Addr::unchecked(owner),
+            nft.addr(),
+            &cw721_base::ExecuteMsg::<Option<Empty>, Empty>::SendNft {
+                contract: fuzionmarket.to_string(),
+                token_id: token_id.to_string(),
+                msg: to_binary(&ReceiveNftMsg::FillCollectionOffer {
+                    offer_id: 1,
+                })?,
+            },
+            &[],
+        )
+    };
+    let by_collection = |router: &App| -> Result<MultiCollectionOfferResponse> {
+        Ok(router.wrap().query_wasm_smart(
+            fuzionmarket.clone(),
+            &QueryMsg::GetCollectionOffers {
+                collection: neonpeepz.addr().to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )?)
+    };
+    let by_bidder = |router: &App, bidder: &Addr| -> Result<MultiCollectionOfferResponse> {
+        Ok(router.wrap().query_wasm_smart(
+            fuzionmarket.clone(),
+            &QueryMsg::GetCollectionOffersByBidder {
+                bidder: bidder.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )?)
+    };
+
+    // Funds must split evenly, quantity can't be 0
+    let res = create(router, 1, 2, 20_001);
+    ensure!(res.is_err(), here("Uneven split", line!(), column!()));
+    let res = create(router, 1, 0, 20_000);
+    ensure!(res.is_err(), here("0 quantity", line!(), column!()));
+
+    // 1: Sam bids 10_000 ujunox each for 2 NeonPeepz
+    create(router, 1, 2, 20_000)?;
+    let res = create(router, 1, 1, 100);
+    ensure!(res.is_err(), here("ID reused", line!(), column!()));
+
+    let res = fill(router, &shittykittyz, "1");
+    ensure!(res.is_err(), here("Filled with another collection", line!(), column!()));
+    let res = fill(router, &neonpeepz, "3");
+    ensure!(res.is_err(), here("Bidder filled own offer", line!(), column!()));
+
+    // John sells NeonPeepz #1 | 0.5% fee of 10_000 = 50, 1% royalty of 9_950 = 99
+    fill(router, &neonpeepz, "1")?;
+    ensure!(
+        neonpeepz.owner_of(&router.wrap(), "1".to_string(), false)?.owner == sam.address.as_str(),
+        here("Bidder didn't get NFT", line!(), column!())
+    );
+    ensure!(
+        native_balance(router, &john.address)? == Uint128::from(9_851u32),
+        here("Seller proceeds", line!(), column!())
+    );
+    ensure!(
+        native_balance(router, &payout.address)? == Uint128::from(99u32),
+        here("Royalty not paid", line!(), column!())
+    );
+
+    let offers = by_collection(router)?.offers;
+    ensure!(
+        offers.len() == 1 && offers[0].quantity == 1 && offers[0].filled == 1,
+        here("Fill not tracked", line!(), column!())
+    );
+
+    // 2: Max bids 10 JVONE each for 3 NeonPeepz, then cancels
+    router.execute_contract(
+        max.address.clone(),
+        jvone.addr(),
+        &cw20_base::msg::ExecuteMsg::Send {
+            contract: fuzionmarket.to_string(),
+            amount: Uint128::from(30u32),
+            msg: to_binary(&ReceiveMsg::CreateCollectionOfferCw20 {
+                offer_id: 2,
+                collection: neonpeepz.addr().to_string(),
+                quantity: 3,
+                seconds: 600,
+            })?,
+        },
+        &[],
+    )?;
+    ensure!(
+        by_bidder(router, &max.address)?.offers[0].price.cw20
+            == vec![Cw20CoinVerified {
+                address: jvone.addr(),
+                amount: Uint128::from(10u32),
+            }],
+        here("CW20 price", line!(), column!())
+    );
+    let res = router.execute_contract(
+        sam.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::CancelCollectionOffer {
+            offer_id: 2,
+        },
+        &[],
+    );
+    ensure!(res.is_err(), here("Non-bidder cancelled", line!(), column!()));
+    router.execute_contract(
+        max.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::CancelCollectionOffer {
+            offer_id: 2,
+        },
+        &[],
+    )?;
+    assert_eq!(jvone.balance(&router.wrap(), max.address.clone()), Ok(Uint128::from(100u32)));
+
+    // Sam's offer expires with 1 unfilled
+    router.update_block(|current_blockinfo| {
+        current_blockinfo.height += 100;
+        current_blockinfo.time = current_blockinfo.time.plus_seconds(601);
+    });
+    let res = fill(router, &neonpeepz, "2");
+    ensure!(res.is_err(), here("Expired offer filled", line!(), column!()));
+    ensure!(by_collection(router)?.offers.is_empty(), here("Expired offer listed", line!(), column!()));
+    ensure!(by_bidder(router, &sam.address)?.offers.len() == 1, here("Bidder's expired offer", line!(), column!()));
+
+    let before = native_balance(router, &sam.address)?;
+    router.execute_contract(
+        sam.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::CancelCollectionOffer {
+            offer_id: 1,
+        },
+        &[],
+    )?;
+    ensure!(
+        native_balance(router, &sam.address)? == before + Uint128::from(10_000u32),
+        here("Unfilled escrow not returned", line!(), column!())
+    );
+
+    Ok(())
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Auctions
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// <X> Reserve must be a single Native or CW20
+// <X> Bids must be the ask asset, meet the reserve & beat the highest by the increment
+// <X> Outbid funds are refunded
+// <X> Late bids push the end back
+// <X> Auctions can't be bought, deleted or processed as expired once bid on
+// <X> Anyone can settle after the end, winner gets the NFT & seller the bid less fee & royalties
+// <X> Auctions with no bids settle back to the seller
+// <X> Flat fee is taken out of the winning bid, settling needs no funds
+// <X> Flat fee is the one set when the auction was finalized, raising it later has no effect
+#[test]
+fn auctions() -> Result<(), anyhow::Error> {
+    use crate::query::SingleListingResponse;
+    use anyhow::Result;
+    use cw_multi_test::AppResponse;
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+    let payout = create_users::fake_user("payout".to_string());
+
+    // Instantiate all contracts
+    let (jvone, _jvtwo, _jvtre, neonpeepz, _shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+    let router = give_natives(&sam, &mut router);
+    let router = give_natives(&max, router);
+
+    // NeonPeepz royalties @ 1%
+    let royalty_addr: Option<Addr> =
+        router.wrap().query_wasm_smart(fuzionmarket.clone(), &QueryMsg::GetRoyaltyAddr {})?;
+    router.execute_contract(
+        contract_admin.address.clone(),
+        royalty_addr.unwrap(),
+        &RoyaltyExecuteMsg::Register {
+            nft_contract: neonpeepz.addr().to_string(),
+            payout_addr: payout.address.to_string(),
+            bps: 100,
+        },
+        &[],
+    )?;
+
+    let list = |router: &mut App,
+                listing_id: u64,
+                token_id: &str,
+                ask: GenericBalanceUnvalidated|
+     -> Result<AppResponse> {
+        let create_msg = CreateListingMsg {
+            ask,
+            whitelisted_buyer: None,
+            partial_fill: None,
+            auction: Some(AuctionMsg::English {
+                min_increment: Uint128::from(100u32),
+                extension_seconds: 300,
+            }),
+        };
+        list_nft(
+            router,
+            &john.address,
+            &neonpeepz,
+            &fuzionmarket,
+            listing_id,
+            token_id,
+            create_msg,
+            Some(1_000),
+        )
+    };
+    let bid = |router: &mut App, bidder: &Addr, amount: u128| -> Result<AppResponse> {
+        router.execute_contract(
+            bidder.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::PlaceBid {
+                listing_id: 1,
+            },
+            &coins(amount, VALID_NATIVE),
+        )
+    };
+    let settle = |router: &mut App, listing_id: u64| -> Result<AppResponse> {
+        router.execute_contract(
+            max.address.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::SettleAuction {
+                listing_id,
+            },
+            &[],
+        )
+    };
+    let get_listing = |router: &App| -> Result<Listing> {
+        let res: SingleListingResponse = router.wrap().query_wasm_smart(
+            fuzionmarket.clone(),
+            &QueryMsg::GetListing {
+                listing_id: 1,
+            },
+        )?;
+        Ok(res.listing)
+    };
+
+    // Reserve can't be more than one asset
+    let res = list(
+        router,
+        1,
+        "1",
+        GenericBalanceUnvalidated {
+            native: coins(1_000, VALID_NATIVE),
+            cw20: vec![Cw20CoinUnverified {
+                address: jvone.addr().to_string(),
+                amount: Uint128::from(10u32),
+            }],
+            nfts: vec![],
+        },
+    );
+    ensure!(res.is_err(), here("Two asset reserve", line!(), column!()));
+
+    // 1: John auctions NeonPeepz #1 with a 1_000 ujunox reserve
+    let ujunox_ask = |amount: u128| GenericBalanceUnvalidated {
+        native: coins(amount, VALID_NATIVE),
+        cw20: vec![],
+        nfts: vec![],
+    };
+    list(router, 1, "1", ujunox_ask(1_000))?;
+    let end = get_listing(router)?.expiration_time.unwrap();
+
+    let res = bid(router, &sam.address, 999);
+    ensure!(res.is_err(), here("Bid under reserve", line!(), column!()));
+    let res = router.execute_contract(
+        max.address.clone(),
+        jvone.addr(),
+        &cw20_base::msg::ExecuteMsg::Send {
+            contract: fuzionmarket.to_string(),
+            amount: Uint128::from(50u32),
+            msg: to_binary(&ReceiveMsg::PlaceBidCw20 {
+                listing_id: 1,
+            })?,
+        },
+        &[],
+    );
+    ensure!(res.is_err(), here("Bid in another asset", line!(), column!()));
+
+    bid(router, &sam.address, 1_000)?;
+    let res = bid(router, &max.address, 1_050);
+    ensure!(res.is_err(), here("Bid under increment", line!(), column!()));
+
+    // Max outbids Sam, who is refunded
+    bid(router, &max.address, 1_100)?;
+    ensure!(
+        native_balance(router, &sam.address)? == Uint128::from(100_000_000u32),
+        here("Outbid not refunded", line!(), column!())
+    );
+    ensure!(
+        get_listing(router)?.expiration_time == Some(end),
+        here("Early bid extended", line!(), column!())
+    );
+
+    // Can't be bought outright
+    router.execute_contract(
+        sam.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::CreateBucket {
+            bucket_id: 1,
+        },
+        &coins(1_000, VALID_NATIVE),
+    )?;
+    let res = router.execute_contract(
+        sam.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::BuyListing {
+            listing_id: 1,
+            bucket_id: 1,
+            quantity: None,
+        },
+        &[],
+    );
+    ensure!(res.is_err(), here("Auction bought", line!(), column!()));
+
+    // Sam bids with 200 seconds left, end is pushed to 300 seconds from now
+    advance(router, 800);
+    bid(router, &sam.address, 1_200)?;
+    ensure!(
+        get_listing(router)?.expiration_time == Some(end.plus_seconds(100)),
+        here("Late bid not extended", line!(), column!())
+    );
+    ensure!(
+        native_balance(router, &max.address)? == Uint128::from(100_000_000u32),
+        here("Outbid not refunded", line!(), column!())
+    );
+
+    // Past the original end, still running
+    advance(router, 250);
+    let res = settle(router, 1);
+    ensure!(res.is_err(), here("Settled early", line!(), column!()));
+
+    advance(router, 51);
+    let res = bid(router, &max.address, 1_300);
+    ensure!(res.is_err(), here("Bid after end", line!(), column!()));
+    let res = router.execute_contract(
+        john.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::DeleteListing {
+            listing_id: 1,
+            destination: None,
+        },
+        &[],
+    );
+    ensure!(res.is_err(), here("Auction with bid deleted", line!(), column!()));
+    let res = router.execute_contract(
+        max.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::ProcessExpired {
+            limit: None,
+        },
+        &[],
+    );
+    ensure!(res.is_err(), here("Auction with bid processed", line!(), column!()));
+
+    // Max settles | 0.5% fee of 1_200 = 6, 1% royalty of 1_194 = 11
+    settle(router, 1)?;
+    ensure!(
+        neonpeepz.owner_of(&router.wrap(), "1".to_string(), false)?.owner == sam.address.as_str(),
+        here("Winner didn't get NFT", line!(), column!())
+    );
+    ensure!(
+        native_balance(router, &john.address)? == Uint128::from(1_183u32),
+        here("Seller proceeds", line!(), column!())
+    );
+    ensure!(
+        native_balance(router, &payout.address)? == Uint128::from(11u32),
+        here("Royalty not paid", line!(), column!())
+    );
+    ensure!(get_listing(router).is_err(), here("Settled auction not removed", line!(), column!()));
+
+    // 2: No bids, NeonPeepz #2 goes back to John
+    list(router, 2, "2", ujunox_ask(1_000))?;
+    advance(router, 1_001);
+    settle(router, 2)?;
+    ensure!(
+        neonpeepz.owner_of(&router.wrap(), "2".to_string(), false)?.owner == john.address.as_str(),
+        here("Unsold NFT not returned", line!(), column!())
+    );
+
+    // 3: NeonPeepz #2 with a 100 ujunox flat fee
+    router.execute_contract(
+        contract_admin.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::UpdateFeeSchedule {
+            denoms: vec![FeeDenomUnvalidated {
+                asset: FeeAssetUnvalidated::Native(VALID_NATIVE.to_string()),
+                fee_bps: None,
+            }],
+            mode: FeeMode::Rotation,
+            any_fungible: None,
+            flat_fee: Some(FlatFeeUnvalidated {
+                asset: FeeAssetUnvalidated::Native(VALID_NATIVE.to_string()),
+                amount: Uint128::new(100),
+                mode: FlatFeeMode::Always,
+            }),
+        },
+        &[],
+    )?;
+    list(router, 3, "2", ujunox_ask(1_000))?;
+    router.execute_contract(
+        sam.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::PlaceBid {
+            listing_id: 3,
+        },
+        &coins(1_000, VALID_NATIVE),
+    )?;
+
+    // Flat fee raised to 900 ujunox before settling
+    router.execute_contract(
+        contract_admin.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::UpdateFeeSchedule {
+            denoms: vec![FeeDenomUnvalidated {
+                asset: FeeAssetUnvalidated::Native(VALID_NATIVE.to_string()),
+                fee_bps: None,
+            }],
+            mode: FeeMode::Rotation,
+            any_fungible: None,
+            flat_fee: Some(FlatFeeUnvalidated {
+                asset: FeeAssetUnvalidated::Native(VALID_NATIVE.to_string()),
+                amount: Uint128::new(900),
+                mode: FlatFeeMode::Always,
+            }),
+        },
+        &[],
+    )?;
+    advance(router, 1_001);
+
+    // 0.5% fee of 1_000 = 5, flat fee 100 (set at finalize), 1% royalty of 895 = 8
+    let john_before = native_balance(router, &john.address)?;
+    settle(router, 3)?;
+    ensure!(
+        native_balance(router, &john.address)? == john_before + Uint128::from(887u32),
+        here("Seller proceeds less flat fee", line!(), column!())
+    );
+
+    Ok(())
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Dutch Auctions
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// <X> Floor must be below the start price, step can't be 0
+// <X> Price drops linearly from the start price to the floor over the listing window
+// <X> Stepwise price only drops once per step
+// <X> Bucket must meet the current price, surplus is refunded
+// <X> Dutch auctions can't be bid on
+#[test]
+fn dutch_auctions() -> Result<(), anyhow::Error> {
+    use crate::query::{CurrentPriceResponse, SingleBucketResponse};
+    use anyhow::Result;
+    use cw_multi_test::AppResponse;
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+
+    // Instantiate all contracts
+    let (_jvone, _jvtwo, _jvtre, neonpeepz, _shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+    let router = give_natives(&sam, &mut router);
+
+    // Start price 10_000 ujunox
+    let list = |router: &mut App,
+                listing_id: u64,
+                token_id: &str,
+                floor: u128,
+                step_seconds: Option<u64>|
+     -> Result<AppResponse> {
+        let create_msg = CreateListingMsg {
+            ask: GenericBalanceUnvalidated {
+                native: coins(10_000, VALID_NATIVE),
+                cw20: vec![],
+                nfts: vec![],
+            },
+            whitelisted_buyer: None,
+            partial_fill: None,
+            auction: Some(AuctionMsg::Dutch {
+                floor: Uint128::from(floor),
+                step_seconds,
+            }),
+        };
+        list_nft(
+            router,
+            &john.address,
+            &neonpeepz,
+            &fuzionmarket,
+            listing_id,
+            token_id,
+            create_msg,
+            Some(1_000),
+        )
+    };
+    let buy =
+        |router: &mut App, listing_id: u64, bucket_id: u64, amount: u128| -> Result<AppResponse> {
+            router.execute_contract(
+                sam.address.clone(),
+                fuzionmarket.clone(),
+                &ExecuteMsg::CreateBucket {
+                    bucket_id,
+                },
+                &coins(amount, VALID_NATIVE),
+            )?;
+            router.execute_contract(
+                sam.address.clone(),
+                fuzionmarket.clone(),
+                &ExecuteMsg::BuyListing {
+                    listing_id,
+                    bucket_id,
+                    quantity: None,
+                },
+                &[],
+            )
+        };
+    let price = |router: &App, listing_id: u64| -> Result<Vec<Coin>> {
+        let res: CurrentPriceResponse = router.wrap().query_wasm_smart(
+            fuzionmarket.clone(),
+            &QueryMsg::GetCurrentPrice {
+                listing_id,
+            },
+        )?;
+        Ok(res.price.native)
+    };
+
+    let res = list(router, 1, "1", 10_000, None);
+    ensure!(res.is_err(), here("Floor at start price", line!(), column!()));
+    let res = list(router, 1, "1", 2_000, Some(0));
+    ensure!(res.is_err(), here("0 second step", line!(), column!()));
+
+    // 1: Linear from 10_000 to 2_000 over 1_000 seconds
+    list(router, 1, "1", 2_000, None)?;
+    ensure!(
+        price(router, 1)? == coins(10_000, VALID_NATIVE),
+        here("Start price", line!(), column!())
+    );
+
+    let res = router.execute_contract(
+        sam.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::PlaceBid {
+            listing_id: 1,
+        },
+        &coins(10_000, VALID_NATIVE),
+    );
+    ensure!(res.is_err(), here("Bid on Dutch auction", line!(), column!()));
+
+    advance(router, 250);
+    ensure!(
+        price(router, 1)? == coins(8_000, VALID_NATIVE),
+        here("Linear price", line!(), column!())
+    );
+
+    let res = buy(router, 1, 1, 7_999);
+    ensure!(res.is_err(), here("Bought under current price", line!(), column!()));
+
+    // Sam pays 9_000 for an 8_000 price, 1_000 is refunded
+    buy(router, 1, 2, 9_000)?;
+    ensure!(
+        router.wrap().query_balance(sam.address.to_string(), VALID_NATIVE)?.amount
+            == Uint128::from(100_000_000u32 - 7_999 - 8_000),
+        here("Surplus not refunded", line!(), column!())
+    );
+    let res: SingleBucketResponse = router.wrap().query_wasm_smart(
+        fuzionmarket.clone(),
+        &QueryMsg::GetBucket {
+            bucket_id: 2,
+        },
+    )?;
+    ensure!(
+        res.bucket.owner == john.address && res.bucket.funds.native == coins(7_960, VALID_NATIVE),
+        here("Seller bucket", line!(), column!())
+    );
+    router.execute_contract(
+        sam.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::WithdrawPurchased {
+            listing_id: 1,
+            destination: None,
+        },
+        &[],
+    )?;
+    ensure!(
+        neonpeepz.owner_of(&router.wrap(), "1".to_string(), false)?.owner == sam.address.as_str(),
+        here("Buyer didn't get NFT", line!(), column!())
+    );
+
+    // 2: Drops every 300 seconds
+    list(router, 2, "2", 2_000, Some(300))?;
+    advance(router, 299);
+    ensure!(
+        price(router, 2)? == coins(10_000, VALID_NATIVE),
+        here("Dropped before step", line!(), column!())
+    );
+    advance(router, 1);
+    ensure!(
+        price(router, 2)? == coins(7_600, VALID_NATIVE),
+        here("Step price", line!(), column!())
+    );
+
+    // Floor once ended
+    advance(router, 1_000);
+    ensure!(
+        price(router, 2)? == coins(2_000, VALID_NATIVE),
+        here("Floor price", line!(), column!())
+    );
+
+    Ok(())
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Sealed Bid Auctions
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// <X> Commitments need a deposit of the ask asset of at least the reserve, one per bidder
+// <X> Bids can only be committed in the bid phase & revealed in the reveal phase
+// <X> Reveal must match the commitment
+// <X> Another bidder's commitment can't be copied
+// <X> Highest reveal wins, outbid deposits are refunded
+// <X> Settles after the reveal phase, winner's deposit surplus is refunded
+// <X> Unrevealed deposits are claimed after the reveal phase, forfeited to the seller
+//     or refunded, per auction
+// <X> Commitments per Listing are capped by max_sealed_bids
+#[test]
+fn sealed_bid_auctions() -> Result<(), anyhow::Error> {
+    use anyhow::Result;
+    use cosmwasm_std::Binary;
+    use cw_multi_test::AppResponse;
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+    let ann = create_users::fake_user("ann".to_string());
+
+    // Instantiate all contracts
+    let (_jvone, _jvtwo, _jvtre, neonpeepz, _shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+    let router = give_natives(&sam, &mut router);
+    let router = give_natives(&max, router);
+    let router = give_natives(&ann, router);
+
+    // Reserve 1_000 ujunox, bid phase 1_000 seconds, reveal phase 600 seconds
+    let list = |router: &mut App,
+                listing_id: u64,
+                token_id: &str,
+                forfeit_unrevealed: bool|
+     -> Result<AppResponse> {
+        let create_msg = CreateListingMsg {
+            ask: GenericBalanceUnvalidated {
+                native: coins(1_000, VALID_NATIVE),
+                cw20: vec![],
+                nfts: vec![],
+            },
+            whitelisted_buyer: None,
+            partial_fill: None,
+            auction: Some(AuctionMsg::SealedBid {
+                reveal_seconds: 600,
+                forfeit_unrevealed,
+            }),
+        };
+        list_nft(
+            router,
+            &john.address,
+            &neonpeepz,
+            &fuzionmarket,
+            listing_id,
+            token_id,
+            create_msg,
+            Some(1_000),
+        )
+    };
+    let commit = |router: &mut App,
+                  bidder: &Addr,
+                  listing_id: u64,
+                  commitment: Binary,
+                  deposit: u128|
+     -> Result<AppResponse> {
+        router.execute_contract(
+            bidder.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::CommitBid {
+                listing_id,
+                commitment,
+            },
+            &coins(deposit, VALID_NATIVE),
+        )
+    };
+    let reveal =
+        |router: &mut App, bidder: &Addr, amount: u128, salt: &str| -> Result<AppResponse> {
+            router.execute_contract(
+                bidder.clone(),
+                fuzionmarket.clone(),
+                &ExecuteMsg::RevealBid {
+                    listing_id: 1,
+                    amount: Uint128::from(amount),
+                    salt: salt.to_string(),
+                },
+                &[],
+            )
+        };
+    let settle = |router: &mut App, listing_id: u64| -> Result<AppResponse> {
+        router.execute_contract(
+            max.address.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::SettleAuction {
+                listing_id,
+            },
+            &[],
+        )
+    };
+    let claim = |router: &mut App,
+                 sender: &Addr,
+                 listing_id: u64,
+                 bidder: Option<&Addr>|
+     -> Result<AppResponse> {
+        router.execute_contract(
+            sender.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::ClaimDeposit {
+                listing_id,
+                bidder: bidder.map(Addr::to_string),
+            },
+            &[],
+        )
+    };
+    let hash = |listing_id: u64, bidder: &Addr, amount: u128, salt: &str| {
+        SealedBidAuction::commitment(listing_id, bidder, Uint128::from(amount), salt)
+    };
+
+    // 1: Unrevealed deposits are forfeited
+    list(router, 1, "1", true)?;
+
+    let res = commit(router, &sam.address, 1, hash(1, &sam.address, 900, "sam"), 900);
+    ensure!(res.is_err(), here("Deposit under reserve", line!(), column!()));
+    let res = commit(router, &sam.address, 1, Binary::from(vec![1u8; 8]), 2_000);
+    ensure!(res.is_err(), here("Commitment not a hash", line!(), column!()));
+
+    // Deposits hide the bids
+    commit(router, &sam.address, 1, hash(1, &sam.address, 1_500, "sam"), 2_000)?;
+
+    // Max can't copy Sam's commitment, it would only reveal for Sam anyway
+    let res = commit(router, &max.address, 1, hash(1, &sam.address, 1_500, "sam"), 2_000);
+    ensure!(res.is_err(), here("Copied commitment accepted", line!(), column!()));
+
+    commit(router, &max.address, 1, hash(1, &max.address, 1_800, "max"), 1_800)?;
+    commit(router, &ann.address, 1, hash(1, &ann.address, 3_000, "ann"), 5_000)?;
+    let res = commit(router, &sam.address, 1, hash(1, &sam.address, 1_600, "sam"), 2_000);
+    ensure!(res.is_err(), here("Committed twice", line!(), column!()));
+    let res = reveal(router, &sam.address, 1_500, "sam");
+    ensure!(res.is_err(), here("Revealed in bid phase", line!(), column!()));
+    let res = router.execute_contract(
+        john.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::DeleteListing {
+            listing_id: 1,
+            destination: None,
+        },
+        &[],
+    );
+    ensure!(res.is_err(), here("Deleted with commitments", line!(), column!()));
+
+    // Reveal phase
+    advance(router, 1_001);
+    let res = commit(router, &john.address, 1, hash(1, &john.address, 1_000, "john"), 1_000);
+    ensure!(res.is_err(), here("Committed in reveal phase", line!(), column!()));
+    let res = reveal(router, &sam.address, 1_500, "wrong");
+    ensure!(res.is_err(), here("Reveal didn't match", line!(), column!()));
+
+    reveal(router, &sam.address, 1_500, "sam")?;
+    let res = reveal(router, &sam.address, 1_500, "sam");
+    ensure!(res.is_err(), here("Revealed twice", line!(), column!()));
+
+    // Max outbids Sam, who is refunded
+    reveal(router, &max.address, 1_800, "max")?;
+    ensure!(
+        native_balance(router, &sam.address)? == Uint128::from(100_000_000u32),
+        here("Outbid deposit not refunded", line!(), column!())
+    );
+
+    let res = settle(router, 1);
+    ensure!(res.is_err(), here("Settled in reveal phase", line!(), column!()));
+    let res = claim(router, &ann.address, 1, None);
+    ensure!(res.is_err(), here("Claimed in reveal phase", line!(), column!()));
+
+    // Max wins | 0.5% fee of 1_800 = 9
+    advance(router, 600);
+    let res = claim(router, &max.address, 1, None);
+    ensure!(res.is_err(), here("Winner claimed deposit", line!(), column!()));
+    settle(router, 1)?;
+    ensure!(
+        neonpeepz.owner_of(&router.wrap(), "1".to_string(), false)?.owner == max.address.as_str(),
+        here("Winner didn't get NFT", line!(), column!())
+    );
+    ensure!(
+        native_balance(router, &max.address)? == Uint128::from(100_000_000u32 - 1_800),
+        here("Winner paid more than the bid", line!(), column!())
+    );
+    ensure!(
+        native_balance(router, &john.address)? == Uint128::from(1_791u32),
+        here("Seller proceeds", line!(), column!())
+    );
+    let res = claim(router, &max.address, 1, None);
+    ensure!(res.is_err(), here("Winner claimed deposit", line!(), column!()));
+
+    // Anyone can claim Ann's unrevealed 5_000, forfeited to John
+    claim(router, &max.address, 1, Some(&ann.address))?;
+    ensure!(
+        native_balance(router, &john.address)? == Uint128::from(1_791u32 + 5_000),
+        here("Unrevealed deposit not forfeited", line!(), column!())
+    );
+    ensure!(
+        native_balance(router, &ann.address)? == Uint128::from(100_000_000u32 - 5_000),
+        here("Unrevealed deposit refunded", line!(), column!())
+    );
+    let res = claim(router, &ann.address, 1, None);
+    ensure!(res.is_err(), here("Claimed twice", line!(), column!()));
+
+    // 2: Max 1 commitment per Listing
+    router.execute_contract(
+        contract_admin.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::UpdateConfig {
+            min_expiration_seconds: None,
+            max_expiration_seconds: None,
+            max_num_assets: None,
+            max_royalty_bps: None,
+            fee_bps: None,
+            fee_cycle_seconds: None,
+            expiry_bounty: None,
+            max_flat_fee: None,
+            max_sealed_bids: Some(1),
+        },
+        &[],
+    )?;
+
+    // Unrevealed deposits are refunded, no reveals returns the NFT
+    list(router, 2, "2", false)?;
+    commit(router, &sam.address, 2, hash(2, &sam.address, 1_500, "sam"), 2_000)?;
+    let res = commit(router, &ann.address, 2, hash(2, &ann.address, 1_500, "ann"), 2_000);
+    ensure!(res.is_err(), here("Committed over max_sealed_bids", line!(), column!()));
+    advance(router, 1_601);
+    settle(router, 2)?;
+    ensure!(
+        neonpeepz.owner_of(&router.wrap(), "2".to_string(), false)?.owner == john.address.as_str(),
+        here("Unsold NFT not returned", line!(), column!())
+    );
+    claim(router, &sam.address, 2, None)?;
+    ensure!(
+        native_balance(router, &sam.address)? == Uint128::from(100_000_000u32),
+        here("Unrevealed deposit not refunded", line!(), column!())
+    );
+
+    Ok(())
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// One Step Purchases
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// <X> Natives sent must cover the price, surplus is refunded
+// <X> CW20 sent must cover the price, surplus is refunded
+// <X> Purchased assets & payment are sent right away, less fees
+// <X> CW20s & NFTs in the price are pulled through allowances & approvals
+// <X> Approvals can't be used to pay with someone else's NFT
+// <X> A CW20 flat fee is taken out of the CW20 sent, or pulled through an allowance
+// <X> A CW20 flat fee can't be routed to the Community Pool, nor can pending CW20 fees
+#[test]
+fn one_step_purchases() -> Result<(), anyhow::Error> {
+    use anyhow::Result;
+    use cw_multi_test::AppResponse;
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+
+    // Instantiate all contracts
+    let (jvone, _jvtwo, _jvtre, neonpeepz, _shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+    let router = give_natives(&sam, &mut router);
+    let router = give_natives(&max, router);
+
+    let list = |router: &mut App,
+                listing_id: u64,
+                token_id: &str,
+                ask: GenericBalanceUnvalidated|
+     -> Result<AppResponse> {
+        let create_msg = CreateListingMsg {
+            ask,
+            whitelisted_buyer: None,
+            partial_fill: None,
+            auction: None,
+        };
+        list_nft(
+            router,
+            &john.address,
+            &neonpeepz,
+            &fuzionmarket,
+            listing_id,
+            token_id,
+            create_msg,
+            Some(1_000),
+        )
+    };
+    let buy_with_funds = |router: &mut App, amount: u128| -> Result<AppResponse> {
+        router.execute_contract(
+            sam.address.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::BuyListingWithFunds {
+                listing_id: 1,
+            },
+            &coins(amount, VALID_NATIVE),
+        )
+    };
+    let buy_cw20 = |router: &mut App, amount: u128| -> Result<AppResponse> {
+        router.execute_contract(
+            sam.address.clone(),
+            jvone.addr(),
+            &cw20_base::msg::ExecuteMsg::Send {
+                contract: fuzionmarket.to_string(),
+                amount: Uint128::from(amount),
+                msg: to_binary(&ReceiveMsg::BuyListingCw20 {
+                    listing_id: 2,
+                })?,
+            },
+            &[],
+        )
+    };
+    let buy_with_allowances = |router: &mut App, buyer: &Addr| -> Result<AppResponse> {
+        router.execute_contract(
+            buyer.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::BuyListingWithAllowances {
+                listing_id: 3,
+            },
+            &coins(100, VALID_NATIVE),
+        )
+    };
+    let jvone_ask = |amount: u32| GenericBalanceUnvalidated {
+        native: vec![],
+        cw20: vec![Cw20CoinUnverified {
+            address: jvone.addr().to_string(),
+            amount: Uint128::from(amount),
+        }],
+        nfts: vec![],
+    };
+
+    // 1: NeonPeepz #1 for 1_000 ujunox
+    list(
+        router,
+        1,
+        "1",
+        GenericBalanceUnvalidated {
+            native: coins(1_000, VALID_NATIVE),
+            cw20: vec![],
+            nfts: vec![],
+        },
+    )?;
+    let res = buy_with_funds(router, 999);
+    ensure!(res.is_err(), here("Bought under price", line!(), column!()));
+
+    // 0.5% fee of 1_000 = 5, 200 refunded
+    buy_with_funds(router, 1_200)?;
+    ensure!(
+        neonpeepz.owner_of(&router.wrap(), "1".to_string(), false)?.owner == sam.address.as_str(),
+        here("Buyer didn't get NFT", line!(), column!())
+    );
+    ensure!(
+        native_balance(router, &sam.address)? == Uint128::from(100_000_000u32 - 1_000),
+        here("Surplus not refunded", line!(), column!())
+    );
+    ensure!(
+        native_balance(router, &john.address)? == Uint128::from(995u32),
+        here("Seller not paid", line!(), column!())
+    );
+    let res = buy_with_funds(router, 1_000);
+    ensure!(res.is_err(), here("Bought twice", line!(), column!()));
+
+    // 2: NeonPeepz #2 for 10 JVONE
+    list(router, 2, "2", jvone_ask(10))?;
+    let res = buy_cw20(router, 9);
+    ensure!(res.is_err(), here("Bought under price", line!(), column!()));
+    buy_cw20(router, 12)?;
+    ensure!(
+        neonpeepz.owner_of(&router.wrap(), "2".to_string(), false)?.owner == sam.address.as_str(),
+        here("Buyer didn't get NFT", line!(), column!())
+    );
+    assert_eq!(jvone.balance(&router.wrap(), sam.address.clone()), Ok(Uint128::from(90u32)));
+    assert_eq!(jvone.balance(&router.wrap(), john.address.clone()), Ok(Uint128::from(110u32)));
+
+    // 3: Max sells 5_000 ujunox for 100 ujunox, 5 JVONE & NeonPeepz #3
+    router.execute_contract(
+        max.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::CreateListing {
+            listing_id: 3,
+            create_msg: CreateListingMsg {
+                ask: GenericBalanceUnvalidated {
+                    native: coins(100, VALID_NATIVE),
+                    cw20: jvone_ask(5).cw20,
+                    nfts: vec![NftUnverified {
+                        contract_address: neonpeepz.addr().to_string(),
+                        token_id: "3".to_string(),
+                    }],
+                },
+                whitelisted_buyer: None,
+                partial_fill: None,
+                auction: None,
+            },
+        },
+        &coins(5_000, VALID_NATIVE),
+    )?;
+    router.execute_contract(
+        max.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::Finalize {
+            listing_id: 3,
+            seconds: 1_000,
+        },
+        &[],
+    )?;
+
+    let res = buy_with_allowances(router, &sam.address);
+    ensure!(res.is_err(), here("Bought without allowances", line!(), column!()));
+
+    router.execute_contract(
+        sam.address.clone(),
+        jvone.addr(),
+        &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
+            spender: fuzionmarket.to_string(),
+            amount: Uint128::from(5u32),
+            expires: None,
+        },
+        &[],
+    )?;
+    router.execute_contract(
+        sam.address.clone(),
+        neonpeepz.addr(),
+        &cw721_base::ExecuteMsg::<Option<Empty>, Empty>::Approve {
+            spender: fuzionmarket.to_string(),
+            token_id: "3".to_string(),
+            expires: None,
+        },
+        &[],
+    )?;
+
+    // Sam's approval can't be used by John
+    let router = give_natives(&john, router);
+    let res = buy_with_allowances(router, &john.address);
+    ensure!(res.is_err(), here("Paid with someone else's NFT", line!(), column!()));
+
+    // 0.5% fee of 5_000 = 25
+    let before = native_balance(router, &sam.address)?;
+    buy_with_allowances(router, &sam.address)?;
+    ensure!(
+        native_balance(router, &sam.address)?
+            == before + Uint128::from(4_975u32) - Uint128::from(100u32),
+        here("Buyer didn't get Listing", line!(), column!())
+    );
+    ensure!(
+        neonpeepz.owner_of(&router.wrap(), "3".to_string(), false)?.owner == max.address.as_str(),
+        here("Seller didn't get NFT", line!(), column!())
+    );
+    assert_eq!(jvone.balance(&router.wrap(), max.address.clone()), Ok(Uint128::from(105u32)));
+    assert_eq!(jvone.balance(&router.wrap(), sam.address.clone()), Ok(Uint128::from(85u32)));
+
+    // 4: Flat fee of 2 JVONE, Sam sells NeonPeepz #4 for 10 JVONE
+    let cw20_flat_fee = ExecuteMsg::UpdateFeeSchedule {
+        denoms: vec![FeeDenomUnvalidated {
+            asset: FeeAssetUnvalidated::Native(VALID_NATIVE.to_string()),
+            fee_bps: None,
+        }],
+        mode: FeeMode::Rotation,
+        any_fungible: None,
+        flat_fee: Some(FlatFeeUnvalidated {
+            asset: FeeAssetUnvalidated::Cw20(jvone.addr().to_string()),
+            amount: Uint128::from(2u32),
+            mode: FlatFeeMode::Always,
+        }),
+    };
+
+    // Default routes send to the Community Pool, which can't take CW20s
+    let res: Result<AppResponse> =
+        router.execute_contract(contract_admin.address.clone(), fuzionmarket.clone(), &cw20_flat_fee, &[]);
+    ensure!(res.is_err(), here("CW20 flat fee routed to Community Pool", line!(), column!()));
+
+    let treasury_routes = ExecuteMsg::UpdateFeeRoutes {
+        routes: vec![FeeRouteUnvalidated {
+            destination: FeeDestinationUnvalidated::Address(contract_admin.address.to_string()),
+            bps: 10_000,
+        }],
+    };
+    router.execute_contract(contract_admin.address.clone(), fuzionmarket.clone(), &treasury_routes, &[])?;
+    router.execute_contract(contract_admin.address.clone(), fuzionmarket.clone(), &cw20_flat_fee, &[])?;
+    let create_msg = CreateListingMsg {
+        ask: jvone_ask(10),
+        whitelisted_buyer: None,
+        partial_fill: None,
+        auction: None,
+    };
+    list_nft(router, &sam.address, &neonpeepz, &fuzionmarket, 4, "4", create_msg, Some(1_000))?;
+
+    let buy_cw20 = |router: &mut App, amount: u128| -> Result<AppResponse> {
+        router.execute_contract(
+            max.address.clone(),
+            jvone.addr(),
+            &cw20_base::msg::ExecuteMsg::Send {
+                contract: fuzionmarket.to_string(),
+                amount: Uint128::from(amount),
+                msg: to_binary(&ReceiveMsg::BuyListingCw20 {
+                    listing_id: 4,
+                })?,
+            },
+            &[],
+        )
+    };
+    let res = buy_cw20(router, 10);
+    ensure!(res.is_err(), here("Bought without flat fee", line!(), column!()));
+
+    // 2 JVONE flat fee taken out of the 13 sent, 1 refunded
+    let market_before = jvone.balance(&router.wrap(), fuzionmarket.clone())?;
+    buy_cw20(router, 13)?;
+    ensure!(
+        neonpeepz.owner_of(&router.wrap(), "4".to_string(), false)?.owner == max.address.as_str(),
+        here("Buyer didn't get NFT", line!(), column!())
+    );
+    assert_eq!(jvone.balance(&router.wrap(), max.address.clone()), Ok(Uint128::from(93u32)));
+    assert_eq!(jvone.balance(&router.wrap(), sam.address.clone()), Ok(Uint128::from(95u32)));
+    assert_eq!(
+        jvone.balance(&router.wrap(), fuzionmarket.clone()),
+        Ok(market_before + Uint128::from(2u32))
+    );
+
+    // 5: Max buys Sam's NeonPeepz #1 for 100 ujunox, the flat fee is pulled
+    let create_msg = CreateListingMsg {
+        ask: GenericBalanceUnvalidated {
+            native: coins(100, VALID_NATIVE),
+            cw20: vec![],
+            nfts: vec![],
+        },
+        whitelisted_buyer: None,
+        partial_fill: None,
+        auction: None,
+    };
+    list_nft(router, &sam.address, &neonpeepz, &fuzionmarket, 5, "1", create_msg, Some(1_000))?;
+    let buy = ExecuteMsg::BuyListingWithFunds {
+        listing_id: 5,
+    };
+
+    let res: Result<AppResponse> =
+        router.execute_contract(max.address.clone(), fuzionmarket.clone(), &buy, &coins(100, VALID_NATIVE));
+    ensure!(res.is_err(), here("Bought without flat fee allowance", line!(), column!()));
+
+    router.execute_contract(
+        max.address.clone(),
+        jvone.addr(),
+        &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
+            spender: fuzionmarket.to_string(),
+            amount: Uint128::from(2u32),
+            expires: None,
+        },
+        &[],
+    )?;
+    router.execute_contract(max.address.clone(), fuzionmarket.clone(), &buy, &coins(100, VALID_NATIVE))?;
+    ensure!(
+        neonpeepz.owner_of(&router.wrap(), "1".to_string(), false)?.owner == max.address.as_str(),
+        here("Buyer didn't get NFT", line!(), column!())
+    );
+    assert_eq!(jvone.balance(&router.wrap(), max.address.clone()), Ok(Uint128::from(91u32)));
+
+    // Back to the default routes with no CW20 fees charged, blocked until the JVONE fees are swept
+    router.execute_contract(
+        contract_admin.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::UpdateFeeSchedule {
+            denoms: vec![FeeDenomUnvalidated {
+                asset: FeeAssetUnvalidated::Native(VALID_NATIVE.to_string()),
+                fee_bps: None,
+            }],
+            mode: FeeMode::Rotation,
+            any_fungible: None,
+            flat_fee: None,
+        },
+        &[],
+    )?;
+    let default_routes = ExecuteMsg::UpdateFeeRoutes {
+        routes: vec![FeeRouteUnvalidated {
+            destination: FeeDestinationUnvalidated::CommunityPool(CommunityPoolMsg::Stargate),
+            bps: 10_000,
+        }],
+    };
+    let res: Result<AppResponse> =
+        router.execute_contract(contract_admin.address.clone(), fuzionmarket.clone(), &default_routes, &[]);
+    ensure!(res.is_err(), here("Pending CW20 fees routed to Community Pool", line!(), column!()));
+
+    router.execute_contract(
+        max.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::SweepFees {
+            denoms: Some(vec![jvone.addr().to_string()]),
+        },
+        &[],
+    )?;
+    router.execute_contract(contract_admin.address.clone(), fuzionmarket.clone(), &default_routes, &[])?;
+
+    Ok(())
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Batches
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// <X> Actions run in order as the sender
+// <X> One failed Action fails the whole Batch
+// <X> Natives sent must be exactly the Actions' funds
+// <X> Empty & nested Batches are rejected
+#[test]
+fn batches() -> Result<(), anyhow::Error> {
+    use crate::query::{SingleBucketResponse, SingleListingResponse};
+    use anyhow::Result;
+    use cw_multi_test::AppResponse;
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+
+    // Instantiate all contracts
+    let (_jvone, _jvtwo, _jvtre, _neonpeepz, _shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+    let router = give_natives(&sam, &mut router);
+    let router = give_natives(&max, router);
+
+    let batch =
+        |router: &mut App, user: &Addr, actions: Vec<Action>, sent: u128| -> Result<AppResponse> {
+            let funds = if sent == 0 {
+                vec![]
+            } else {
+                coins(sent, VALID_NATIVE)
+            };
+            router.execute_contract(
+                user.clone(),
+                fuzionmarket.clone(),
+                &ExecuteMsg::Batch(actions),
+                &funds,
+            )
+        };
+    let create = |listing_id: u64, amount: u128| Action::CreateListing {
+        listing_id,
+        create_msg: CreateListingMsg {
+            ask: GenericBalanceUnvalidated {
+                native: coins(2_000, VALID_NATIVE),
+                cw20: vec![],
+                nfts: vec![],
+            },
+            whitelisted_buyer: None,
+            partial_fill: None,
+            auction: None,
+        },
+        funds: coins(amount, VALID_NATIVE),
+    };
+    let get_listing = |router: &App, listing_id: u64| -> Result<Listing> {
+        let res: SingleListingResponse = router.wrap().query_wasm_smart(
+            fuzionmarket.clone(),
+            &QueryMsg::GetListing {
+                listing_id,
+            },
+        )?;
+        Ok(res.listing)
+    };
+
+    let res = batch(router, &sam.address, vec![], 0);
+    ensure!(res.is_err(), here("Empty batch", line!(), column!()));
+    ensure!(
+        serde_json::from_str::<ExecuteMsg>(r#"{"batch":[{"batch":[]}]}"#).is_err(),
+        here("Nested batch", line!(), column!())
+    );
+
+    // Natives sent must match the Actions' funds
+    let res = batch(router, &sam.address, vec![create(1, 1_000)], 900);
+    ensure!(res.is_err(), here("Funds under Actions", line!(), column!()));
+    let res = batch(router, &sam.address, vec![create(1, 1_000)], 1_100);
+    ensure!(res.is_err(), here("Funds over Actions", line!(), column!()));
+
+    // Finalize fails, so the Listing isn't created
+    let res = batch(
+        router,
+        &sam.address,
+        vec![
+            create(1, 1_000),
+            Action::Finalize {
+                listing_id: 1,
+                seconds: 1,
+            },
+        ],
+        1_000,
+    );
+    ensure!(res.is_err(), here("Invalid Finalize", line!(), column!()));
+    ensure!(get_listing(router, 1).is_err(), here("Half-built Listing", line!(), column!()));
+
+    // 1: Sam creates, adds to & finalizes a Listing of 1_500 ujunox for 2_000 ujunox
+    batch(
+        router,
+        &sam.address,
+        vec![
+            create(1, 1_000),
+            Action::AddToListing {
+                listing_id: 1,
+                funds: coins(500, VALID_NATIVE),
+            },
+            Action::Finalize {
+                listing_id: 1,
+                seconds: 1_000,
+            },
+        ],
+        1_500,
+    )?;
+    let listing = get_listing(router, 1)?;
+    ensure!(
+        listing.status == Status::FinalizedReady
+            && listing.for_sale.native == coins(1_500, VALID_NATIVE),
+        here("Listing not built", line!(), column!())
+    );
+
+    // Max creates a Bucket, buys & withdraws
+    // 0.5% fee of 1_500 = 7, 0.5% fee of 2_000 = 10
+    batch(
+        router,
+        &max.address,
+        vec![
+            Action::CreateBucket {
+                bucket_id: 1,
+                funds: coins(2_000, VALID_NATIVE),
+            },
+            Action::BuyListing {
+                listing_id: 1,
+                bucket_id: 1,
+                quantity: None,
+                funds: vec![],
+            },
+            Action::WithdrawPurchased {
+                listing_id: 1,
+                destination: None,
+            },
+        ],
+        2_000,
+    )?;
+    ensure!(
+        router.wrap().query_balance(max.address.to_string(), VALID_NATIVE)?.amount
+            == Uint128::from(100_000_000u32 - 2_000 + 1_493),
+        here("Purchase not withdrawn", line!(), column!())
+    );
+    let res: SingleBucketResponse = router.wrap().query_wasm_smart(
+        fuzionmarket.clone(),
+        &QueryMsg::GetBucket {
+            bucket_id: 1,
+        },
+    )?;
+    ensure!(
+        res.bucket.owner == sam.address && res.bucket.funds.native == coins(1_990, VALID_NATIVE),
+        here("Seller bucket", line!(), column!())
+    );
+
+    Ok(())
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Deposits
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// <X> CW20s & NFTs are pulled through allowances & approvals in one transaction
+// <X> Natives sent are added too, CW20s already held are summed
+// <X> Approvals can't be used to deposit someone else's NFT
+// <X> Duplicates & finalized Listings are rejected
+#[test]
+fn deposits() -> Result<(), anyhow::Error> {
+    use crate::query::{SingleBucketResponse, SingleListingResponse};
+    use anyhow::Result;
+    use cw_multi_test::AppResponse;
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+
+    // Instantiate all contracts
+    let (jvone, jvtwo, _jvtre, neonpeepz, _shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+    let router = give_natives(&john, &mut router);
+    let router = give_natives(&sam, router);
+
+    let cw20 = |contract: &Addr, amount: u32| Cw20CoinUnverified {
+        address: contract.to_string(),
+        amount: Uint128::from(amount),
+    };
+    let nft = |token_id: &str| NftUnverified {
+        contract_address: neonpeepz.addr().to_string(),
+        token_id: token_id.to_string(),
+    };
+    let create = |router: &mut App, user: &Addr, listing_id: u64| -> Result<AppResponse> {
+        router.execute_contract(
+            user.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::CreateListing {
+                listing_id,
+                create_msg: CreateListingMsg {
+                    ask: GenericBalanceUnvalidated {
+                        native: coins(2_000, VALID_NATIVE),
+                        cw20: vec![],
+                        nfts: vec![],
+                    },
+                    whitelisted_buyer: None,
+                    partial_fill: None,
+                    auction: None,
+                },
+            },
+            &coins(1_000, VALID_NATIVE),
+        )
+    };
+    let deposit = |router: &mut App,
+                   user: &Addr,
+                   listing_id: u64,
+                   cw20: Vec<Cw20CoinUnverified>,
+                   nfts: Vec<NftUnverified>,
+                   sent: u128|
+     -> Result<AppResponse> {
+        let funds = if sent == 0 {
+            vec![]
+        } else {
+            coins(sent, VALID_NATIVE)
+        };
+        router.execute_contract(
+            user.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::DepositToListing {
+                listing_id,
+                cw20,
+                nfts,
+            },
+            &funds,
+        )
+    };
+    let allow =
+        |router: &mut App, user: &Addr, contract: &Addr, amount: u32| -> Result<AppResponse> {
+            router.execute_contract(
+                user.clone(),
+                contract.clone(),
+                &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
+                    spender: fuzionmarket.to_string(),
+                    amount: Uint128::from(amount),
+                    expires: None,
+                },
+                &[],
+            )
+        };
+
+    create(router, &sam.address, 1)?;
+    create(router, &john.address, 2)?;
+
+    // Nothing approved yet
+    let res = deposit(router, &sam.address, 1, vec![cw20(&jvone.addr(), 10)], vec![nft("3")], 0);
+    ensure!(res.is_err(), here("Deposited without approvals", line!(), column!()));
+
+    allow(router, &sam.address, &jvone.addr(), 15)?;
+    allow(router, &sam.address, &jvtwo.addr(), 20)?;
+    router.execute_contract(
+        sam.address.clone(),
+        neonpeepz.addr(),
+        &cw721_base::ExecuteMsg::<Option<Empty>, Empty>::ApproveAll {
+            operator: fuzionmarket.to_string(),
+            expires: None,
+        },
+        &[],
+    )?;
+
+    // Sam's approval can't be used by John
+    let res = deposit(router, &john.address, 2, vec![], vec![nft("3")], 0);
+    ensure!(res.is_err(), here("Deposited someone else's NFT", line!(), column!()));
+
+    let res = deposit(router, &sam.address, 1, vec![], vec![nft("3"), nft("3")], 0);
+    ensure!(res.is_err(), here("Deposited duplicate NFTs", line!(), column!()));
+    let res = deposit(router, &sam.address, 1, vec![], vec![], 0);
+    ensure!(res.is_err(), here("Deposited nothing", line!(), column!()));
+
+    // 1: Sam deposits 2 CW20s, 2 NFTs & 500 ujunox, then 5 more JVONE
+    deposit(
+        router,
+        &sam.address,
+        1,
+        vec![cw20(&jvone.addr(), 10), cw20(&jvtwo.addr(), 20)],
+        vec![nft("3"), nft("4")],
+        500,
+    )?;
+    deposit(router, &sam.address, 1, vec![cw20(&jvone.addr(), 5)], vec![], 0)?;
+
+    let res: SingleListingResponse = router.wrap().query_wasm_smart(
+        fuzionmarket.clone(),
+        &QueryMsg::GetListing {
+            listing_id: 1,
+        },
+    )?;
+    ensure!(
+        res.listing.for_sale.native == coins(1_500, VALID_NATIVE)
+            && res.listing.for_sale.cw20.len() == 2
+            && res.listing.for_sale.cw20[0].amount == Uint128::from(15u32)
+            && res.listing.for_sale.nfts.len() == 2,
+        here("Deposit not added", line!(), column!())
+    );
+    ensure!(
+        neonpeepz.owner_of(&router.wrap(), "4".to_string(), false)?.owner == fuzionmarket.as_str(),
+        here("NFT not pulled", line!(), column!())
+    );
+    assert_eq!(jvone.balance(&router.wrap(), sam.address.clone()), Ok(Uint128::from(85u32)));
+    assert_eq!(jvtwo.balance(&router.wrap(), sam.address.clone()), Ok(Uint128::from(80u32)));
+
+    // Finalized Listings can't be deposited to
+    router.execute_contract(
+        sam.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::Finalize {
+            listing_id: 1,
+            seconds: 1_000,
+        },
+        &[],
+    )?;
+    allow(router, &sam.address, &jvone.addr(), 5)?;
+    let res = deposit(router, &sam.address, 1, vec![cw20(&jvone.addr(), 5)], vec![], 0);
+    ensure!(res.is_err(), here("Deposited to finalized Listing", line!(), column!()));
+
+    // Buckets take deposits the same way
+    router.execute_contract(
+        sam.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::CreateBucket {
+            bucket_id: 1,
+        },
+        &coins(100, VALID_NATIVE),
+    )?;
+    router.execute_contract(
+        sam.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::DepositToBucket {
+            bucket_id: 1,
+            cw20: vec![cw20(&jvone.addr(), 5)],
+            nfts: vec![],
+        },
+        &[],
+    )?;
+    let res: SingleBucketResponse = router.wrap().query_wasm_smart(
+        fuzionmarket.clone(),
+        &QueryMsg::GetBucket {
+            bucket_id: 1,
+        },
+    )?;
+    ensure!(
+        res.bucket.funds.native == coins(100, VALID_NATIVE)
+            && res.bucket.funds.cw20[0].amount == Uint128::from(5u32),
+        here("Bucket deposit not added", line!(), column!())
+    );
+
+    Ok(())
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Approved NFT Listings
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// <X> NFTs listed by approval stay in the creator's wallet until sold
+// <X> Creator must own & have approved each NFT, until the Listing expires
+// <X> Moved NFTs are flagged & can't be bought, the purchase fails cleanly
+// <X> Deleting the Listing only returns what was escrowed
+// <X> English & sealed bid auctions can't list NFTs by approval
+// <X> An NFT can't be in two of the creator's Listings
+// <X> WhereIsNft flags NFTs listed by approval, escrowed locations come first
+#[test]
+fn approved_nft_listings() -> Result<(), anyhow::Error> {
+    use crate::query::{AssetLocation, LapsedApprovalsResponse, NftLocationResponse};
+    use anyhow::Result;
+    use cw721::Expiration;
+    use cw_multi_test::AppResponse;
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+
+    // Instantiate all contracts
+    let (_jvone, _jvtwo, _jvtre, neonpeepz, _shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+    let router = give_natives(&john, &mut router);
+    let router = give_natives(&sam, router);
+    let router = give_natives(&max, router);
+
+    let create = |router: &mut App,
+                  user: &Addr,
+                  listing_id: u64,
+                  auction: Option<AuctionMsg>|
+     -> Result<AppResponse> {
+        router.execute_contract(
+            user.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::CreateListing {
+                listing_id,
+                create_msg: CreateListingMsg {
+                    ask: GenericBalanceUnvalidated {
+                        native: coins(2_000, VALID_NATIVE),
+                        cw20: vec![],
+                        nfts: vec![],
+                    },
+                    whitelisted_buyer: None,
+                    partial_fill: None,
+                    auction,
+                },
+            },
+            &coins(1_000, VALID_NATIVE),
+        )
+    };
+    let add_approved =
+        |router: &mut App, user: &Addr, listing_id: u64, token_id: &str| -> Result<AppResponse> {
+            router.execute_contract(
+                user.clone(),
+                fuzionmarket.clone(),
+                &ExecuteMsg::AddApprovedNfts {
+                    listing_id,
+                    nfts: vec![NftUnverified {
+                        contract_address: neonpeepz.addr().to_string(),
+                        token_id: token_id.to_string(),
+                    }],
+                },
+                &[],
+            )
+        };
+    let approve = |router: &mut App,
+                   user: &Addr,
+                   token_id: &str,
+                   expires: Option<Expiration>|
+     -> Result<AppResponse> {
+        router.execute_contract(
+            user.clone(),
+            neonpeepz.addr(),
+            &cw721_base::ExecuteMsg::<Option<Empty>, Empty>::Approve {
+                spender: fuzionmarket.to_string(),
+                token_id: token_id.to_string(),
+                expires,
+            },
+            &[],
+        )
+    };
+    let transfer = |router: &mut App, from: &Addr, to: &Addr| -> Result<AppResponse> {
+        router.execute_contract(
+            from.clone(),
+            neonpeepz.addr(),
+            &cw721_base::ExecuteMsg::<Option<Empty>, Empty>::TransferNft {
+                recipient: to.to_string(),
+                token_id: "3".to_string(),
+            },
+            &[],
+        )
+    };
+    let finalize = |router: &mut App, listing_id: u64| -> Result<AppResponse> {
+        router.execute_contract(
+            sam.address.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::Finalize {
+                listing_id,
+                seconds: 1_000,
+            },
+            &[],
+        )
+    };
+    let buy = |router: &mut App| -> Result<AppResponse> {
+        router.execute_contract(
+            max.address.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::BuyListingWithFunds {
+                listing_id: 1,
+            },
+            &coins(2_000, VALID_NATIVE),
+        )
+    };
+    let lapsed = |router: &App| -> Result<LapsedApprovalsResponse> {
+        Ok(router.wrap().query_wasm_smart(
+            fuzionmarket.clone(),
+            &QueryMsg::GetLapsedApprovals {
+                listing_ids: vec![1, 2, 99],
+            },
+        )?)
+    };
+    let owner_of = |router: &App, token_id: &str| -> Result<String> {
+        Ok(neonpeepz.owner_of(&router.wrap(), token_id.to_string(), false)?.owner)
+    };
+    let where_is = |router: &App, token_id: &str| -> Result<Option<AssetLocation>> {
+        let res: NftLocationResponse = router.wrap().query_wasm_smart(
+            fuzionmarket.clone(),
+            &QueryMsg::WhereIsNft {
+                collection: neonpeepz.addr().to_string(),
+                token_id: token_id.to_string(),
+            },
+        )?;
+        Ok(res.location)
+    };
+
+    // 1: Sam lists 1_000 ujunox & NeonPeepz #3 for 2_000 ujunox
+    create(router, &sam.address, 1, None)?;
+    let res = add_approved(router, &sam.address, 1, "3");
+    ensure!(res.is_err(), here("Listed without approval", line!(), column!()));
+
+    // Approval lapses before the Listing would expire
+    let soon = router.block_info().time.plus_seconds(500);
+    approve(router, &sam.address, "3", Some(Expiration::AtTime(soon)))?;
+
+    // Sam's approval can't be used by John
+    create(router, &john.address, 2, None)?;
+    let res = add_approved(router, &john.address, 2, "3");
+    ensure!(res.is_err(), here("Listed someone else's NFT", line!(), column!()));
+
+    add_approved(router, &sam.address, 1, "3")?;
+    let res = finalize(router, 1);
+    ensure!(res.is_err(), here("Approval lapses before expiration", line!(), column!()));
+    approve(router, &sam.address, "3", None)?;
+    finalize(router, 1)?;
+    ensure!(
+        owner_of(router, "3")? == sam.address.as_str(),
+        here("NFT escrowed", line!(), column!())
+    );
+    ensure!(lapsed(router)?.listings.is_empty(), here("Approval flagged", line!(), column!()));
+
+    // Transfers clear approvals, moving it back doesn't restore it
+    transfer(router, &sam.address, &john.address)?;
+    let res = lapsed(router)?;
+    ensure!(
+        res.listings.len() == 1
+            && res.listings[0].listing_id == 1
+            && res.listings[0].lapsed[0].token_id == "3",
+        here("Moved NFT not flagged", line!(), column!())
+    );
+    let res = buy(router);
+    ensure!(res.is_err(), here("Bought moved NFT", line!(), column!()));
+    transfer(router, &john.address, &sam.address)?;
+    ensure!(!lapsed(router)?.listings.is_empty(), here("Approval restored", line!(), column!()));
+
+    // 0.5% fee of 1_000 = 5, NFT is pulled from Sam & sent to Max
+    approve(router, &sam.address, "3", None)?;
+    buy(router)?;
+    ensure!(
+        owner_of(router, "3")? == max.address.as_str(),
+        here("NFT not sold", line!(), column!())
+    );
+    ensure!(
+        router.wrap().query_balance(max.address.to_string(), VALID_NATIVE)?.amount
+            == Uint128::from(100_000_000u32 - 2_000 + 995),
+        here("Buyer didn't get Listing", line!(), column!())
+    );
+
+    // 3: Deleting returns the natives, the NFT never left Sam
+    create(router, &sam.address, 3, None)?;
+    approve(router, &sam.address, "4", None)?;
+    add_approved(router, &sam.address, 3, "4")?;
+    ensure!(
+        where_is(router, "4")?
+            == Some(AssetLocation::Listing {
+                listing_id: 3,
+                creator: sam.address.clone(),
+                status: Status::BeingPrepared,
+                approved: true,
+            }),
+        here("Approved NFT not flagged", line!(), column!())
+    );
+
+    // NeonPeepz #4 can't be for sale twice
+    create(router, &sam.address, 5, None)?;
+    let res = add_approved(router, &sam.address, 5, "4");
+    ensure!(res.is_err(), here("Listed NFT twice", line!(), column!()));
+
+    // Escrowed in a Bucket, the stale approval in Listing 3 is found after it
+    router.execute_contract(
+        sam.address.clone(),
+        neonpeepz.addr(),
+        &cw721_base::ExecuteMsg::<Option<Empty>, Empty>::SendNft {
+            contract: fuzionmarket.to_string(),
+            token_id: "4".to_string(),
+            msg: to_binary(&ReceiveNftMsg::CreateBucketCw721 {
+                bucket_id: 1,
+            })?,
+        },
+        &[],
+    )?;
+    ensure!(
+        where_is(router, "4")?
+            == Some(AssetLocation::Bucket {
+                bucket_id: 1,
+                owner: sam.address.clone(),
+            }),
+        here("Bucket not found first", line!(), column!())
+    );
+    router.execute_contract(
+        sam.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::RemoveBucket {
+            bucket_id: 1,
+            destination: None,
+        },
+        &[],
+    )?;
+
+    let before = router.wrap().query_balance(sam.address.to_string(), VALID_NATIVE)?.amount;
+    router.execute_contract(
+        sam.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::DeleteListing {
+            listing_id: 3,
+            destination: None,
+        },
+        &[],
+    )?;
+    ensure!(
+        router.wrap().query_balance(sam.address.to_string(), VALID_NATIVE)?.amount
+            == before + Uint128::from(1_000u32)
+            && owner_of(router, "4")? == sam.address.as_str(),
+        here("Delete returned wrong assets", line!(), column!())
+    );
+
+    // 4: English auctions can't list by approval
+    create(
+        router,
+        &sam.address,
+        4,
+        Some(AuctionMsg::English {
+            min_increment: Uint128::from(100u32),
+            extension_seconds: 300,
+        }),
+    )?;
+    let res = add_approved(router, &sam.address, 4, "4");
+    ensure!(res.is_err(), here("Auction listed by approval", line!(), column!()));
+
+    Ok(())
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Withdraw Destinations
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// <X> Withdrawals go to the withdrawer without a destination
+// <X> Address destinations get plain transfers
+// <X> Contract destinations get CW20s & NFTs with their hook msgs, Natives by bank send
+// <X> Invalid destinations are rejected
+#[test]
+fn withdraw_destinations() -> Result<(), anyhow::Error> {
+    use crate::query::SingleBucketResponse;
+    use anyhow::Result;
+    use cw_multi_test::AppResponse;
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+
+    // Instantiate all contracts
+    let (jvone, _jvtwo, _jvtre, neonpeepz, _shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+    // A second market stands in for a vault receiving withdrawals
+    let vault = init_contracts::init_jv_contract(&mut router, &contract_admin.address);
+    let router = give_natives(&sam, &mut router);
+    let router = give_natives(&max, router);
+
+    let create = |router: &mut App, listing_id: u64| -> Result<AppResponse> {
+        router.execute_contract(
+            sam.address.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::CreateListing {
+                listing_id,
+                create_msg: CreateListingMsg {
+                    ask: GenericBalanceUnvalidated {
+                        native: coins(2_000, VALID_NATIVE),
+                        cw20: vec![],
+                        nfts: vec![],
+                    },
+                    whitelisted_buyer: None,
+                    partial_fill: None,
+                    auction: None,
+                },
+            },
+            &coins(1_000, VALID_NATIVE),
+        )
+    };
+    let vault_bucket = |router: &App, bucket_id: u64| -> Result<Bucket> {
+        let res: SingleBucketResponse = router.wrap().query_wasm_smart(
+            vault.clone(),
+            &QueryMsg::GetBucket {
+                bucket_id,
+            },
+        )?;
+        Ok(res.bucket)
+    };
+
+    // 1: Sam lists 1_000 ujunox, 10 JVONE & NeonPeepz #3 for 2_000 ujunox
+    create(router, 1)?;
+    router.execute_contract(
+        sam.address.clone(),
+        jvone.addr(),
+        &cw20_base::msg::ExecuteMsg::Send {
+            contract: fuzionmarket.to_string(),
+            amount: Uint128::from(10u32),
+            msg: to_binary(&ReceiveMsg::AddToListingCw20 {
+                listing_id: 1,
+            })?,
+        },
+        &[],
+    )?;
+    router.execute_contract(
+        sam.address.clone(),
+        neonpeepz.addr(),
+        &cw721_base::ExecuteMsg::<Option<Empty>, Empty>::SendNft {
+            contract: fuzionmarket.to_string(),
+            token_id: "3".to_string(),
+            msg: to_binary(&ReceiveNftMsg::AddToListingCw721 {
+                listing_id: 1,
+            })?,
+        },
+        &[],
+    )?;
+    router.execute_contract(
+        sam.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::Finalize {
+            listing_id: 1,
+            seconds: 1_000,
+        },
+        &[],
+    )?;
+
+    // Max buys with Bucket 1
+    router.execute_contract(
+        max.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::CreateBucket {
+            bucket_id: 1,
+        },
+        &coins(2_000, VALID_NATIVE),
+    )?;
+    router.execute_contract(
+        max.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::BuyListing {
+            listing_id: 1,
+            bucket_id: 1,
+            quantity: None,
+        },
+        &[],
+    )?;
+
+    let withdraw = |router: &mut App,
+                    destination: WithdrawDestinationUnvalidated|
+     -> Result<AppResponse> {
+        router.execute_contract(
+            max.address.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::WithdrawPurchased {
+                listing_id: 1,
+                destination: Some(destination),
+            },
+            &[],
+        )
+    };
+    let res = withdraw(router, WithdrawDestinationUnvalidated::Address("".to_string()));
+    ensure!(res.is_err(), here("Invalid destination", line!(), column!()));
+
+    // Max withdraws into the vault, the hooks open a Bucket per asset class
+    withdraw(
+        router,
+        WithdrawDestinationUnvalidated::Contract {
+            contract_addr: vault.to_string(),
+            cw20_msg: Some(to_binary(&ReceiveMsg::CreateBucketCw20 {
+                bucket_id: 1,
+            })?),
+            nft_msg: Some(to_binary(&ReceiveNftMsg::CreateBucketCw721 {
+                bucket_id: 2,
+            })?),
+        },
+    )?;
+    let cw20_bucket = vault_bucket(router, 1)?;
+    let nft_bucket = vault_bucket(router, 2)?;
+    ensure!(
+        cw20_bucket.owner == fuzionmarket
+            && cw20_bucket.funds.cw20[0].amount == Uint128::from(10u32)
+            && nft_bucket.funds.nfts[0].token_id == "3",
+        here("Hooks not called", line!(), column!())
+    );
+    // 0.5% fee of 1_000 = 5
+    ensure!(
+        native_balance(router, &vault)? == Uint128::from(995u32),
+        here("Natives not sent", line!(), column!())
+    );
+
+    // Sam sends the proceeds (0.5% fee of 2_000 = 10) to John
+    router.execute_contract(
+        sam.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::RemoveBucket {
+            bucket_id: 1,
+            destination: Some(WithdrawDestinationUnvalidated::Address(john.address.to_string())),
+        },
+        &[],
+    )?;
+    ensure!(
+        native_balance(router, &john.address)? == Uint128::from(1_990u32),
+        here("Proceeds not sent", line!(), column!())
+    );
+
+    // 2: Deleting without a destination returns to Sam, with one to John
+    create(router, 2)?;
+    let before = native_balance(router, &sam.address)?;
+    router.execute_contract(
+        sam.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::DeleteListing {
+            listing_id: 2,
+            destination: None,
+        },
+        &[],
+    )?;
+    ensure!(
+        native_balance(router, &sam.address)? == before + Uint128::from(1_000u32),
+        here("Not returned to creator", line!(), column!())
+    );
+    create(router, 3)?;
+    router.execute_contract(
+        sam.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::DeleteListing {
+            listing_id: 3,
+            destination: Some(WithdrawDestinationUnvalidated::Address(john.address.to_string())),
+        },
+        &[],
+    )?;
+    ensure!(
+        native_balance(router, &john.address)? == Uint128::from(2_990u32),
+        here("Not sent to destination", line!(), column!())
+    );
+
+    Ok(())
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Lookups & Pagination
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// <X> Single Listing & Bucket are found by ID alone
+// <X> Missing ID is a `not found` error
+// <X> Batch lookups keep request order & return None for missing IDs
+// <X> Batch lookups over the cap are rejected
+#[test]
+fn listing_and_bucket_lookups() -> Result<(), anyhow::Error> {
+    use crate::query::{
+        BucketsByIdResponse, ListingsByIdResponse, SingleBucketResponse, SingleListingResponse,
+        MAX_LOOKUP_IDS,
+    };
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+
+    // Instantiate all contracts
+    let (jvone, _jvtwo, _jvtre, _neonpeepz, _shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+    let router = give_natives(&john, &mut router);
+
+    // John lists 100 ujunox for 10 JVONE, Sam makes a Bucket with 10 JVONE
+    router.execute_contract(
+        john.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::CreateListing {
+            listing_id: 7,
+            create_msg: CreateListingMsg {
+                ask: GenericBalanceUnvalidated {
+                    native: vec![],
+                    cw20: vec![Cw20CoinUnverified {
+                        address: jvone.addr().to_string(),
+                        amount: Uint128::from(10u32),
+                    }],
+                    nfts: vec![],
+                },
+                whitelisted_buyer: None,
+                partial_fill: None,
+                auction: None,
+            },
+        },
+        &coins(100, VALID_NATIVE),
+    )?;
+    router.execute_contract(
+        sam.address.clone(),
+        jvone.addr(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: fuzionmarket.to_string(),
+            amount: Uint128::from(10u32),
+            msg: to_binary(&ReceiveMsg::CreateBucketCw20 {
+                bucket_id: 9,
+            })?,
+        },
+        &[],
+    )?;
+
+    let listing: SingleListingResponse = router.wrap().query_wasm_smart(
+        fuzionmarket.clone(),
+        &QueryMsg::GetListing {
+            listing_id: 7,
+        },
+    )?;
+    ensure!(listing.listing.creator == john.address, here("Listing creator", line!(), column!()));
+    ensure!(listing.listing.id == 7, here("Listing id", line!(), column!()));
+
+    let bucket: SingleBucketResponse = router.wrap().query_wasm_smart(
+        fuzionmarket.clone(),
+        &QueryMsg::GetBucket {
+            bucket_id: 9,
+        },
+    )?;
+    ensure!(bucket.bucket.owner == sam.address, here("Bucket owner", line!(), column!()));
+    ensure!(bucket.bucket.id == 9, here("Bucket id", line!(), column!()));
+
+    let missing: Result<SingleListingResponse, _> = router.wrap().query_wasm_smart(
+        fuzionmarket.clone(),
+        &QueryMsg::GetListing {
+            listing_id: 9,
+        },
+    );
+    ensure!(
+        missing.is_err_and(|e| e.to_string().contains("Listing 9 not found")),
+        here("Missing Listing", line!(), column!())
+    );
+
+    let missing: Result<SingleBucketResponse, _> = router.wrap().query_wasm_smart(
+        fuzionmarket.clone(),
+        &QueryMsg::GetBucket {
+            bucket_id: 7,
+        },
+    );
+    ensure!(
+        missing.is_err_and(|e| e.to_string().contains("Bucket 7 not found")),
+        here("Missing Bucket", line!(), column!())
+    );
+
+    let listings: ListingsByIdResponse = router.wrap().query_wasm_smart(
+        fuzionmarket.clone(),
+        &QueryMsg::GetListingsById {
+            listing_ids: vec![1, 7],
+        },
+    )?;
+    ensure!(
+        listings.listings.iter().map(|l| (l.listing_id, l.listing.is_some())).collect::<Vec<_>>()
+            == vec![(1, false), (7, true)],
+        here("Batch Listings", line!(), column!())
+    );
+
+    let buckets: BucketsByIdResponse = router.wrap().query_wasm_smart(
+        fuzionmarket.clone(),
+        &QueryMsg::GetBucketsById {
+            bucket_ids: vec![9, 1],
+        },
+    )?;
+    ensure!(
+        buckets.buckets.iter().map(|b| (b.bucket_id, b.bucket.is_some())).collect::<Vec<_>>()
+            == vec![(9, true), (1, false)],
+        here("Batch Buckets", line!(), column!())
+    );
+
+    let too_many: Result<ListingsByIdResponse, _> = router.wrap().query_wasm_smart(
+        fuzionmarket,
+        &QueryMsg::GetListingsById {
+            listing_ids: (0..=MAX_LOOKUP_IDS as u64).collect(),
+        },
+    );
+    ensure!(too_many.is_err(), here("Over lookup cap", line!(), column!()));
+
+    Ok(())
+}
+
+// <X> List queries return `limit` items & a `next` cursor, capped at MAX_LIMIT
+// <X> `start_after` continues after the cursor, last page has no `next`
+// <X> Expired & unfinalized Listings are filtered out before the limit, so pages are full
+#[test]
+fn paginated_list_queries() -> Result<(), anyhow::Error> {
+    use crate::query::{
+        MarketListingsResponse, MultiBucketResponse, MultiListingResponse, MAX_LIMIT,
+    };
+    use cosmwasm_std::StdResult;
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+
+    // Instantiate all contracts
+    let (jvone, _jvtwo, _jvtre, _neonpeepz, _shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+    let router = give_natives(&john, &mut router);
+
+    // 35 Listings, all whitelisted to Sam
+    // id % 3 == 0 is never finalized, id % 3 == 1 expires soon, id % 3 == 2 stays live
+    for listing_id in 1..=35_u64 {
+        router.execute_contract(
+            john.address.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::CreateListing {
+                listing_id,
+                create_msg: CreateListingMsg {
+                    ask: GenericBalanceUnvalidated {
+                        native: vec![],
+                        cw20: vec![Cw20CoinUnverified {
+                            address: jvone.addr().to_string(),
+                            amount: Uint128::from(1u32),
+                        }],
+                        nfts: vec![],
+                    },
+                    whitelisted_buyer: Some(sam.address.to_string()),
+                    partial_fill: None,
+                    auction: None,
+                },
+            },
+            &coins(1, VALID_NATIVE),
+        )?;
+
+        let seconds = match listing_id % 3 {
+            1 => 600,
+            2 => 10_000,
+            _ => continue,
+        };
+        router.execute_contract(
+            john.address.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::Finalize {
+                listing_id,
+                seconds,
+            },
+            &[],
+        )?;
+    }
+
+    router.update_block(|current_blockinfo| {
+        current_blockinfo.height += 100;
+        current_blockinfo.time = current_blockinfo.time.plus_seconds(601);
+    });
+
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // By owner
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    let by_owner =
+        |start_after: Option<u64>, limit: Option<u32>| -> StdResult<MultiListingResponse> {
+            router.wrap().query_wasm_smart(
+                fuzionmarket.clone(),
+                &QueryMsg::GetListingsByOwner {
+                    owner: john.address.to_string(),
+                    start_after,
+                    limit,
+                },
+            )
+        };
+
+    let page = by_owner(None, None)?;
+    ensure!(
+        page.listings.len() == 20 && page.next == Some(20),
+        here("Default limit", line!(), column!())
+    );
+
+    let page = by_owner(Some(20), Some(1_000))?;
+    ensure!(
+        page.listings.first().map(|l| l.id) == Some(21)
+            && page.listings.len() == 15
+            && page.next.is_none(),
+        here("Last page", line!(), column!())
+    );
+
+    let page = by_owner(None, Some(1_000))?;
+    ensure!(page.listings.len() == MAX_LIMIT as usize, here("Max limit", line!(), column!()));
+
+    let page = by_owner(None, Some(0))?;
+    ensure!(
+        page.listings.len() == 1 && page.next == Some(1),
+        here("Zero limit", line!(), column!())
+    );
+
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Market & whitelist skip expired & unfinalized
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    let live: Vec<u64> = (1..=35).filter(|id| id % 3 == 2).collect();
+
+    let mut market_ids = vec![];
+    let mut start_after = None;
+    loop {
+        let page: MarketListingsResponse = router.wrap().query_wasm_smart(
+            fuzionmarket.clone(),
+            &QueryMsg::GetListingsForMarket {
+                start_after,
+                limit: Some(5),
+            },
+        )?;
+        ensure!(
+            page.listings.len() == 5 || page.next.is_none(),
+            here("Short market page", line!(), column!())
+        );
+        market_ids.extend(page.listings.iter().map(|l| l.id));
+        start_after = page.next;
+        if start_after.is_none() {
+            break;
+        }
+    }
+    market_ids.sort_unstable();
+    ensure!(
+        market_ids == live,
+        here(format!("Market listings: {market_ids:?}"), line!(), column!())
+    );
+
+    let mut whitelist_ids = vec![];
+    let mut start_after = None;
+    loop {
+        let page: MultiListingResponse = router.wrap().query_wasm_smart(
+            fuzionmarket.clone(),
+            &QueryMsg::GetListingsByWhitelist {
+                owner: sam.address.to_string(),
+                start_after,
+                limit: Some(5),
+            },
+        )?;
+        ensure!(
+            page.listings.len() == 5 || page.next.is_none(),
+            here("Short whitelist page", line!(), column!())
+        );
+        whitelist_ids.extend(page.listings.iter().map(|l| l.id));
+        start_after = page.next;
+        if start_after.is_none() {
+            break;
+        }
+    }
+    ensure!(
+        whitelist_ids == live,
+        here(format!("Whitelisted listings: {whitelist_ids:?}"), line!(), column!())
+    );
+
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Buckets
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    for bucket_id in 1..=3 {
+        router.execute_contract(
+            john.address.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::CreateBucket {
+                bucket_id,
+            },
+            &coins(1, VALID_NATIVE),
+        )?;
+    }
+
+    let buckets = |start_after: Option<u64>| -> StdResult<MultiBucketResponse> {
+        router.wrap().query_wasm_smart(
+            fuzionmarket.clone(),
+            &QueryMsg::GetBuckets {
+                bucket_owner: john.address.to_string(),
+                start_after,
+                limit: Some(2),
+            },
+        )
+    };
+
+    let page = buckets(None)?;
+    ensure!(
+        page.buckets.len() == 2 && page.next == Some(2),
+        here("Bucket page 1", line!(), column!())
+    );
+
+    let page = buckets(page.next)?;
+    ensure!(
+        page.buckets.iter().map(|(id, _)| *id).collect::<Vec<_>>() == vec![3]
+            && page.next.is_none(),
+        here("Bucket page 2", line!(), column!())
+    );
+
+    Ok(())
+}
+
+// <X> WhereIsNft finds the Listing or Bucket holding an NFT
+// <X> Listings & Buckets are found by NFT collection & by native/CW20 denom
+// <X> Index entries are removed with the Listing
+#[test]
+fn asset_location_queries() -> Result<(), anyhow::Error> {
+    use crate::query::{
+        AssetLocation, MultiBucketResponse, MultiListingResponse, NftLocationResponse,
+    };
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+
+    // Instantiate all contracts
+    let (jvone, _jvtwo, _jvtre, neonpeepz, shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+    let router = give_natives(&john, &mut router);
+
+    // John's Listing 1: 100 ujunox, 10 JVONE & NeonPeepz 1
+    router.execute_contract(
+        john.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::CreateListing {
+            listing_id: 1,
+            create_msg: CreateListingMsg {
+                ask: GenericBalanceUnvalidated {
+                    native: vec![],
+                    cw20: vec![Cw20CoinUnverified {
+                        address: jvone.addr().to_string(),
+                        amount: Uint128::from(1u32),
+                    }],
+                    nfts: vec![],
+                },
+                whitelisted_buyer: None,
+                partial_fill: None,
+                auction: None,
+            },
+        },
+        &coins(100, VALID_NATIVE),
+    )?;
+    router.execute_contract(
+        john.address.clone(),
+        jvone.addr(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: fuzionmarket.to_string(),
+            amount: Uint128::from(10u32),
+            msg: to_binary(&ReceiveMsg::AddToListingCw20 {
+                listing_id: 1,
+            })?,
+        },
+        &[],
+    )?;
+    let add_nft: cw721_base::ExecuteMsg<Option<Empty>, Empty> = cw721_base::ExecuteMsg::SendNft {
+        contract: fuzionmarket.to_string(),
+        token_id: "1".to_string(),
+        msg: to_binary(&ReceiveNftMsg::AddToListingCw721 {
+            listing_id: 1,
+        })?,
+    };
+    router.execute_contract(john.address.clone(), neonpeepz.addr(), &add_nft, &[])?;
+
+    // Sam's Bucket 2: NeonPeepz 3
+    let create_bucket: cw721_base::ExecuteMsg<Option<Empty>, Empty> =
+        cw721_base::ExecuteMsg::SendNft {
+            contract: fuzionmarket.to_string(),
+            token_id: "3".to_string(),
+            msg: to_binary(&ReceiveNftMsg::CreateBucketCw721 {
+                bucket_id: 2,
+            })?,
+        };
+    router.execute_contract(sam.address.clone(), neonpeepz.addr(), &create_bucket, &[])?;
+
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // WhereIsNft
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    let where_is = |router: &App, token_id: &str| -> Result<NftLocationResponse, anyhow::Error> {
+        Ok(router.wrap().query_wasm_smart(
+            fuzionmarket.clone(),
+            &QueryMsg::WhereIsNft {
+                collection: neonpeepz.addr().to_string(),
+                token_id: token_id.to_string(),
+            },
+        )?)
+    };
+
+    ensure!(
+        where_is(router, "1")?.location
+            == Some(AssetLocation::Listing {
+                listing_id: 1,
+                creator: john.address.clone(),
+                status: Status::BeingPrepared,
+                approved: false,
+            }),
+        here("NFT in Listing", line!(), column!())
+    );
+    ensure!(
+        where_is(router, "3")?.location
+            == Some(AssetLocation::Bucket {
+                bucket_id: 2,
+                owner: sam.address.clone(),
+            }),
+        here("NFT in Bucket", line!(), column!())
+    );
+    ensure!(
+        where_is(router, "2")?.location.is_none(),
+        here("NFT not escrowed", line!(), column!())
+    );
+
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // By collection & denom
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    let listing_ids = |router: &App, msg: QueryMsg| -> Result<Vec<u64>, anyhow::Error> {
+        let res: MultiListingResponse =
+            router.wrap().query_wasm_smart(fuzionmarket.clone(), &msg)?;
+        Ok(res.listings.iter().map(|l| l.id).collect())
+    };
+    let by_collection = |collection: &Addr| QueryMsg::ListingsByCollection {
+        collection: collection.to_string(),
+        start_after: None,
+        limit: None,
+    };
+    let by_denom = |denom: &str| QueryMsg::ListingsContainingDenom {
+        denom: denom.to_string(),
+        start_after: None,
+        limit: None,
+    };
+
+    ensure!(
+        listing_ids(router, by_collection(&neonpeepz.addr()))? == vec![1],
+        here("NeonPeepz Listings", line!(), column!())
+    );
+    ensure!(
+        listing_ids(router, by_collection(&shittykittyz.addr()))?.is_empty(),
+        here("ShittyKittyz Listings", line!(), column!())
+    );
+    ensure!(
+        listing_ids(router, by_denom(VALID_NATIVE))? == vec![1],
+        here("Native Listings", line!(), column!())
+    );
+    ensure!(
+        listing_ids(router, by_denom(jvone.addr().as_str()))? == vec![1],
+        here("CW20 Listings", line!(), column!())
+    );
+
+    let buckets: MultiBucketResponse = router.wrap().query_wasm_smart(
+        fuzionmarket.clone(),
+        &QueryMsg::BucketsByCollection {
+            collection: neonpeepz.addr().to_string(),
+            start_after: None,
+            limit: None,
+        },
+    )?;
+    ensure!(
+        buckets.buckets.iter().map(|(id, _)| *id).collect::<Vec<_>>() == vec![2],
+        here("NeonPeepz Buckets", line!(), column!())
+    );
+    let buckets: MultiBucketResponse = router.wrap().query_wasm_smart(
+        fuzionmarket.clone(),
+        &QueryMsg::BucketsContainingDenom {
+            denom: VALID_NATIVE.to_string(),
+            start_after: None,
+            limit: None,
+        },
+    )?;
+    ensure!(buckets.buckets.is_empty(), here("Native Buckets", line!(), column!()));
+
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Removed with the Listing
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    router.execute_contract(
+        john.address.clone(),
+        fuzionmarket.clone(),
+        &ExecuteMsg::DeleteListing {
+            listing_id: 1,
+            destination: None,
+        },
+        &[],
+    )?;
+
+    ensure!(
+        where_is(router, "1")?.location.is_none(),
+        here("Deleted Listing NFT", line!(), column!())
+    );
+    ensure!(
+        listing_ids(router, by_collection(&neonpeepz.addr()))?.is_empty(),
+        here("Deleted Listing collection", line!(), column!())
+    );
+    ensure!(
+        listing_ids(router, by_denom(VALID_NATIVE))?.is_empty(),
+        here("Deleted Listing denom", line!(), column!())
+    );
+
+    Ok(())
+}
+
+// <X> Live Listings are found by the native, CW20, collection or exact NFT they ask for
+// <X> Unfinalized & expired Listings are filtered out
+#[test]
+fn listings_by_ask_asset() -> Result<(), anyhow::Error> {
+    use crate::msg::AskAsset;
+    use crate::query::MultiListingResponse;
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+
+    // Instantiate all contracts
+    let (jvone, _jvtwo, _jvtre, neonpeepz, shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+    let router = give_natives(&john, &mut router);
+
+    let nft = |collection: &Addr, token_id: &str| NftUnverified {
+        contract_address: collection.to_string(),
+        token_id: token_id.to_string(),
+    };
+
+    // 1: asks 5 JVONE & NeonPeepz 3 | 2: asks 10 ujunox
+    // 3: asks 1 JVONE, never finalized | 4: asks ShittyKittyz 5, expires
+    let asks = [
+        (
+            GenericBalanceUnvalidated {
+                native: vec![],
+                cw20: vec![Cw20CoinUnverified {
+                    address: jvone.addr().to_string(),
+                    amount: Uint128::from(5u32),
+                }],
+                nfts: vec![nft(&neonpeepz.addr(), "3")],
+            },
+            Some(10_000),
+        ),
+        (
+            GenericBalanceUnvalidated {
+                native: coins(10, VALID_NATIVE),
+                cw20: vec![],
+                nfts: vec![],
+            },
+            Some(10_000),
+        ),
+        (
+            GenericBalanceUnvalidated {
+                native: vec![],
+                cw20: vec![Cw20CoinUnverified {
+                    address: jvone.addr().to_string(),
+                    amount: Uint128::from(1u32),
+                }],
+                nfts: vec![],
+            },
+            None,
+        ),
+        (
+            GenericBalanceUnvalidated {
+                native: vec![],
+                cw20: vec![],
+                nfts: vec![nft(&shittykittyz.addr(), "5")],
+            },
+            Some(600),
+        ),
+    ];
+
+    for (listing_id, (ask, seconds)) in (1..).zip(asks) {
+        router.execute_contract(
+            john.address.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::CreateListing {
+                listing_id,
+                create_msg: CreateListingMsg {
+                    ask,
+                    whitelisted_buyer: None,
+                    partial_fill: None,
+                    auction: None,
+                },
+            },
+            &coins(1, VALID_NATIVE),
+        )?;
+        if let Some(seconds) = seconds {
+            router.execute_contract(
+                john.address.clone(),
+                fuzionmarket.clone(),
+                &ExecuteMsg::Finalize {
+                    listing_id,
+                    seconds,
+                },
+                &[],
+            )?;
+        }
+    }
+
+    router.update_block(|current_blockinfo| {
+        current_blockinfo.height += 100;
+        current_blockinfo.time = current_blockinfo.time.plus_seconds(601);
+    });
+
+    let asking_for = |asset: AskAsset| -> Result<Vec<u64>, anyhow::Error> {
+        let res: MultiListingResponse = router.wrap().query_wasm_smart(
+            fuzionmarket.clone(),
+            &QueryMsg::ListingsByAskAsset {
+                asset,
+                start_after: None,
+                limit: None,
+            },
+        )?;
+        Ok(res.listings.iter().map(|l| l.id).collect())
+    };
+
+    ensure!(
+        asking_for(AskAsset::Cw20(jvone.addr().to_string()))? == vec![1],
+        here("Asking for JVONE", line!(), column!())
+    );
+    ensure!(
+        asking_for(AskAsset::Native(VALID_NATIVE.to_string()))? == vec![2],
+        here("Asking for ujunox", line!(), column!())
+    );
+    ensure!(
+        asking_for(AskAsset::Collection(neonpeepz.addr().to_string()))? == vec![1],
+        here("Asking for NeonPeepz", line!(), column!())
+    );
+    ensure!(
+        asking_for(AskAsset::Nft {
+            collection: neonpeepz.addr().to_string(),
+            token_id: "3".to_string(),
+        })? == vec![1],
+        here("Asking for NeonPeepz 3", line!(), column!())
+    );
+    ensure!(
+        asking_for(AskAsset::Nft {
+            collection: neonpeepz.addr().to_string(),
+            token_id: "4".to_string(),
+        })?
+        .is_empty(),
+        here("Asking for NeonPeepz 4", line!(), column!())
+    );
+    ensure!(
+        asking_for(AskAsset::Collection(shittykittyz.addr().to_string()))?.is_empty(),
+        here("Expired Listing returned", line!(), column!())
+    );
+
+    Ok(())
+}
+
+// <X> Listings are filtered by status, creator, whitelist, expiration & collection
+// <X> Listings are sorted by finalized or expiration time, either order
+// <X> Pages continue from the cursor in the requested order
+// <X> Creator & collection filters page through their own Listings
+// <X> Unnarrowed scans stop at MAX_MARKET_SCAN, returning a cursor with a short page
+#[test]
+fn market_listings_filter_and_sort() -> Result<(), anyhow::Error> {
+    use crate::msg::{MarketFilter, MarketSort, SortOrder};
+    use crate::query::{MarketListingsResponse, MAX_MARKET_SCAN};
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+
+    // Instantiate all contracts
+    let (jvone, _jvtwo, _jvtre, neonpeepz, _shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+    let router = give_natives(&john, &mut router);
+    let router = give_natives(&sam, router);
+    let t0 = router.block_info().time.seconds();
+
+    // (creator, whitelisted buyer, finalize seconds)
+    // 1: John, public, 5_000 | 2: John, whitelisted, 1_000
+    // 3: Sam, public, 3_000 | 4: Sam, public, not finalized
+    let listings = [
+        (&john, None, Some(5_000)),
+        (&john, Some(max.address.to_string()), Some(1_000)),
+        (&sam, None, Some(3_000)),
+        (&sam, None, None),
+    ];
+
+    for (listing_id, (creator, whitelisted_buyer, seconds)) in (1..).zip(listings) {
+        router.execute_contract(
+            creator.address.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::CreateListing {
+                listing_id,
+                create_msg: CreateListingMsg {
+                    ask: GenericBalanceUnvalidated {
+                        native: vec![],
+                        cw20: vec![Cw20CoinUnverified {
+                            address: jvone.addr().to_string(),
+                            amount: Uint128::from(1u32),
+                        }],
+                        nfts: vec![],
+                    },
+                    whitelisted_buyer,
+                    partial_fill: None,
+                    auction: None,
+                },
+            },
+            &coins(1, VALID_NATIVE),
+        )?;
+        if listing_id == 1 {
+            let add_nft: cw721_base::ExecuteMsg<Option<Empty>, Empty> =
+                cw721_base::ExecuteMsg::SendNft {
+                    contract: fuzionmarket.to_string(),
+                    token_id: "1".to_string(),
+                    msg: to_binary(&ReceiveNftMsg::AddToListingCw721 {
+                        listing_id: 1,
+                    })?,
+                };
+            router.execute_contract(john.address.clone(), neonpeepz.addr(), &add_nft, &[])?;
+        }
+        if let Some(seconds) = seconds {
+            router.execute_contract(
+                creator.address.clone(),
+                fuzionmarket.clone(),
+                &ExecuteMsg::Finalize {
+                    listing_id,
+                    seconds,
+                },
+                &[],
+            )?;
+        }
+        // Finalized 10 seconds apart
+        router.update_block(|current_blockinfo| {
+            current_blockinfo.height += 2;
+            current_blockinfo.time = current_blockinfo.time.plus_seconds(10);
+        });
+    }
+
+    let market = |router: &App,
+                  filter: MarketFilter,
+                  sort_by: MarketSort,
+                  order: SortOrder|
+     -> Result<Vec<u64>, anyhow::Error> {
+        let res: MarketListingsResponse = router.wrap().query_wasm_smart(
+            fuzionmarket.clone(),
+            &QueryMsg::GetMarketListings {
+                filter: Some(filter),
+                sort_by: Some(sort_by),
+                order: Some(order),
+                start_after: None,
+                limit: None,
+            },
+        )?;
+        Ok(res.listings.iter().map(|l| l.id).collect())
+    };
+    let finalized_ready = || MarketFilter {
+        status: Some(Status::FinalizedReady),
+        ..MarketFilter::default()
+    };
+
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Sorting
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    let all: MarketListingsResponse = router.wrap().query_wasm_smart(
+        fuzionmarket.clone(),
+        &QueryMsg::GetMarketListings {
+            filter: None,
+            sort_by: None,
+            order: None,
+            start_after: None,
+            limit: None,
+        },
+    )?;
+    ensure!(
+        all.listings.iter().map(|l| l.id).collect::<Vec<_>>() == vec![4, 1, 2, 3],
+        here("Default sort", line!(), column!())
+    );
+    ensure!(
+        market(router, MarketFilter::default(), MarketSort::FinalizedTime, SortOrder::Descending)?
+            == vec![3, 2, 1, 4],
+        here("Finalized descending", line!(), column!())
+    );
+    ensure!(
+        market(router, MarketFilter::default(), MarketSort::ExpirationTime, SortOrder::Ascending)?
+            == vec![4, 2, 3, 1],
+        here("Expiration ascending", line!(), column!())
+    );
+
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Filters
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    ensure!(
+        market(router, finalized_ready(), MarketSort::ExpirationTime, SortOrder::Ascending)?
+            == vec![2, 3, 1],
+        here("Status filter", line!(), column!())
+    );
+    ensure!(
+        market(
+            router,
+            MarketFilter {
+                creator: Some(sam.address.to_string()),
+                ..MarketFilter::default()
+            },
+            MarketSort::FinalizedTime,
+            SortOrder::Ascending
+        )? == vec![4, 3],
+        here("Creator filter", line!(), column!())
+    );
+    ensure!(
+        market(
+            router,
+            MarketFilter {
+                whitelisted: Some(true),
+                ..MarketFilter::default()
+            },
+            MarketSort::FinalizedTime,
+            SortOrder::Ascending
+        )? == vec![2],
+        here("Whitelisted filter", line!(), column!())
+    );
+    ensure!(
+        market(
+            router,
+            MarketFilter {
+                whitelisted: Some(false),
+                ..finalized_ready()
+            },
+            MarketSort::FinalizedTime,
+            SortOrder::Ascending
+        )? == vec![1, 3],
+        here("Public filter", line!(), column!())
+    );
+    ensure!(
+        market(
+            router,
+            MarketFilter {
+                expires_before: Some(t0 + 4_000),
+                ..MarketFilter::default()
+            },
+            MarketSort::ExpirationTime,
+            SortOrder::Ascending
+        )? == vec![2, 3],
+        here("Expires before filter", line!(), column!())
+    );
+    ensure!(
+        market(
+            router,
+            MarketFilter {
+                expires_after: Some(t0 + 2_000),
+                ..MarketFilter::default()
+            },
+            MarketSort::ExpirationTime,
+            SortOrder::Ascending
+        )? == vec![3, 1],
+        here("Expires after filter", line!(), column!())
+    );
+    ensure!(
+        market(
+            router,
+            MarketFilter {
+                collection: Some(neonpeepz.addr().to_string()),
+                ..MarketFilter::default()
+            },
+            MarketSort::FinalizedTime,
+            SortOrder::Ascending
+        )? == vec![1],
+        here("Collection filter", line!(), column!())
+    );
+
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Pagination
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    let page = |start_after| -> Result<MarketListingsResponse, anyhow::Error> {
+        Ok(router.wrap().query_wasm_smart(
+            fuzionmarket.clone(),
+            &QueryMsg::GetMarketListings {
+                filter: None,
+                sort_by: Some(MarketSort::ExpirationTime),
+                order: Some(SortOrder::Descending),
+                start_after,
+                limit: Some(2),
+            },
+        )?)
+    };
+    let first = page(None)?;
+    ensure!(
+        first.listings.iter().map(|l| l.id).collect::<Vec<_>>() == vec![1, 3]
+            && first.next.is_some(),
+        here("Descending page 1", line!(), column!())
+    );
+    let second = page(first.next)?;
+    ensure!(
+        second.listings.iter().map(|l| l.id).collect::<Vec<_>>() == vec![2, 4]
+            && second.next.is_none(),
+        here("Descending page 2", line!(), column!())
+    );
+
+    // John's Listings, one per page
+    let johns_page = |start_after| -> Result<MarketListingsResponse, anyhow::Error> {
+        Ok(router.wrap().query_wasm_smart(
+            fuzionmarket.clone(),
+            &QueryMsg::GetMarketListings {
+                filter: Some(MarketFilter {
+                    creator: Some(john.address.to_string()),
+                    ..MarketFilter::default()
+                }),
+                sort_by: Some(MarketSort::FinalizedTime),
+                order: Some(SortOrder::Descending),
+                start_after,
+                limit: Some(1),
+            },
+        )?)
+    };
+    let first = johns_page(None)?;
+    ensure!(
+        first.listings.iter().map(|l| l.id).collect::<Vec<_>>() == vec![2] && first.next.is_some(),
+        here("Creator page 1", line!(), column!())
+    );
+    let second = johns_page(first.next)?;
+    ensure!(
+        second.listings.iter().map(|l| l.id).collect::<Vec<_>>() == vec![1]
+            && second.next.is_none(),
+        here("Creator page 2", line!(), column!())
+    );
+
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Scan cap
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // MAX_MARKET_SCAN unfinalized Listings sort before the whitelisted Listing 2
+    for listing_id in 100..100 + MAX_MARKET_SCAN as u64 {
+        router.execute_contract(
+            sam.address.clone(),
+            fuzionmarket.clone(),
+            &ExecuteMsg::CreateListing {
+                listing_id,
+                create_msg: CreateListingMsg {
+                    ask: GenericBalanceUnvalidated {
+                        native: coins(1, VALID_NATIVE),
+                        cw20: vec![],
+                        nfts: vec![],
+                    },
+                    whitelisted_buyer: None,
+                    partial_fill: None,
+                    auction: None,
+                },
+            },
+            &coins(1, VALID_NATIVE),
+        )?;
+    }
+    let whitelisted_page = |start_after| -> Result<MarketListingsResponse, anyhow::Error> {
+        Ok(router.wrap().query_wasm_smart(
+            fuzionmarket.clone(),
+            &QueryMsg::GetMarketListings {
+                filter: Some(MarketFilter {
+                    whitelisted: Some(true),
+                    ..MarketFilter::default()
+                }),
+                sort_by: Some(MarketSort::FinalizedTime),
+                order: Some(SortOrder::Ascending),
+                start_after,
+                limit: None,
+            },
+        )?)
+    };
+    let first = whitelisted_page(None)?;
+    ensure!(
+        first.listings.is_empty() && first.next.is_some(),
+        here("Capped scan returns a cursor", line!(), column!())
+    );
+    let second = whitelisted_page(first.next)?;
+    ensure!(
+        second.listings.iter().map(|l| l.id).collect::<Vec<_>>() == vec![2]
+            && second.next.is_none(),
+        here("Scan resumed from the cursor", line!(), column!())
+    );
+
+    Ok(())
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Migrations
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+pub fn init_legacy_contract(
+    router: &mut App,
+    admin: &Addr,
+    royalty_registry: &Addr,
+    contract_name: &str,
+    contract_version: &str,
+) -> Addr {
+    let legacy_id = router.store_code(legacy_contract::legacy_contract());
+    let msg = legacy_contract::LegacyInstantiateMsg {
+        contract_name: contract_name.to_string(),
+        contract_version: contract_version.to_string(),
+        royalty_registry: royalty_registry.to_string(),
+    };
+
+    router
+        .instantiate_contract(legacy_id, admin.clone(), &msg, &[], "legacy", Some(admin.to_string()))
+        .unwrap()
+}
+
+// <X> Populated v0.3.0 store is migrated
+// <X> Listing indexes are rebuilt (owner, market, id lookups)
+// <X> Migrated Listings & Buckets can be traded & withdrawn
+// <X> cw2 version is updated
+#[test]
+fn migrate_populated_v0_3_0_store() -> Result<(), anyhow::Error> {
+    use crate::migrations::v0_3_0;
+    use anyhow::Result;
+    use cw_multi_test::AppResponse;
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let sam = create_users::fake_user("sam".to_string());
+    let max = create_users::fake_user("max".to_string());
+
+    // Instantiate all contracts
+    let (jvone, jvtwo, _jvtre, neonpeepz, _shittykittyz, fuzionmarket) =
+        init_all_contracts(&mut router, &contract_admin, &john, &sam, &max)?;
+
+    // Reuse the royalty registry created by the current market
+    let royalty_addr: Option<Addr> =
+        router.wrap().query_wasm_smart(fuzionmarket, &QueryMsg::GetRoyaltyAddr {})?;
+    let royalty_addr = royalty_addr.unwrap();
+
+    let legacy = init_legacy_contract(
+        &mut router,
+        &contract_admin.address,
+        &royalty_addr,
+        crate::contract::CONTRACT_NAME,
+        "0.3.0",
+    );
+
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Escrow assets held by the v0.3.0 store
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    router.execute_contract(
+        john.address.clone(),
+        jvone.addr(),
+        &cw20_base::msg::ExecuteMsg::Transfer {
+            recipient: legacy.to_string(),
+            amount: Uint128::from(10u32),
+        },
+        &[],
+    )?;
+    router.execute_contract(
+        john.address.clone(),
+        neonpeepz.addr(),
+        &cw721_base::ExecuteMsg::<Option<Empty>, Empty>::TransferNft {
+            recipient: legacy.to_string(),
+            token_id: "1".to_string(),
+        },
+        &[],
+    )?;
+    router.execute_contract(
+        sam.address.clone(),
+        jvtwo.addr(),
+        &cw20_base::msg::ExecuteMsg::Transfer {
+            recipient: legacy.to_string(),
+            amount: Uint128::from(20u32),
+        },
+        &[],
+    )?;
+    router.init_modules(|router, _, storage| {
+        router.bank.init_balance(storage, &legacy, coins(1_100, "ujunox")).unwrap()
+    });
+
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Seed v0.3.0 Listings & Bucket
+    // Listing 1: Finalized | FOR_SALE: 10 JVONE + NeonPeepz #1 | ASK: 20 JVTWO
+    // Listing 2: BeingPrepared | FOR_SALE: 1000 ujunox | ASK: 5 JVONE
+    // Bucket 1: Sam | 20 JVTWO
+    // Bucket 2: Max | 95 ujunox, 5 ujunox fee not yet sent
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    let now = router.block_info().time;
+    let seed_msgs = vec![
+        legacy_contract::LegacyExecuteMsg::SeedListing(v0_3_0::Listing {
+            creator: john.address.clone(),
+            id: 1,
+            finalized_time: Some(now),
+            expiration_time: Some(now.plus_seconds(10_000)),
+            status: Status::FinalizedReady,
+            claimant: None,
+            whitelisted_buyer: None,
+            for_sale: GenericBalance {
+                native: vec![],
+                cw20: vec![Cw20CoinVerified {
+                    address: jvone.addr(),
+                    amount: Uint128::from(10u32),
+                }],
+                nfts: vec![Nft {
+                    contract_address: neonpeepz.addr(),
+                    token_id: "1".to_string(),
+                }],
+            },
+            ask: GenericBalance {
+                native: vec![],
+                cw20: vec![Cw20CoinVerified {
+                    address: jvtwo.addr(),
+                    amount: Uint128::from(20u32),
+                }],
+                nfts: vec![],
+            },
+            fee_amount: None,
+        }),
+        legacy_contract::LegacyExecuteMsg::SeedListing(v0_3_0::Listing {
+            creator: john.address.clone(),
+            id: 2,
+            finalized_time: None,
+            expiration_time: None,
+            status: Status::BeingPrepared,
+            claimant: None,
+            whitelisted_buyer: None,
+            for_sale: GenericBalance {
+                native: coins(1_000, "ujunox"),
+                cw20: vec![],
+                nfts: vec![],
+            },
+            ask: GenericBalance {
+                native: vec![],
+                cw20: vec![Cw20CoinVerified {
+                    address: jvone.addr(),
+                    amount: Uint128::from(5u32),
+                }],
+                nfts: vec![],
+            },
+            fee_amount: None,
+        }),
+        legacy_contract::LegacyExecuteMsg::SeedBucket {
+            bucket_id: 1,
+            bucket: v0_3_0::Bucket {
+                owner: sam.address.clone(),
+                funds: GenericBalance {
+                    native: vec![],
+                    cw20: vec![Cw20CoinVerified {
+                        address: jvtwo.addr(),
+                        amount: Uint128::from(20u32),
+                    }],
+                    nfts: vec![],
+                },
+                fee_amount: None,
+            },
+        },
+        legacy_contract::LegacyExecuteMsg::SeedBucket {
+            bucket_id: 2,
+            bucket: v0_3_0::Bucket {
+                owner: max.address.clone(),
+                funds: GenericBalance {
+                    native: coins(95, "ujunox"),
+                    cw20: vec![],
+                    nfts: vec![],
+                },
+                fee_amount: Some(Coin::new(5, "ujunox")),
+            },
+        },
+    ];
+    for seed in seed_msgs {
+        router.execute_contract(contract_admin.address.clone(), legacy.clone(), &seed, &[])?;
+    }
+
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Migrate to current code
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    let new_code_id = router.store_code(fuzionmarket_contract());
+
+    // Only the contract admin can migrate
+    let res: Result<AppResponse> = router.migrate_contract(
+        john.address.clone(),
+        legacy.clone(),
+        &MigrateMsg {
+            limit: None,
+        },
+        new_code_id,
+    );
+    ensure!(res.is_err(), here("Non admin migrate", line!(), column!()));
+
+    let res: AppResponse = router.migrate_contract(
+        contract_admin.address.clone(),
+        legacy.clone(),
+        &MigrateMsg {
+            limit: None,
+        },
+        new_code_id,
+    )?;
+    ensure!(
+        res.events.iter().any(|e| e
+            .attributes
+            .iter()
+            .any(|a| a.key == "migrations_applied" && a.value == "0.4.0")),
+        here("0.4.0 migration not applied", line!(), column!())
+    );
+
+    let version = cw2::query_contract_info(&router.wrap(), legacy.to_string())?;
+    ensure!(
+        version.version == crate::contract::CONTRACT_VERSION,
+        here("cw2 version not updated", line!(), column!())
+    );
+
+    // Contract admin becomes owner
+    let ownership: crate::query::OwnershipResponse =
+        router.wrap().query_wasm_smart(legacy.clone(), &QueryMsg::GetOwnership {})?;
+    ensure!(
+        ownership.owner == Some(contract_admin.address.clone()),
+        here("Admin should be owner after migration", line!(), column!())
+    );
+
+    // Config holds the previously hardcoded values
+    let config: crate::query::ConfigResponse =
+        router.wrap().query_wasm_smart(legacy.clone(), &QueryMsg::GetConfig {})?;
+    ensure!(config.config == Config::default(), here("Config not migrated", line!(), column!()));
+
+    // JUNO FeeDenom becomes a Rotation schedule starting at ujunox
+    let schedule: crate::query::FeeScheduleResponse =
+        router.wrap().query_wasm_smart(legacy.clone(), &QueryMsg::GetFeeSchedule {})?;
+    ensure!(
+        schedule.mode == FeeMode::Rotation
+            && schedule.current == Some(FeeAsset::Native("ujunox".to_string()))
+            && schedule.denoms.len() == 2
+            && schedule.last_rotation == now.seconds(),
+        here("Fee denom not migrated", line!(), column!())
+    );
+
+    // Unsent fee is credited to the Fee Ledger
+    let ledger: crate::query::FeeLedgerResponse =
+        router.wrap().query_wasm_smart(legacy.clone(), &QueryMsg::GetFeeLedger {})?;
+    ensure!(
+        ledger.fees.len() == 1
+            && ledger.fees[0].asset == FeeAsset::Native("ujunox".to_string())
+            && ledger.fees[0].pending == Uint128::from(5u32)
+            && ledger.fees[0].lifetime == Uint128::from(5u32),
+        here("Unsent fee not credited", line!(), column!())
+    );
+
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Indexes are rebuilt
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    let by_owner: crate::query::MultiListingResponse = router.wrap().query_wasm_smart(
+        legacy.clone(),
+        &QueryMsg::GetListingsByOwner {
+            owner: john.address.to_string(),
+            start_after: None,
+            limit: None,
+        },
+    )?;
+    ensure!(by_owner.listings.len() == 2, here("Owner listings not migrated", line!(), column!()));
+
+    let market: crate::query::MarketListingsResponse = router.wrap().query_wasm_smart(
+        legacy.clone(),
+        &QueryMsg::GetListingsForMarket {
+            start_after: None,
+            limit: None,
+        },
+    )?;
+    ensure!(
+        market.listings.len() == 1 && market.listings[0].id == 1,
+        here("Finalized date index not rebuilt", line!(), column!())
+    );
+
+    let buckets: crate::query::MultiBucketResponse = router.wrap().query_wasm_smart(
+        legacy.clone(),
+        &QueryMsg::GetBuckets {
+            bucket_owner: sam.address.to_string(),
+            start_after: None,
+            limit: None,
+        },
+    )?;
+    ensure!(buckets.buckets.len() == 1, here("Bucket not migrated", line!(), column!()));
+
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Migrated Listing 1 can be bought with migrated Bucket 1
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    let buy_msg = ExecuteMsg::BuyListing {
+        listing_id: 1,
+        bucket_id: 1,
+        quantity: None,
+    };
+    router.execute_contract(sam.address.clone(), legacy.clone(), &buy_msg, &[])?;
+
+    let rem = ExecuteMsg::RemoveBucket {
+        bucket_id: 1,
+        destination: None,
+    };
+    router.execute_contract(john.address.clone(), legacy.clone(), &rem, &[])?;
+
+    let withdraw = ExecuteMsg::WithdrawPurchased {
+        listing_id: 1,
+        destination: None,
+    };
+    router.execute_contract(sam.address.clone(), legacy.clone(), &withdraw, &[])?;
+
+    assert_eq!(jvtwo.balance(&router.wrap(), john.address.clone()), Ok(Uint128::from(120u32)));
+    assert_eq!(jvone.balance(&router.wrap(), sam.address.clone()), Ok(Uint128::from(110u32)));
+    let sam_neonpeepz = neonpeepz.tokens(&router.wrap(), sam.address.to_string(), None, None)?;
+    ensure!(
+        sam_neonpeepz.tokens.contains(&"1".to_string()),
+        here("Sam did not receive NeonPeepz #1", line!(), column!())
+    );
+
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Migrated Listing 2 can be deleted & refunded
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    let delete = ExecuteMsg::DeleteListing {
+        listing_id: 2,
+        destination: None,
+    };
+    router.execute_contract(john.address.clone(), legacy.clone(), &delete, &[])?;
+    let john_juno: Coin = router.wrap().query_balance(john.address.to_string(), "ujunox")?;
+    ensure!(john_juno.amount == Uint128::from(1_000u32), here("Refund wrong", line!(), column!()));
+
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Migrated Bucket 2 withdraws without the fee, fee stays for SweepFees
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    let rem = ExecuteMsg::RemoveBucket {
+        bucket_id: 2,
+        destination: None,
+    };
+    router.execute_contract(max.address.clone(), legacy.clone(), &rem, &[])?;
+    let max_juno: Coin = router.wrap().query_balance(max.address.to_string(), "ujunox")?;
+    let legacy_juno: Coin = router.wrap().query_balance(legacy.to_string(), "ujunox")?;
+    ensure!(
+        max_juno.amount == Uint128::from(95u32) && legacy_juno.amount == Uint128::from(5u32),
+        here("Bucket 2 withdraw wrong", line!(), column!())
+    );
+
+    // Migrating again at the same version applies nothing
+    let res: AppResponse = router.migrate_contract(
+        contract_admin.address.clone(),
+        legacy,
+        &MigrateMsg {
+            limit: None,
+        },
+        new_code_id,
+    )?;
+    ensure!(
+        res.events.iter().any(|e| e
+            .attributes
+            .iter()
+            .any(|a| a.key == "migrations_applied" && a.value == "none")),
+        here("Re-migration should be a no-op", line!(), column!())
+    );
+
+    Ok(())
+}
+
+// <X> Migration is split over several calls by `limit`
+// <X> Execute is rejected & cw2 version kept until migration is done
+// <X> Every Listing & Bucket is migrated exactly once
+#[test]
+fn migrate_v0_3_0_store_in_pages() -> Result<(), anyhow::Error> {
+    use crate::migrations::v0_3_0;
+    use anyhow::Result;
+    use cw_multi_test::AppResponse;
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let john = create_users::fake_user("john".to_string());
+    let royalty_addr = Addr::unchecked("royalty");
+
+    let legacy = init_legacy_contract(
+        &mut router,
+        &contract_admin.address,
+        &royalty_addr,
+        crate::contract::CONTRACT_NAME,
+        "0.3.0",
+    );
+
+    let empty = GenericBalance {
+        native: vec![],
+        cw20: vec![],
+        nfts: vec![],
+    };
+
+    // 3 Listings & 2 Buckets, migrated 2 entries at a time
+    for id in 1..=3 {
+        let seed = legacy_contract::LegacyExecuteMsg::SeedListing(v0_3_0::Listing {
+            creator: john.address.clone(),
+            id,
+            finalized_time: None,
+            expiration_time: None,
+            status: Status::BeingPrepared,
+            claimant: None,
+            whitelisted_buyer: None,
+            for_sale: empty.clone(),
+            ask: empty.clone(),
+            fee_amount: None,
+        });
+        router.execute_contract(contract_admin.address.clone(), legacy.clone(), &seed, &[])?;
+    }
+    for bucket_id in 1..=2 {
+        let seed = legacy_contract::LegacyExecuteMsg::SeedBucket {
+            bucket_id,
+            bucket: v0_3_0::Bucket {
+                owner: john.address.clone(),
+                funds: empty.clone(),
+                fee_amount: None,
+            },
+        };
+        router.execute_contract(contract_admin.address.clone(), legacy.clone(), &seed, &[])?;
+    }
+
+    let new_code_id = router.store_code(fuzionmarket_contract());
+    let paged = MigrateMsg {
+        limit: Some(2),
+    };
+    let attr = |res: &AppResponse, key: &str| -> Option<String> {
+        res.events
+            .iter()
+            .flat_map(|e| e.attributes.iter())
+            .find(|a| a.key == key)
+            .map(|a| a.value.clone())
+    };
+
+    // Listings 1 & 2
+    let res = router.migrate_contract(
+        contract_admin.address.clone(),
+        legacy.clone(),
+        &paged,
+        new_code_id,
+    )?;
+    ensure!(
+        attr(&res, "migration_complete") == Some("false".to_string())
+            && attr(&res, "migrations_applied") == Some("none".to_string()),
+        here("First page should be incomplete", line!(), column!())
+    );
+    let version = cw2::query_contract_info(&router.wrap(), legacy.to_string())?;
+    ensure!(version.version == "0.3.0", here("cw2 version updated early", line!(), column!()));
+
+    let res: Result<AppResponse> = router.execute_contract(
+        john.address.clone(),
+        legacy.clone(),
+        &ExecuteMsg::FeeCycle {},
+        &[],
+    );
+    ensure!(res.is_err(), here("Execute during migration", line!(), column!()));
+
+    // Listing 3 & Bucket 1
+    let res = router.migrate_contract(
+        contract_admin.address.clone(),
+        legacy.clone(),
+        &paged,
+        new_code_id,
+    )?;
+    ensure!(
+        attr(&res, "migration_complete") == Some("false".to_string()),
+        here("Second page should be incomplete", line!(), column!())
+    );
+
+    // Bucket 2
+    let res = router.migrate_contract(
+        contract_admin.address.clone(),
+        legacy.clone(),
+        &paged,
+        new_code_id,
+    )?;
+    ensure!(
+        attr(&res, "migration_complete") == Some("true".to_string())
+            && attr(&res, "migrations_applied") == Some("0.4.0".to_string()),
+        here("Last page should complete", line!(), column!())
+    );
+    let version = cw2::query_contract_info(&router.wrap(), legacy.to_string())?;
+    ensure!(
+        version.version == crate::contract::CONTRACT_VERSION,
+        here("cw2 version not updated", line!(), column!())
+    );
+
+    let by_owner: crate::query::MultiListingResponse = router.wrap().query_wasm_smart(
+        legacy.clone(),
+        &QueryMsg::GetListingsByOwner {
+            owner: john.address.to_string(),
+            start_after: None,
+            limit: None,
+        },
+    )?;
+    ensure!(by_owner.listings.len() == 3, here("Listings not migrated", line!(), column!()));
+
+    let buckets: crate::query::MultiBucketResponse = router.wrap().query_wasm_smart(
+        legacy.clone(),
+        &QueryMsg::GetBuckets {
+            bucket_owner: john.address.to_string(),
+            start_after: None,
+            limit: None,
+        },
+    )?;
+    ensure!(buckets.buckets.len() == 2, here("Buckets not migrated", line!(), column!()));
+
+    // Execute works again
+    let delete = ExecuteMsg::DeleteListing {
+        listing_id: 3,
+        destination: None,
+    };
+    router.execute_contract(john.address.clone(), legacy, &delete, &[])?;
+
+    Ok(())
+}
+
+// <X> Can't migrate from a different contract
+// <X> Can't migrate to an older version
+#[test]
+fn migrate_rejects_foreign_and_downgrade() -> Result<(), anyhow::Error> {
+    use anyhow::Result;
+    use cw_multi_test::AppResponse;
+    // Setup
+    let mut router = App::default();
+    let contract_admin = create_users::fake_user("admin".to_string());
+    let royalty_addr = Addr::unchecked("royalty");
+    let new_code_id = router.store_code(fuzionmarket_contract());
+
+    let foreign = init_legacy_contract(
+        &mut router,
+        &contract_admin.address,
+        &royalty_addr,
+        "crates.io:cw20-base",
+        "0.3.0",
+    );
+    let res: Result<AppResponse> = router.migrate_contract(
+        contract_admin.address.clone(),
+        foreign,
+        &MigrateMsg {
+            limit: None,
+        },
+        new_code_id,
+    );
+    ensure!(res.is_err(), here("Foreign contract migrate", line!(), column!()));
+
+    let newer = init_legacy_contract(
+        &mut router,
+        &contract_admin.address,
+        &royalty_addr,
+        crate::contract::CONTRACT_NAME,
+        "99.0.0",
+    );
+    let res: Result<AppResponse> = router.migrate_contract(
+        contract_admin.address.clone(),
+        newer,
+        &MigrateMsg {
+            limit: None,
+        },
+        new_code_id,
+    );
+    ensure!(res.is_err(), here("Downgrade migrate", line!(), column!()));
+
+    Ok(())
+}
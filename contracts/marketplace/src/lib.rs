@@ -6,21 +6,20 @@ pub mod execute;
 #[cfg(test)]
 pub mod integration_tests;
 
+pub mod migrations;
 pub mod msg;
 pub mod query;
 pub mod state;
 pub mod utils;
 
-pub const MAX_NUM_ASSETS: u32 = 25u32;
-
 mod contract_imports {
     pub use cosmwasm_std::{
-        entry_point, from_binary, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo,
+        entry_point, from_binary, to_binary, Binary, Deps, DepsMut, Env, MessageInfo,
         Response, StdResult,
     };
-    pub use cw2::set_contract_version;
+    pub use cw2::{get_contract_version, set_contract_version};
     pub use cw20::{Balance, Cw20CoinVerified, Cw20ReceiveMsg, Cw20QueryMsg, TokenInfoResponse};
-    pub use cw721::{Cw721ReceiveMsg, Cw721QueryMsg};
+    pub use cw721::Cw721ReceiveMsg;
 
     pub use crate::error::ContractError;
     pub use crate::execute::{
@@ -28,46 +27,106 @@ mod contract_imports {
         execute_add_to_listing_cw721, execute_buy_listing, execute_change_ask,
         execute_create_bucket, execute_create_bucket_cw721, execute_create_listing,
         execute_create_listing_cw721, execute_delete_listing, execute_finalize,
-        execute_withdraw_bucket, execute_withdraw_purchased,
+        execute_withdraw_bucket, execute_withdraw_purchased, execute_propose_owner,
+        execute_accept_ownership, execute_renounce_ownership, execute_update_config,
+        execute_update_fee_schedule, execute_update_fee_routes, execute_sweep_fees,
+        execute_process_expired, process_expired, execute_make_offer, execute_retract_offer,
+        execute_accept_offer, execute_create_collection_offer, execute_fill_collection_offer,
+        execute_cancel_collection_offer,
+        execute_place_bid,
+        execute_settle_auction,
+        execute_commit_bid,
+        execute_buy_listing_with_funds,
+        execute_buy_listing_with_allowances,
+        execute_reveal_bid,
+        execute_claim_deposit,
+        execute_deposit_to_listing,
+        execute_deposit_to_bucket,
+        execute_add_approved_nfts,
+        execute_batch,
+    };
+    pub use crate::migrations::{
+        parse_version, run_migrations, DEFAULT_MIGRATION_LIMIT, MAX_MIGRATION_LIMIT,
+    };
+    pub use crate::msg::{
+        ExecuteMsg, FeeDenomUnvalidated, FeeRouteUnvalidated, InstantiateMsg, MarketSort, MigrateMsg,
+        QueryMsg, SortOrder, SudoMsg, ReceiveMsg, ReceiveNftMsg,
     };
-    pub use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, ReceiveMsg, ReceiveNftMsg};
     pub use crate::query::*;
-    pub use crate::state::{FeeDenom, Nft, FEE_DENOM, BUCKET_ID_USED, LISTING_ID_USED};
+    pub use crate::state::{
+        FeeRoute, FeeSchedule, Nft, CONFIG, FEE_ROUTES, FEE_SCHEDULE, BUCKET_ID_USED,
+        LISTING_ID_USED, MIGRATION_IN_PROGRESS, OWNER, PENDING_OWNER,
+    };
     pub use royalties::msg::InstantiateMsg as RoyaltyInstantiateMsg;
 }
 
 mod execute_imports {
     pub use crate::error::ContractError;
-    pub use crate::msg::{CreateListingMsg, GenericBalanceUnvalidated};
+    pub use crate::msg::{
+        Action, CreateListingMsg, Cw20CoinUnverified, FeeDenomUnvalidated, FeeRouteUnvalidated,
+        FlatFeeUnvalidated, GenericBalanceUnvalidated, NftUnverified,
+        WithdrawDestinationUnvalidated, MAX_BATCH_ACTIONS,
+    };
     pub use crate::state::{
+        accrue_fee,
+        check_pending_routes,
+        fee_route_msgs,
         genbal_cmp,
         listingz,
         BalanceUtil,
         Bucket,
-        FeeDenom,
+        Config,
+        FeeAsset,
+        FeeCoin,
+        FlatFee,
+        FeeMode,
+        FeeSchedule,
         GenericBalance,
         Listing,
         Nft,
         Status,
-        BUCKETS,
+        bucketz,
         BUCKET_ID_USED,
-        FEE_DENOM,
+        CONFIG,
+        FEE_LEDGER,
+        FEE_ROUTES,
+        FEE_SCHEDULE,
         LISTING_ID_USED, //BUCKET_COUNT, LISTING_COUNT
+        Offer,
+        offerz,
+        Auction,
+        Bid,
+        EnglishAuction,
+        SealedBid,
+        SealedBidAuction,
+        SealedBidTerms,
+        SEALED_BIDS,
+        SEALED_BID_COMMITMENTS,
+        SEALED_BID_TERMS,
+        CollectionOffer,
+        collection_offerz,
+        COLLECTION_OFFER_ID_USED,
+        OWNER,
+        PENDING_OWNER,
         ROYALTY_REGISTRY
     };
     pub use crate::utils::{calc_fee_coin, max, send_tokens_cosmos};
-    pub use cosmwasm_std::{Addr, DepsMut, Env, Response, StdError, CosmosMsg};
-    pub use cw20::Balance;
+    pub use cosmwasm_std::{
+        coin, to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, Order,
+        Response, StdResult, Storage, SubMsg, Uint128, WasmMsg,
+    };
+    pub use cw20::{Balance, Cw20ExecuteMsg};
+    pub use cw721::{Cw721ExecuteMsg, Cw721QueryMsg, Expiration, OwnerOfResponse};
+    pub use cw_storage_plus::PrefixBound;
     pub use std::collections::BTreeSet;
     
     pub use royalties::{
         RoyaltyInfo,
         msg::QueryMsg as RoyaltyQueryMsg,
     };
-
-    pub use super::MAX_NUM_ASSETS;
 }
 
+#[cfg(test)]
 mod integration_tests_imports {
     pub use anyhow::ensure;
     pub use core::fmt::Display;
@@ -77,6 +136,21 @@ mod integration_tests_imports {
     pub use cw20::{Cw20Coin, Cw20CoinVerified, Cw20Contract};
 }
 
+mod migrations_imports {
+    pub use crate::error::ContractError;
+    pub use crate::state::{
+        listingz, Bucket, Config, FeeAsset, FeeCoin, FeeDenom as CurrentFeeDenom, FeeMode, FeeSchedule,
+        FeeRoute, GenericBalance, Listing, Status, bucketz, CONFIG, FEE_ROUTES, FEE_SCHEDULE,
+        OWNER, PENDING_OWNER, MIGRATION_IN_PROGRESS, accrue_fee,
+    };
+    pub use cosmwasm_schema::cw_serde;
+    pub use cosmwasm_std::{Addr, Coin, DepsMut, Env, Order, StdResult, Timestamp};
+    pub use cw_storage_plus::{
+        Bound, Index, IndexList, IndexedMap, Item, Map, MultiIndex, UniqueIndex,
+    };
+    pub use semver::Version;
+}
+
 mod msg_imports {
     pub use std::collections::BTreeSet;
     pub use crate::query::*;
@@ -84,23 +158,40 @@ mod msg_imports {
     pub use cosmwasm_schema::{cw_serde, QueryResponses};
     pub use cw20::Cw20ReceiveMsg;
     pub use cw721::Cw721ReceiveMsg;
-    pub use cosmwasm_std::{Uint128, Coin, Addr};
-    pub use super::MAX_NUM_ASSETS;
+    pub use cosmwasm_std::{Binary, Coin, Order, Uint128};
 }
 
 mod query_imports {
+    pub use crate::msg::{AskAsset, MarketFilter, MarketSort};
     pub use crate::state::{
         listingz,
+        offerz,
+        Auction,
+        EnglishAuction,
+        collection_offerz,
+        CollectionOffer,
+        GenericBalance,
+        Offer,
         Bucket,
-        FeeDenom,
+        Config,
+        FeeAsset,
+        FeeMode,
+        FeeRoute,
+        FeeSchedule,
+        FeeTotals,
+        FlatFee,
         Listing,
+        Nft,
         Status,
-        BUCKETS,
-        FEE_DENOM, //LISTING_COUNT, BUCKET_COUNT
+        bucketz,
+        CONFIG,
+        FEE_LEDGER,
+        FEE_ROUTES,
+        FEE_SCHEDULE, //LISTING_COUNT, BUCKET_COUNT
     };
     pub use cosmwasm_schema::cw_serde;
     pub use cosmwasm_std::{Addr, Deps, Env, Order, StdError, StdResult};
-    pub use cw_storage_plus::PrefixBound;
+    pub use cw_storage_plus::{Bound, PrefixBound};
 }
 
 mod state_imports {
@@ -109,26 +200,30 @@ mod state_imports {
     pub use crate::utils::send_tokens_cosmos;
     pub use cosmwasm_schema::cw_serde;
     pub use cosmwasm_std::{
-        to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, StdResult, Timestamp, Uint128, WasmMsg,
+        to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Decimal, QuerierWrapper, StdResult,
+        Storage, Timestamp, Uint128, Uint256, WasmMsg,
     };
     pub use cw20::{Balance, Cw20CoinVerified, Cw20ExecuteMsg};
-    pub use cw721::Cw721ExecuteMsg;
-    pub use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, UniqueIndex};
+    pub use cw721::{Approval, Cw721ExecuteMsg, Cw721QueryMsg, OwnerOfResponse};
+    pub use cosmwasm_std::Order;
+    pub use sha2::{Digest, Sha256};
+    pub use cw_storage_plus::{
+        Bound, Index, IndexList, IndexedMap, Item, KeyDeserialize, Map, MultiIndex, Prefixer, PrimaryKey,
+        UniqueIndex,
+    };
+    pub use serde::{de::DeserializeOwned, Serialize};
     pub use std::collections::BTreeMap;
     pub use anybuf::Anybuf;
-    pub use cosmwasm_std::{Empty, coin, StdError};
+    pub use cosmwasm_std::coin;
     pub use royalties::RoyaltyInfo;
-    pub use super::MAX_NUM_ASSETS;
 }
 
 mod utils_imports {
     pub use crate::error::ContractError;
-    pub use crate::state::{FeeDenom, GenericBalance, Listing};
+    pub use crate::state::{FeeAsset, FeeCoin, FeeSchedule, GenericBalance};
     pub use cosmwasm_std::{
-        coin, coins, to_binary, Addr, BankMsg, Coin, CosmosMsg, DepsMut, Empty, StdError,
-        StdResult, WasmMsg,
+        coins, to_binary, Addr, BankMsg, CosmosMsg, Empty, StdResult, WasmMsg,
     };
-    pub use cw20::Cw20ExecuteMsg;
+    pub use cw20::{Cw20CoinVerified, Cw20ExecuteMsg};
     pub use cw721::Cw721ExecuteMsg;
-    pub use std::collections::BTreeMap;
 }
@@ -0,0 +1,344 @@
+use crate::migrations_imports::*;
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Registry
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Entries rewritten per `migrate` call when `MigrateMsg.limit` is not set
+pub const DEFAULT_MIGRATION_LIMIT: u32 = 100;
+/// Max entries rewritten per `migrate` call
+pub const MAX_MIGRATION_LIMIT: u32 = 500;
+
+/// A state transform that rewrites storage into the layout used by `version`
+///
+/// `migrate` rewrites at most `budget` entries, subtracting what it used,
+/// and returns `true` once the transform is complete. Until then it must
+/// be resumable by calling it again
+pub struct Migration {
+    pub version: &'static str,
+    pub migrate: fn(DepsMut, &Env, &mut u32) -> Result<bool, ContractError>,
+}
+
+/// Result of one `migrate` call
+pub struct MigrationProgress {
+    /// Migrations completed in this call
+    pub applied: Vec<&'static str>,
+    /// `false` if a migration ran out of budget & `migrate` must be called again
+    pub complete: bool,
+}
+
+/// All state transforms, in ascending version order
+///
+/// Add an entry here whenever a release changes the layout of
+/// `Listing`, `Bucket`, or any other stored item
+pub const MIGRATIONS: &[Migration] = &[Migration {
+    version: "0.4.0",
+    migrate: v0_4_0::migrate,
+}];
+
+/// Parse a cw2 version string
+pub fn parse_version(version: &str) -> Result<Version, ContractError> {
+    Version::parse(version)
+        .map_err(|_e| ContractError::GenericError(format!("Invalid contract version: {version}")))
+}
+
+/// Runs every migration newer than `stored` and no newer than `current`, in order
+/// - At most `limit` entries are rewritten, a migration that runs out
+///   is resumed by the next call
+/// - While a migration is unfinished `MIGRATION_IN_PROGRESS` is set,
+///   which blocks all execute messages
+pub fn run_migrations(
+    mut deps: DepsMut,
+    env: &Env,
+    stored: &Version,
+    current: &Version,
+    limit: u32,
+) -> Result<MigrationProgress, ContractError> {
+    let mut budget = limit;
+    let mut applied = vec![];
+
+    for migration in MIGRATIONS {
+        let version = parse_version(migration.version)?;
+
+        if &version > stored && &version <= current {
+            if !(migration.migrate)(deps.branch(), env, &mut budget)? {
+                MIGRATION_IN_PROGRESS.save(deps.storage, &migration.version.to_string())?;
+                return Ok(MigrationProgress {
+                    applied,
+                    complete: false,
+                });
+            }
+            applied.push(migration.version);
+        }
+    }
+
+    MIGRATION_IN_PROGRESS.remove(deps.storage);
+
+    Ok(MigrationProgress {
+        applied,
+        complete: true,
+    })
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// v0.3.0 Layout
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Storage layout as written by v0.3.0
+///
+/// Only used to read (and clear) old entries during migration
+pub mod v0_3_0 {
+    use super::*;
+
+    pub const BUCKETS: Map<(Addr, u64), Bucket> = Map::new("buckets");
+
+    pub const FEE_DENOM: Item<FeeDenom> = Item::new("fee_denom");
+
+    #[cw_serde]
+    pub enum FeeDenom {
+        JUNO(u64),
+        USDC(u64),
+    }
+
+    #[cw_serde]
+    pub struct Listing {
+        pub creator: Addr,
+        pub id: u64,
+        pub finalized_time: Option<Timestamp>,
+        pub expiration_time: Option<Timestamp>,
+        pub status: Status,
+        pub claimant: Option<Addr>,
+        pub whitelisted_buyer: Option<Addr>,
+        pub for_sale: GenericBalance,
+        pub ask: GenericBalance,
+        pub fee_amount: Option<Coin>,
+    }
+
+    #[cw_serde]
+    pub struct Bucket {
+        pub owner: Addr,
+        pub funds: GenericBalance,
+        pub fee_amount: Option<Coin>,
+    }
+
+    pub struct ListingIndexes<'a> {
+        pub id: UniqueIndex<'a, u64, Listing, (&'a Addr, u64)>,
+        pub finalized_date: MultiIndex<'a, u64, Listing, (&'a Addr, u64)>,
+        pub whitelisted_buyer: UniqueIndex<'a, (String, u64), Listing, (&'a Addr, u64)>,
+    }
+
+    impl IndexList<Listing> for ListingIndexes<'_> {
+        fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Listing>> + '_> {
+            let v: Vec<&dyn Index<Listing>> =
+                vec![&self.id, &self.finalized_date, &self.whitelisted_buyer];
+            Box::new(v.into_iter())
+        }
+    }
+
+    #[must_use]
+    pub fn listingz<'a>() -> IndexedMap<'a, (&'a Addr, u64), Listing, ListingIndexes<'a>> {
+        let indexes = ListingIndexes {
+            id: UniqueIndex::new(|a_listing| a_listing.id, "listing__id"),
+            finalized_date: MultiIndex::new(
+                |_pk, a_listing| a_listing.finalized_time.map_or(0_u64, |x| x.seconds()),
+                "listings_im",
+                "listing__finalized__date",
+            ),
+            whitelisted_buyer: UniqueIndex::new(
+                |listing| {
+                    (
+                        listing
+                            .whitelisted_buyer
+                            .clone()
+                            .map_or_else(|| "1".to_string(), |addr| addr.to_string()),
+                        listing.id,
+                    )
+                },
+                "listing__whitelisted__buyer",
+            ),
+        };
+
+        IndexedMap::new("listings_im", indexes)
+    }
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// v0.3.0 -> v0.4.0
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+pub mod v0_4_0 {
+    use super::*;
+
+    /// How far a v0.4.0 migration has got
+    /// - Holds the key of the last Listing or Bucket rewritten, `None` if none yet
+    #[cw_serde]
+    pub enum Cursor {
+        Listings(Option<(Addr, u64)>),
+        Buckets(Option<(Addr, u64)>),
+    }
+
+    /// Only present while the v0.4.0 migration is unfinished
+    pub const CURSOR: Item<Cursor> = Item::new("migration_v0_4_0_cursor");
+
+    /// Rewrites every v0.3.0 Listing & Bucket into the current layout
+    /// - Old Listing index entries are removed and rebuilt from the new Listing
+    /// - Buckets get their id & a bucket id index
+    /// - Listings & Buckets are indexed by the assets they hold,
+    ///   Listings also by the assets they ask for
+    /// - The contract admin becomes the owner (v0.3.0 had no owner)
+    /// - Config is set to the values v0.3.0 had hardcoded
+    /// - The JUNO <> USDC FeeDenom becomes a Rotation fee schedule
+    ///   at the same position in the cycle
+    /// - Fees keep going 100% to the Community Pool
+    /// - Fees on Listings & Buckets not yet withdrawn are credited
+    ///   to the Fee Ledger (v0.3.0 sent them on withdraw)
+    ///
+    /// Listings then Buckets are rewritten in key order, `budget` at a time,
+    /// resuming after the key saved in `CURSOR`
+    pub fn migrate(mut deps: DepsMut, env: &Env, budget: &mut u32) -> Result<bool, ContractError> {
+        let mut cursor = match CURSOR.may_load(deps.storage)? {
+            Some(cursor) => cursor,
+            None => {
+                migrate_items(deps.branch(), env)?;
+                Cursor::Listings(None)
+            }
+        };
+
+        if let Cursor::Listings(last) = cursor.clone() {
+            let min = last.as_ref().map(|(creator, id)| Bound::exclusive((creator, *id)));
+            let old_listings = v0_3_0::listingz()
+                .range(deps.storage, min, None, Order::Ascending)
+                .take(*budget as usize)
+                .collect::<StdResult<Vec<_>>>()?;
+            let done = (old_listings.len() as u32) < *budget;
+            *budget -= old_listings.len() as u32;
+
+            let mut last = last;
+            for ((creator, listing_id), old_listing) in old_listings {
+                if let Some(fee) = &old_listing.fee_amount {
+                    accrue_fee(deps.storage, &fee.clone().into())?;
+                }
+                v0_3_0::listingz().remove(deps.storage, (&creator, listing_id))?;
+                listingz().save(deps.storage, (&creator, listing_id), &listing(old_listing))?;
+                last = Some((creator, listing_id));
+            }
+
+            cursor = if done {
+                Cursor::Buckets(None)
+            } else {
+                Cursor::Listings(last)
+            };
+        }
+
+        if let Cursor::Buckets(last) = cursor.clone() {
+            let min = last.clone().map(Bound::exclusive);
+            let old_buckets = v0_3_0::BUCKETS
+                .range(deps.storage, min, None, Order::Ascending)
+                .take(*budget as usize)
+                .collect::<StdResult<Vec<_>>>()?;
+            let done = (old_buckets.len() as u32) < *budget;
+            *budget -= old_buckets.len() as u32;
+
+            let mut last = last;
+            for ((owner, bucket_id), old_bucket) in old_buckets {
+                if let Some(fee) = &old_bucket.fee_amount {
+                    accrue_fee(deps.storage, &fee.clone().into())?;
+                }
+                v0_3_0::BUCKETS.remove(deps.storage, (owner.clone(), bucket_id));
+                bucketz().save(
+                    deps.storage,
+                    (owner.clone(), bucket_id),
+                    &bucket(old_bucket, bucket_id),
+                )?;
+                last = Some((owner, bucket_id));
+            }
+
+            if done {
+                CURSOR.remove(deps.storage);
+                return Ok(true);
+            }
+            cursor = Cursor::Buckets(last);
+        }
+
+        CURSOR.save(deps.storage, &cursor)?;
+
+        Ok(false)
+    }
+
+    /// Owner, Config & fee Items, written once at the start of the migration
+    fn migrate_items(deps: DepsMut, env: &Env) -> Result<(), ContractError> {
+        let admin = deps
+            .querier
+            .query_wasm_contract_info(env.contract.address.clone())?
+            .admin
+            .map(|admin| deps.api.addr_validate(&admin))
+            .transpose()?;
+        OWNER.save(deps.storage, &admin)?;
+        PENDING_OWNER.save(deps.storage, &None)?;
+        CONFIG.save(deps.storage, &Config::default())?;
+
+        let old_fee_denom = v0_3_0::FEE_DENOM.load(deps.storage)?;
+        FEE_SCHEDULE.save(deps.storage, &fee_schedule(old_fee_denom))?;
+        v0_3_0::FEE_DENOM.remove(deps.storage);
+
+        FEE_ROUTES.save(deps.storage, &FeeRoute::default_routes())?;
+
+        Ok(())
+    }
+
+    pub fn listing(old: v0_3_0::Listing) -> Listing {
+        Listing {
+            creator: old.creator,
+            id: old.id,
+            finalized_time: old.finalized_time,
+            expiration_time: old.expiration_time,
+            status: old.status,
+            claimant: old.claimant,
+            whitelisted_buyer: old.whitelisted_buyer,
+            for_sale: old.for_sale,
+            ask: old.ask,
+            partial_fill: None,
+            auction: None,
+            approved_nfts: None,
+            flat_fee: None,
+            fee_amount: old.fee_amount.map(FeeCoin::from),
+        }
+    }
+
+    pub fn fee_schedule(old: v0_3_0::FeeDenom) -> FeeSchedule {
+        let (current, last_rotation) = match old {
+            v0_3_0::FeeDenom::JUNO(last) => (0, last),
+            v0_3_0::FeeDenom::USDC(last) => (1, last),
+        };
+
+        FeeSchedule {
+            denoms: vec![
+                CurrentFeeDenom {
+                    asset: FeeAsset::Native("ujunox".to_string()),
+                    fee_bps: None,
+                },
+                CurrentFeeDenom {
+                    asset: FeeAsset::Native("uusdcx".to_string()),
+                    fee_bps: None,
+                },
+            ],
+            mode: FeeMode::Rotation,
+            current,
+            last_rotation,
+            any_fungible: false,
+            flat_fee: None,
+        }
+    }
+
+    pub fn bucket(old: v0_3_0::Bucket, id: u64) -> Bucket {
+        Bucket {
+            owner: old.owner,
+            id,
+            funds: old.funds,
+            fee_amount: old.fee_amount.map(FeeCoin::from),
+        }
+    }
+}
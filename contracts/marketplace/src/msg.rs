@@ -1,24 +1,68 @@
 use cosmwasm_std::DepsMut;
-use cw20::Cw20CoinVerified;
+use cw20::{Cw20CoinVerified, Cw20QueryMsg, TokenInfoResponse};
 
-use crate::{msg_imports::*, ContractError, state::Nft};
+use crate::{msg_imports::*, ContractError, state::{CommunityPoolMsg, Config, FeeAsset, FeeDenom, FeeDestination, FeeMode, FeeRoute, FeeCoin, FlatFee, FlatFeeMode, Nft, PartialFill, Status, Auction, DutchAuction, EnglishAuction, SealedBidAuction, WithdrawDestination}};
 
 #[cw_serde]
 pub struct InstantiateMsg {
-    pub royalty_code_id: u64
+    pub royalty_code_id: u64,
+    /// Contract owner, defaults to the instantiator
+    pub owner: Option<String>,
+    /// Market parameters, defaults to `Config::default()`
+    pub config: Option<Config>,
+    /// Denoms (native or CW20) fees are charged in, an empty list charges no fees
+    pub fee_denoms: Vec<FeeDenomUnvalidated>,
+    pub fee_mode: FeeMode,
+    /// Charge fees in any native found when no fee denom is in a balance,
+    /// defaults to `false`
+    pub fee_any_fungible: Option<bool>,
+    /// Flat fee charged on every sale, defaults to none
+    pub flat_fee: Option<FlatFeeUnvalidated>,
+    /// Where fees are sent, defaults to 100% to the Community Pool
+    pub fee_routes: Option<Vec<FeeRouteUnvalidated>>,
+}
+
+/// Migrations rewrite at most `limit` stored entries per call
+/// - `limit` defaults to `DEFAULT_MIGRATION_LIMIT`, max `MAX_MIGRATION_LIMIT`
+/// - While a migration is unfinished, execute messages are rejected
+///   and `migrate` must be called again with the same code id
+#[cw_serde]
+pub struct MigrateMsg {
+    pub limit: Option<u32>,
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    /// Cycles the token denomination charged in fees (JUNO <> USDC)
+    /// Cycles the token denomination charged in fees to the next
+    /// denom in the fee schedule (Rotation mode only)
     /// </br>
     /// This can be called by anyone, but can only be called once
-    /// every 100,800 blocks
-    /// </br> (approx. 1 week assuming 6 sec blocks)
+    /// every `Config.fee_cycle_seconds` (default 1 week)
     FeeCycle {},
+    /// Sends pending fees in the Fee Ledger out across the fee routes
+    /// - `denoms`: only sweep these native denoms / CW20 addresses,
+    ///   sweeps everything if `None`
+    /// </br>
+    /// This can be called by anyone
+    SweepFees {
+        denoms: Option<Vec<String>>,
+    },
+    /// Returns up to `limit` expired Listings to their creators,
+    /// oldest expiration first
+    /// - `limit` defaults to `DEFAULT_EXPIRED_LIMIT`, max `MAX_EXPIRED_LIMIT`
+    /// - Pays `Config.expiry_bounty` per Listing to the caller
+    /// </br>
+    /// This can be called by anyone
+    ProcessExpired {
+        limit: Option<u32>,
+    },
     // Receive Filters
     Receive(Cw20ReceiveMsg),
     ReceiveNft(Cw721ReceiveMsg),
+    /// Runs up to `MAX_BATCH_ACTIONS` Listing & Bucket Actions in order,
+    /// if any Action fails none of them happen
+    /// - Natives sent must be exactly the sum of the Actions' `funds`
+    Batch(Vec<Action>),
     /// Create Listing with Native/cw20
     CreateListing {
         listing_id: u64,
@@ -40,8 +84,10 @@ pub enum ExecuteMsg {
     },
     /// Callable if listing has not been finalized
     /// or is expired
+    /// - `destination` defaults to the creator
     DeleteListing {
         listing_id: u64,
+        destination: Option<WithdrawDestinationUnvalidated>,
     },
     /// Create Bucket with native/cw20
     CreateBucket {
@@ -51,18 +97,206 @@ pub enum ExecuteMsg {
     AddToBucket {
         bucket_id: u64,
     },
+    /// Adds CW20s & NFTs to a Listing that isn't finalized, pulling them through
+    /// allowances & approvals given to this contract (Natives sent are added too)
+    /// </br>
+    /// Same rules as `AddToListing`, in one transaction
+    DepositToListing {
+        listing_id: u64,
+        cw20: Vec<Cw20CoinUnverified>,
+        nfts: Vec<NftUnverified>,
+    },
+    /// Lists NFTs that stay in the creator's wallet under a cw721 `Approve` to this
+    /// contract, instead of escrowing them. The approval should last until the Listing expires
+    /// </br>
+    /// Same rules as `AddToListing`. When the Listing sells, the NFTs are pulled from
+    /// the creator & the purchase fails if any was moved. Not for English or sealed bid auctions
+    AddApprovedNfts {
+        listing_id: u64,
+        nfts: Vec<NftUnverified>,
+    },
+    /// Adds CW20s & NFTs to a Bucket, pulling them through allowances & approvals
+    /// given to this contract (Natives sent are added too)
+    /// </br>
+    /// Same rules as `AddToBucket`, in one transaction
+    DepositToBucket {
+        bucket_id: u64,
+        cw20: Vec<Cw20CoinUnverified>,
+        nfts: Vec<NftUnverified>,
+    },
     /// Withdraw bucket
+    /// - `destination` defaults to the owner
     RemoveBucket {
         bucket_id: u64,
+        destination: Option<WithdrawDestinationUnvalidated>,
     },
     /// Buy listing
+    /// - If the fee schedule has a flat fee due, a native one must be sent in as funds
+    ///   & a CW20 one is pulled through an allowance given to this contract
+    /// - `quantity` is how much of a partial fill Listing to buy, defaults to the rest.
+    ///   The bucket must hold exactly the price of `quantity`, which is sent
+    ///   to the buyer right away
     BuyListing {
         listing_id: u64,
         bucket_id: u64,
+        quantity: Option<Uint128>,
     },
     /// Withdraw purchased listing
+    /// - `destination` defaults to the buyer
     WithdrawPurchased {
         listing_id: u64,
+        destination: Option<WithdrawDestinationUnvalidated>,
+    },
+    /// Offers a Bucket for a Listing in place of its `ask`, for `seconds`
+    /// - The Bucket is locked until the Offer is retracted or accepted,
+    ///   or the Listing is sold or removed
+    /// - Expiration has the same bounds as a Listing's
+    /// - If the fee schedule has a flat fee due, the Bucket must hold it.
+    ///   That flat fee is stored on the Offer & is what accepting charges
+    MakeOffer {
+        listing_id: u64,
+        bucket_id: u64,
+        seconds: u64,
+    },
+    /// Bidder only
+    /// </br>
+    /// Removes the Offer & unlocks its Bucket
+    RetractOffer {
+        bucket_id: u64,
+    },
+    /// Listing creator only
+    /// </br>
+    /// Sells the Listing for the offered Bucket, settled the same as `BuyListing`
+    /// - The flat fee (if any) stored when the Offer was made is taken out of the Bucket
+    AcceptOffer {
+        listing_id: u64,
+        bucket_id: u64,
+    },
+    /// Bids on any `quantity` NFTs from `collection`, with Native funds
+    /// - Funds sent are split evenly into the price paid per NFT
+    /// - Holders fill it with `ReceiveNftMsg::FillCollectionOffer`
+    /// - Expiration has the same bounds as a Listing's
+    CreateCollectionOffer {
+        offer_id: u64,
+        collection: String,
+        quantity: u32,
+        seconds: u64,
+    },
+    /// Bidder only
+    /// </br>
+    /// Removes the Collection Offer & returns the funds for NFTs not yet filled
+    CancelCollectionOffer {
+        offer_id: u64,
+    },
+    /// Bids on an auctioned Listing with the Native sent (CW20 bids use `PlaceBidCw20`)
+    /// </br>
+    /// Outbid funds are refunded when a higher bid lands
+    PlaceBid {
+        listing_id: u64,
+    },
+    /// Commits a sealed bid on a sealed bid auction, depositing the Native sent
+    /// (CW20 deposits use `CommitBidCw20`)
+    /// </br>
+    /// `commitment` is `SealedBidAuction::commitment(listing_id, bidder, amount, salt)`,
+    /// the deposit must be at least the reserve & hides the bid.
+    /// A commitment another bidder already submitted on the Listing is rejected,
+    /// as is any over `Config.max_sealed_bids` on the Listing
+    CommitBid {
+        listing_id: u64,
+        commitment: Binary,
+    },
+    /// Reveals a sealed bid once the bid phase has ended
+    /// </br>
+    /// Deposits that aren't the highest reveal are refunded
+    RevealBid {
+        listing_id: u64,
+        amount: Uint128,
+        salt: String,
+    },
+    /// Anyone can call this once a sealed bid auction's reveal phase has ended
+    /// </br>
+    /// Sends `bidder`'s (defaults to the sender) unrevealed deposit back to them,
+    /// or to the seller if the auction forfeits unrevealed deposits.
+    /// The winning deposit is settled by `SettleAuction` instead
+    ClaimDeposit {
+        listing_id: u64,
+        bidder: Option<String>,
+    },
+    /// Buys a whole Listing with the Natives sent, no Bucket needed
+    /// (CW20 payments use `BuyListingCw20`)
+    /// </br>
+    /// Send the price plus the flat fee (if any), anything over is refunded.
+    /// A CW20 flat fee is pulled through an allowance instead.
+    /// Purchased assets are sent right away
+    BuyListingWithFunds {
+        listing_id: u64,
+    },
+    /// Buys a whole Listing with the Natives sent, pulling the CW20s & NFTs in the
+    /// price through allowances & approvals given to this contract
+    /// </br>
+    /// Send the Natives in the price plus the flat fee (if any), anything over is refunded.
+    /// A CW20 flat fee is pulled through an allowance too.
+    /// Purchased assets are sent right away
+    BuyListingWithAllowances {
+        listing_id: u64,
+    },
+    /// Anyone can call this once an English auction has ended,
+    /// or a sealed bid auction's reveal phase has ended
+    /// </br>
+    /// Sends the Listing to the highest bidder & the bid to the seller (less fees & royalties),
+    /// or the Listing back to the seller if there were no bids.
+    /// A sealed bid winner's deposit surplus is refunded, other deposits use `ClaimDeposit`.
+    /// Flat fee (if any) set when the auction was finalized is taken out of the winning bid,
+    /// nothing is sent in
+    SettleAuction {
+        listing_id: u64,
+    },
+    /// Owner only
+    /// </br>
+    /// Proposes a new owner, who must call AcceptOwnership.
+    /// Replaces any previous proposal
+    ProposeOwner {
+        new_owner: String,
+    },
+    /// Pending owner only
+    AcceptOwnership {},
+    /// Owner only
+    /// </br>
+    /// Permanently removes the owner & any pending proposal
+    RenounceOwnership {},
+    /// Owner only
+    /// </br>
+    /// Updates any provided Config fields, the rest are unchanged.
+    /// Only affects Listings finalized & sales made after the update
+    UpdateConfig {
+        min_expiration_seconds: Option<u64>,
+        max_expiration_seconds: Option<u64>,
+        max_num_assets: Option<u32>,
+        max_royalty_bps: Option<u64>,
+        fee_bps: Option<u64>,
+        fee_cycle_seconds: Option<u64>,
+        /// A zero amount removes the bounty
+        expiry_bounty: Option<Coin>,
+        /// Can't be under the flat fee in the current fee schedule
+        max_flat_fee: Option<Uint128>,
+        max_sealed_bids: Option<u32>,
+    },
+    /// Owner only
+    /// </br>
+    /// Replaces the fee schedule, rotation restarts from the first denom
+    /// - `any_fungible` & `flat_fee` are turned off if `None`
+    UpdateFeeSchedule {
+        denoms: Vec<FeeDenomUnvalidated>,
+        mode: FeeMode,
+        any_fungible: Option<bool>,
+        flat_fee: Option<FlatFeeUnvalidated>,
+    },
+    /// Owner only
+    /// </br>
+    /// Replaces where fees are sent, bps of all routes must sum to 10_000
+    /// - Can't route to the Community Pool while CW20 fees can be charged or are pending
+    UpdateFeeRoutes {
+        routes: Vec<FeeRouteUnvalidated>,
     },
 }
 
@@ -81,6 +315,25 @@ pub enum ReceiveMsg {
     AddToBucketCw20 {
         bucket_id: u64,
     },
+    CreateCollectionOfferCw20 {
+        offer_id: u64,
+        collection: String,
+        quantity: u32,
+        seconds: u64,
+    },
+    PlaceBidCw20 {
+        listing_id: u64,
+    },
+    CommitBidCw20 {
+        listing_id: u64,
+        commitment: Binary,
+    },
+    /// Buys a whole Listing priced in this CW20, see `BuyListingWithFunds`.
+    /// A flat fee in this CW20 is taken out of what's sent, a CW20 flat fee in
+    /// another token is pulled through an allowance. Fails if a native flat fee is due
+    BuyListingCw20 {
+        listing_id: u64,
+    },
 }
 
 #[cw_serde]
@@ -98,46 +351,319 @@ pub enum ReceiveNftMsg {
     AddToBucketCw721 {
         bucket_id: u64,
     },
+    /// Sells the NFT into a Collection Offer for its price less fees & royalties
+    FillCollectionOffer {
+        offer_id: u64,
+    },
+}
+
+/// Most Actions in one `ExecuteMsg::Batch`
+pub const MAX_BATCH_ACTIONS: usize = 20;
+
+/// One step of an `ExecuteMsg::Batch`, same as the `ExecuteMsg` of the same name
+/// - `funds` are the Natives this step uses, out of those sent with the Batch
+/// - There is no `Batch` Action, Batches can't be nested
+#[cw_serde]
+pub enum Action {
+    CreateListing {
+        listing_id: u64,
+        create_msg: CreateListingMsg,
+        funds: Vec<Coin>,
+    },
+    AddToListing {
+        listing_id: u64,
+        funds: Vec<Coin>,
+    },
+    ChangeAsk {
+        listing_id: u64,
+        new_ask: GenericBalanceUnvalidated,
+    },
+    Finalize {
+        listing_id: u64,
+        seconds: u64,
+    },
+    DeleteListing {
+        listing_id: u64,
+        destination: Option<WithdrawDestinationUnvalidated>,
+    },
+    CreateBucket {
+        bucket_id: u64,
+        funds: Vec<Coin>,
+    },
+    AddToBucket {
+        bucket_id: u64,
+        funds: Vec<Coin>,
+    },
+    RemoveBucket {
+        bucket_id: u64,
+        destination: Option<WithdrawDestinationUnvalidated>,
+    },
+    DepositToListing {
+        listing_id: u64,
+        cw20: Vec<Cw20CoinUnverified>,
+        nfts: Vec<NftUnverified>,
+        funds: Vec<Coin>,
+    },
+    AddApprovedNfts {
+        listing_id: u64,
+        nfts: Vec<NftUnverified>,
+    },
+    DepositToBucket {
+        bucket_id: u64,
+        cw20: Vec<Cw20CoinUnverified>,
+        nfts: Vec<NftUnverified>,
+        funds: Vec<Coin>,
+    },
+    /// `funds` is the flat fee (if any) in a native, a CW20 one is pulled through an allowance
+    BuyListing {
+        listing_id: u64,
+        bucket_id: u64,
+        quantity: Option<Uint128>,
+        funds: Vec<Coin>,
+    },
+    WithdrawPurchased {
+        listing_id: u64,
+        destination: Option<WithdrawDestinationUnvalidated>,
+    },
+}
+
+impl Action {
+    /// Natives this Action uses
+    pub fn funds(&self) -> &[Coin] {
+        match self {
+            Action::CreateListing { funds, .. }
+            | Action::AddToListing { funds, .. }
+            | Action::CreateBucket { funds, .. }
+            | Action::AddToBucket { funds, .. }
+            | Action::DepositToListing { funds, .. }
+            | Action::DepositToBucket { funds, .. }
+            | Action::BuyListing { funds, .. } => funds,
+            _ => &[],
+        }
+    }
+}
+
+/// Called by the chain, ie a clock module
+#[cw_serde]
+pub enum SudoMsg {
+    /// Same as `ExecuteMsg::ProcessExpired`, without a bounty
+    ProcessExpired {
+        limit: Option<u32>,
+    },
+    /// Called every block by Juno's clock module,
+    /// processes up to `DEFAULT_EXPIRED_LIMIT` expired Listings
+    ClockEndBlock {},
 }
 
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
     /// Gets the current Fee Denom
+    /// - Rotation: the denom currently charged
+    /// - Priority: the highest priority denom
     #[returns(FeeDenomResponse)]
     GetFeeDenom {},
+    /// Gets every fee denom & rate, the mode, and the next rotation time
+    #[returns(FeeScheduleResponse)]
+    GetFeeSchedule {},
+    /// Gets where fees are sent
+    #[returns(FeeRoutesResponse)]
+    GetFeeRoutes {},
+    /// Gets pending & lifetime fees for every denom fees have been taken in
+    #[returns(FeeLedgerResponse)]
+    GetFeeLedger {},
     /// Gets Buckets owned by user
-    /// - Requires pagination
+    /// - `limit` defaults to `DEFAULT_LIMIT`, max `MAX_LIMIT`
+    /// - `start_after` is the `next` of the previous page
     #[returns(MultiBucketResponse)]
     GetBuckets {
         bucket_owner: String,
-        page_num: u8,
+        start_after: Option<u64>,
+        limit: Option<u32>,
     },
     /// Gets Listings owned by user
-    /// - Requires pagination
+    /// - `limit` defaults to `DEFAULT_LIMIT`, max `MAX_LIMIT`
+    /// - `start_after` is the `next` of the previous page
     #[returns(MultiListingResponse)]
     GetListingsByOwner {
         owner: String,
-        page_num: u8,
+        start_after: Option<u64>,
+        limit: Option<u32>,
     },
     /// Gets listings user is whitelisted for
+    /// - Does not return non-finalized, expired, or already sold listings
+    /// - `limit` defaults to `DEFAULT_LIMIT`, max `MAX_LIMIT`
+    /// - `start_after` is the `next` of the previous page
     #[returns(MultiListingResponse)]
     GetListingsByWhitelist {
         owner: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
     },
     /// Gets listings for Marketplace
     /// - Does not return non-finalized, expired, or already sold listings
-    /// - Requires pagination
-    #[returns(MultiListingResponse)]
+    /// - `limit` defaults to `DEFAULT_LIMIT`, max `MAX_LIMIT`
+    /// - `start_after` is the `next` of the previous page
+    #[returns(MarketListingsResponse)]
     GetListingsForMarket {
-        page_num: u8,
+        start_after: Option<MarketCursor>,
+        limit: Option<u32>,
+    },
+    /// Gets Listings matching `filter`, sorted by `sort_by`
+    /// - `filter` defaults to matching every Listing
+    /// - `sort_by` defaults to `FinalizedTime`, `order` to `Ascending`
+    /// - `limit` defaults to `DEFAULT_LIMIT`, max `MAX_LIMIT`
+    /// - `start_after` is the `next` of the previous page, with the same sort & order
+    /// - Without a `creator` or `collection` filter, at most `MAX_MARKET_SCAN` Listings
+    ///   are read per call, a short page can still have a `next`
+    #[returns(MarketListingsResponse)]
+    GetMarketListings {
+        filter: Option<MarketFilter>,
+        sort_by: Option<MarketSort>,
+        order: Option<SortOrder>,
+        start_after: Option<MarketCursor>,
+        limit: Option<u32>,
+    },
+    /// Gets a single Listing by ID
+    /// - Errors with `not found` if no Listing has this ID
+    #[returns(SingleListingResponse)]
+    GetListing {
+        listing_id: u64,
+    },
+    /// Gets what a Listing costs right now
+    /// - Dutch auctions: the decayed price
+    /// - English auctions: the lowest bid that would be accepted
+    /// - Sealed bid auctions: the reserve
+    /// - Otherwise: the `ask`
+    #[returns(CurrentPriceResponse)]
+    GetCurrentPrice {
+        listing_id: u64,
+    },
+    /// Gets a single Bucket by ID
+    /// - Errors with `not found` if no Bucket has this ID
+    #[returns(SingleBucketResponse)]
+    GetBucket {
+        bucket_id: u64,
+    },
+    /// Gets up to `MAX_LOOKUP_IDS` Listings by ID
+    /// - `listing` is `None` for IDs that don't exist
+    #[returns(ListingsByIdResponse)]
+    GetListingsById {
+        listing_ids: Vec<u64>,
+    },
+    /// Flags which of up to `MAX_LOOKUP_IDS` Listings have NFTs listed by approval
+    /// that were moved or whose approval lapsed, those can't be bought
+    /// - IDs that don't exist are skipped
+    #[returns(LapsedApprovalsResponse)]
+    GetLapsedApprovals {
+        listing_ids: Vec<u64>,
+    },
+    /// Gets up to `MAX_LOOKUP_IDS` Buckets by ID
+    /// - `bucket` is `None` for IDs that don't exist
+    #[returns(BucketsByIdResponse)]
+    GetBucketsById {
+        bucket_ids: Vec<u64>,
+    },
+    /// Gets the Listing or Bucket an NFT is escrowed in, if any,
+    /// else the Listing it is listed in by approval
+    #[returns(NftLocationResponse)]
+    WhereIsNft {
+        collection: String,
+        token_id: String,
+    },
+    /// Gets Listings selling at least one NFT from `collection`
+    /// - `limit` defaults to `DEFAULT_LIMIT`, max `MAX_LIMIT`
+    /// - `start_after` is the `next` of the previous page
+    #[returns(MultiListingResponse)]
+    ListingsByCollection {
+        collection: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Gets Listings selling `denom`, a native denom or CW20 address
+    /// - `limit` defaults to `DEFAULT_LIMIT`, max `MAX_LIMIT`
+    /// - `start_after` is the `next` of the previous page
+    #[returns(MultiListingResponse)]
+    ListingsContainingDenom {
+        denom: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Gets Buckets holding at least one NFT from `collection`
+    /// - `limit` defaults to `DEFAULT_LIMIT`, max `MAX_LIMIT`
+    /// - `start_after` is the `next` of the previous page
+    #[returns(MultiBucketResponse)]
+    BucketsByCollection {
+        collection: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Gets Buckets holding `denom`, a native denom or CW20 address
+    /// - `limit` defaults to `DEFAULT_LIMIT`, max `MAX_LIMIT`
+    /// - `start_after` is the `next` of the previous page
+    #[returns(MultiBucketResponse)]
+    BucketsContainingDenom {
+        denom: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Gets Listings asking for `asset`
+    /// - Does not return non-finalized, expired, or already sold listings
+    /// - `limit` defaults to `DEFAULT_LIMIT`, max `MAX_LIMIT`
+    /// - `start_after` is the `next` of the previous page
+    #[returns(MultiListingResponse)]
+    ListingsByAskAsset {
+        asset: AskAsset,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Gets Offers made on a Listing, with the funds offered
+    /// - Includes expired Offers not yet retracted
+    /// - `limit` defaults to `DEFAULT_LIMIT`, max `MAX_LIMIT`
+    /// - `start_after` is the `next` of the previous page
+    #[returns(MultiOfferResponse)]
+    GetOffersByListing {
+        listing_id: u64,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Gets Offers made by `bidder`, with the funds offered
+    /// - Includes expired Offers not yet retracted
+    /// - `limit` defaults to `DEFAULT_LIMIT`, max `MAX_LIMIT`
+    /// - `start_after` is the `next` of the previous page
+    #[returns(MultiOfferResponse)]
+    GetOffersByBidder {
+        bidder: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Gets Collection Offers that can be filled with an NFT from `collection`
+    /// - Does not return expired Collection Offers
+    /// - `limit` defaults to `DEFAULT_LIMIT`, max `MAX_LIMIT`
+    /// - `start_after` is the `next` of the previous page
+    #[returns(MultiCollectionOfferResponse)]
+    GetCollectionOffers {
+        collection: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Gets Collection Offers made by `bidder`, including expired ones
+    /// - `limit` defaults to `DEFAULT_LIMIT`, max `MAX_LIMIT`
+    /// - `start_after` is the `next` of the previous page
+    #[returns(MultiCollectionOfferResponse)]
+    GetCollectionOffersByBidder {
+        bidder: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
     },
-    // #[returns(ListingInfoResponse)]
-    // GetListingInfo {
-    //     listing_id: u64,
-    // },
     #[returns(Option<cosmwasm_std::Addr>)]
-    GetRoyaltyAddr {}
+    GetRoyaltyAddr {},
+    /// Gets the current & pending owner
+    #[returns(OwnershipResponse)]
+    GetOwnership {},
+    /// Gets the market Config & contract version
+    #[returns(ConfigResponse)]
+    GetConfig {},
 }
 
 /// Must be sent along with message when creating a Listing
@@ -145,6 +671,61 @@ pub enum QueryMsg {
 pub struct CreateListingMsg {
     pub ask: GenericBalanceUnvalidated,
     pub whitelisted_buyer: Option<String>,
+    /// Lets buyers take any part of the Listing, `ask` becomes a unit price
+    pub partial_fill: Option<PartialFill>,
+    /// Auctions the Listing, `ask` becomes the reserve (English) or start (Dutch) price
+    pub auction: Option<AuctionMsg>,
+}
+
+/// Auction settings for a new Listing
+#[cw_serde]
+pub enum AuctionMsg {
+    /// See `EnglishAuction`
+    English {
+        min_increment: Uint128,
+        extension_seconds: u64,
+    },
+    /// See `DutchAuction`
+    Dutch {
+        floor: Uint128,
+        step_seconds: Option<u64>,
+    },
+    /// See `SealedBidAuction`
+    SealedBid {
+        reveal_seconds: u64,
+        forfeit_unrevealed: bool,
+    },
+}
+
+impl From<AuctionMsg> for Auction {
+    fn from(msg: AuctionMsg) -> Self {
+        match msg {
+            AuctionMsg::English {
+                min_increment,
+                extension_seconds,
+            } => Auction::English(EnglishAuction {
+                min_increment,
+                extension_seconds,
+                highest_bid: None,
+            }),
+            AuctionMsg::Dutch {
+                floor,
+                step_seconds,
+            } => Auction::Dutch(DutchAuction {
+                floor,
+                step_seconds,
+            }),
+            AuctionMsg::SealedBid {
+                reveal_seconds,
+                forfeit_unrevealed,
+            } => Auction::SealedBid(SealedBidAuction {
+                reveal_seconds,
+                forfeit_unrevealed,
+                commits: 0,
+                highest_bid: None,
+            }),
+        }
+    }
 }
 
 
@@ -160,9 +741,9 @@ impl GenericBalanceUnvalidated {
     /// Errors if any are true:
     /// - Any Native or CW20 token amount is 0
     /// - `deps.api.addr_validate` errors for any cw20 or nft
-    /// - Number of Natives, CW20's, and NFTs are over MAX_NUM_ASSETS
+    /// - Number of Natives, CW20's, and NFTs are over `max_num_assets`
     /// - Any duplicate Native Denom, Cw20 contract_addr, or NFT (contract_addr + token_id)
-    pub fn validate(self, deps: &DepsMut) -> Result<GenericBalance, ContractError> {
+    pub fn validate(self, deps: &DepsMut, max_num_assets: u32) -> Result<GenericBalance, ContractError> {
 
         // Check Natives for 0's
         if self.native.iter().any(|n| n.amount.is_zero()) {
@@ -202,15 +783,15 @@ impl GenericBalanceUnvalidated {
             }).collect::<Result<Vec<Nft>, ContractError>>()?;
 
 
-        // Validate number of assets (MAX_NUM_ASSETS is to avoid out of gas problems)
-        let _ = self
+        // Validate number of assets (max_num_assets is to avoid out of gas problems)
+        self
             .native.len()
             .checked_add(validated_cw20s.len())
             .and_then(|v| v.checked_add(validated_nfts.len()))
-            .ok_or_else(|| ContractError::GenericError(format!("Listing cannot contain over {} items", MAX_NUM_ASSETS)))
+            .ok_or_else(|| ContractError::GenericError(format!("Listing cannot contain over {} items", max_num_assets)))
             .and_then(|v| {
-                if v == 0 || v as u32 > MAX_NUM_ASSETS {
-                    return Err(ContractError::GenericError(format!("Number of items must be between 1 and {}", MAX_NUM_ASSETS)));
+                if v == 0 || v as u32 > max_num_assets {
+                    return Err(ContractError::GenericError(format!("Number of items must be between 1 and {}", max_num_assets)));
                 }
                 Ok(())
             })?;
@@ -264,4 +845,214 @@ pub struct NftUnverified {
     pub token_id: String,
 }
 
+/// Where a withdrawal is sent, see `WithdrawDestination`
+#[cw_serde]
+pub enum WithdrawDestinationUnvalidated {
+    Address(String),
+    Contract {
+        contract_addr: String,
+        cw20_msg: Option<Binary>,
+        nft_msg: Option<Binary>,
+    },
+}
+
+impl WithdrawDestinationUnvalidated {
+    /// Validate to a `WithdrawDestination`, errors if the address is invalid
+    pub fn validate(self, deps: &DepsMut) -> Result<WithdrawDestination, ContractError> {
+        match self {
+            WithdrawDestinationUnvalidated::Address(addr) => Ok(WithdrawDestination::Address(
+                deps.api
+                    .addr_validate(&addr)
+                    .map_err(|_e| ContractError::GenericError(format!("Invalid withdraw address: {addr}")))?,
+            )),
+            WithdrawDestinationUnvalidated::Contract {
+                contract_addr,
+                cw20_msg,
+                nft_msg,
+            } => Ok(WithdrawDestination::Contract {
+                contract_addr: deps.api.addr_validate(&contract_addr).map_err(|_e| {
+                    ContractError::GenericError(format!("Invalid withdraw contract: {contract_addr}"))
+                })?,
+                cw20_msg,
+                nft_msg,
+            }),
+        }
+    }
+}
+
+#[cw_serde]
+pub enum FeeDestinationUnvalidated {
+    CommunityPool(CommunityPoolMsg),
+    Address(String),
+    Contract {
+        contract_addr: String,
+        msg: Binary,
+    },
+}
+
+#[cw_serde]
+pub struct FeeRouteUnvalidated {
+    pub destination: FeeDestinationUnvalidated,
+    /// 100 = 1%
+    pub bps: u64,
+}
+
+impl FeeRouteUnvalidated {
+    /// Validate a list of `FeeRouteUnvalidated` to `Vec<FeeRoute>`
+    /// - Errors if any address is invalid or `FeeRoute::check_valid` fails
+    pub fn validate_all(routes: Vec<FeeRouteUnvalidated>, deps: &DepsMut) -> Result<Vec<FeeRoute>, ContractError> {
+        let valid_routes = routes
+            .into_iter()
+            .map(|route| {
+                let destination = match route.destination {
+                    FeeDestinationUnvalidated::CommunityPool(cp_msg) => FeeDestination::CommunityPool(cp_msg),
+                    FeeDestinationUnvalidated::Address(addr) => FeeDestination::Address(
+                        deps.api
+                            .addr_validate(&addr)
+                            .map_err(|_e| ContractError::GenericError(format!("Invalid fee route address: {addr}")))?,
+                    ),
+                    FeeDestinationUnvalidated::Contract {
+                        contract_addr,
+                        msg,
+                    } => FeeDestination::Contract {
+                        contract_addr: deps.api.addr_validate(&contract_addr).map_err(|_e| {
+                            ContractError::GenericError(format!("Invalid fee route contract: {contract_addr}"))
+                        })?,
+                        msg,
+                    },
+                };
+
+                Ok(FeeRoute {
+                    destination,
+                    bps: route.bps,
+                })
+            })
+            .collect::<Result<Vec<FeeRoute>, ContractError>>()?;
+
+        FeeRoute::check_valid(&valid_routes)?;
+
+        Ok(valid_routes)
+    }
+}
+
+#[cw_serde]
+pub enum FeeAssetUnvalidated {
+    Native(String),
+    /// CW20 contract address
+    Cw20(String),
+}
+
+impl FeeAssetUnvalidated {
+    /// Validate to `FeeAsset`
+    /// - Errors if a CW20 address is invalid or does not answer a CW20 TokenInfo query
+    pub fn validate(self, deps: &DepsMut) -> Result<FeeAsset, ContractError> {
+        match self {
+            FeeAssetUnvalidated::Native(denom) => Ok(FeeAsset::Native(denom)),
+            FeeAssetUnvalidated::Cw20(address) => {
+                let valid = deps
+                    .api
+                    .addr_validate(&address)
+                    .map_err(|_e| ContractError::GenericError(format!("Invalid fee CW20: {address}")))?;
+
+                let _x: TokenInfoResponse = deps
+                    .querier
+                    .query_wasm_smart(valid.clone(), &Cw20QueryMsg::TokenInfo {})
+                    .map_err(|_e| ContractError::GenericError(format!("Invalid fee CW20: {address}")))?;
+
+                Ok(FeeAsset::Cw20(valid))
+            }
+        }
+    }
+}
+
+/// Filters for `GetMarketListings`, every filter set must match
+#[cw_serde]
+#[derive(Default)]
+pub struct MarketFilter {
+    pub status: Option<Status>,
+    pub creator: Option<String>,
+    /// `true` for only whitelisted Listings, `false` for only public Listings
+    pub whitelisted: Option<bool>,
+    /// Expires before this time in seconds, unfinalized Listings never match
+    pub expires_before: Option<u64>,
+    /// Expires after this time in seconds, unfinalized Listings never match
+    pub expires_after: Option<u64>,
+    /// Selling at least one NFT from this collection
+    pub collection: Option<String>,
+}
+
+#[cw_serde]
+pub enum MarketSort {
+    FinalizedTime,
+    ExpirationTime,
+}
+
+#[cw_serde]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+impl From<SortOrder> for Order {
+    fn from(order: SortOrder) -> Self {
+        match order {
+            SortOrder::Ascending => Order::Ascending,
+            SortOrder::Descending => Order::Descending,
+        }
+    }
+}
+
+/// An asset a Listing can ask for
+#[cw_serde]
+pub enum AskAsset {
+    Native(String),
+    Cw20(String),
+    /// Any NFT from the collection
+    Collection(String),
+    Nft {
+        collection: String,
+        token_id: String,
+    },
+}
+
+#[cw_serde]
+pub struct FeeDenomUnvalidated {
+    pub asset: FeeAssetUnvalidated,
+    /// Fee rate for this denom (100 = 1%), `None` uses `Config.fee_bps`
+    pub fee_bps: Option<u64>,
+}
+
+/// Flat fee in a native or a CW20, see `FlatFee`
+#[cw_serde]
+pub struct FlatFeeUnvalidated {
+    pub asset: FeeAssetUnvalidated,
+    pub amount: Uint128,
+    pub mode: FlatFeeMode,
+}
+
+impl FlatFeeUnvalidated {
+    /// Validate to `FlatFee`
+    /// - Errors if the CW20 address is invalid or does not answer a CW20 TokenInfo query
+    pub fn validate(self, deps: &DepsMut) -> Result<FlatFee, ContractError> {
+        Ok(FlatFee {
+            amount: FeeCoin::new(self.amount, self.asset.validate(deps)?),
+            mode: self.mode,
+        })
+    }
+}
 
+impl FeeDenomUnvalidated {
+    /// Validate a list of `FeeDenomUnvalidated` to `Vec<FeeDenom>`
+    /// - Errors if any CW20 address is invalid or does not answer a CW20 TokenInfo query
+    pub fn validate_all(denoms: Vec<FeeDenomUnvalidated>, deps: &DepsMut) -> Result<Vec<FeeDenom>, ContractError> {
+        denoms
+            .into_iter()
+            .map(|fee_denom| {
+                Ok(FeeDenom {
+                    asset: fee_denom.asset.validate(deps)?,
+                    fee_bps: fee_denom.fee_bps,
+                })
+            })
+            .collect()
+    }
+}
@@ -1,4 +1,5 @@
-use crate::{query_imports::*, state::ROYALTY_REGISTRY};
+use crate::{query_imports::*, state::{OWNER, PENDING_OWNER, ROYALTY_REGISTRY}};
+use cw2::get_contract_version;
 
 //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // Queries
@@ -6,66 +7,121 @@ use crate::{query_imports::*, state::ROYALTY_REGISTRY};
 
 /// Get the current Fee Denom
 pub fn get_fee_denom(deps: Deps) -> StdResult<FeeDenomResponse> {
-    let fee_denom: FeeDenom = FEE_DENOM.load(deps.storage)?;
+    let fee_schedule: FeeSchedule = FEE_SCHEDULE.load(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
 
-    let (name, next_change) = match fee_denom {
-        FeeDenom::JUNO(x) => ("JUNO".to_string(), x),
-        FeeDenom::USDC(y) => ("USDC".to_string(), y),
-    };
+    let current = fee_schedule.current();
 
     Ok(FeeDenomResponse {
-        name,
-        denom: fee_denom.value(),
-        next_change,
+        asset: current.map(|d| d.asset.clone()),
+        fee_bps: current.map(|d| d.rate(config.fee_bps)),
+        next_change: fee_schedule.next_rotation(config.fee_cycle_seconds),
+    })
+}
+
+/// Get the full fee schedule with each denom's fee rate
+pub fn get_fee_schedule(deps: Deps) -> StdResult<FeeScheduleResponse> {
+    let fee_schedule: FeeSchedule = FEE_SCHEDULE.load(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    Ok(FeeScheduleResponse {
+        denoms: fee_schedule
+            .denoms
+            .iter()
+            .map(|d| FeeRate {
+                asset: d.asset.clone(),
+                fee_bps: d.rate(config.fee_bps),
+            })
+            .collect(),
+        current: fee_schedule.current().map(|d| d.asset.clone()),
+        last_rotation: fee_schedule.last_rotation,
+        next_rotation: fee_schedule.next_rotation(config.fee_cycle_seconds),
+        mode: fee_schedule.mode,
+        any_fungible: fee_schedule.any_fungible,
+        flat_fee: fee_schedule.flat_fee,
     })
 }
 
+/// Page size when `limit` is not set
+pub const DEFAULT_LIMIT: u32 = 20;
+/// Largest page a list query will return
+pub const MAX_LIMIT: u32 = 30;
+
+/// Takes one page of `limit` items from `iter`
+/// - `next` is the cursor of the last item returned, `None` on the last page
+/// - Anything filtered out must be filtered out of `iter` already
+fn paginate<T, C>(
+    iter: impl Iterator<Item = StdResult<T>>,
+    limit: Option<u32>,
+    cursor: impl Fn(&T) -> C,
+) -> StdResult<(Vec<T>, Option<C>)> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT) as usize;
+
+    // One extra to know if there is a next page
+    let mut items = iter.take(limit + 1).collect::<StdResult<Vec<T>>>()?;
+
+    let next = if items.len() > limit {
+        items.truncate(limit);
+        items.last().map(cursor)
+    } else {
+        None
+    };
+
+    Ok((items, next))
+}
+
+/// Listing can be bought right now
+/// - Finalized, not expired & not Closed (sold)
+fn is_live(listing: &Listing, current_time: u64) -> bool {
+    listing.expiration_time.filter(|exp| exp.seconds() >= current_time).is_some()
+        && listing.status != Status::Closed
+}
+
 /// Get all buckets owned by an address
-/// - Requires pagination to avoid exceeding gas limits
-/// - `Page 1: first 20` `Page 2: second 20`...
-pub fn get_buckets(deps: Deps, bucket_owner: &str, page_num: u8) -> StdResult<MultiBucketResponse> {
+/// - Paginated by bucket id
+pub fn get_buckets(
+    deps: Deps,
+    bucket_owner: &str,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<MultiBucketResponse> {
     let valid_owner = deps.api.addr_validate(bucket_owner)?;
 
-    let to_skip_usize = usize::from(page_num * 20 - 20);
+    let user_buckets = bucketz().prefix(valid_owner).range(
+        deps.storage,
+        start_after.map(Bound::exclusive),
+        None,
+        Order::Ascending,
+    );
 
-    let user_buckets: Vec<_> = BUCKETS
-        .prefix(valid_owner)
-        .range(deps.storage, None, None, Order::Ascending)
-        .collect::<StdResult<Vec<_>>>()?
-        .into_iter()
-        .skip(to_skip_usize)
-        .take(20)
-        .collect();
+    let (buckets, next) = paginate(user_buckets, limit, |(bucket_id, _)| *bucket_id)?;
 
     Ok(MultiBucketResponse {
-        buckets: user_buckets,
+        buckets,
+        next,
     })
 }
 
 /// Get all listings owned by an Address
-/// - Requires pagination to avoid exceeding gas limits
-/// - `Page 1: first 20` `Page 2: second 20`...
+/// - Paginated by listing id
 pub fn get_listings_by_owner(
     deps: Deps,
     owner: &str,
-    page_num: u8,
+    start_after: Option<u64>,
+    limit: Option<u32>,
 ) -> StdResult<MultiListingResponse> {
     let valid_owner = deps.api.addr_validate(owner)?;
 
-    let to_skip_usize = usize::from(page_num * 20 - 20);
-
-    let listing_data: Vec<_> = listingz()
+    let listing_data = listingz()
         .prefix(&valid_owner)
-        .range(deps.storage, None, None, Order::Ascending)
-        .collect::<StdResult<Vec<_>>>()?
-        .into_iter()
-        .skip(to_skip_usize)
-        .take(20)
-        .map(|entry| entry.1)
-        .collect();
+        .range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .map(|entry| entry.map(|(_id, listing)| listing));
+
+    let (listings, next) = paginate(listing_data, limit, |listing| listing.id)?;
 
     Ok(MultiListingResponse {
-        listings: listing_data,
+        listings,
+        next,
     })
 }
 
@@ -73,86 +129,562 @@ pub fn get_listings_by_owner(
 /// - Only returns listings that are finalized
 /// - Only returns listings that are not expired
 /// - Only returns listings that are not closed (sold)
-pub fn get_whitelisted(deps: Deps, env: Env, owner: String) -> StdResult<MultiListingResponse> {
+/// - Paginated by listing id
+pub fn get_whitelisted(
+    deps: Deps,
+    env: Env,
+    owner: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<MultiListingResponse> {
     let valid_owner = deps.api.addr_validate(owner.as_str())?;
 
     let current_time = env.block.time.seconds();
 
-    let search_whitelists: Vec<_> = listingz()
+    let search_whitelists = listingz()
         .idx
         .whitelisted_buyer
         .prefix(valid_owner.to_string())
-        .range(deps.storage, None, None, Order::Ascending)
-        .collect::<StdResult<Vec<_>>>()? // StdResult<Vec<((Addr, u64), Listing)>>
-        .iter()
-        .filter_map(|entry| {
-            let x = entry.1.clone();
-            // Disregard entries that have no expiration, are expired, or are already closed
-            if x.expiration_time.filter(|&exp| exp.seconds() >= current_time).is_none()
-                || x.status == Status::Closed
-            {
-                None
-            } else {
-                Some(x)
-            }
-        })
-        .collect();
+        .range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .map(|entry| entry.map(|(_pk, listing)| listing))
+        // Disregard entries that have no expiration, are expired, or are already closed
+        .filter(|entry| entry.as_ref().map_or(true, |listing| is_live(listing, current_time)));
+
+    let (listings, next) = paginate(search_whitelists, limit, |listing| listing.id)?;
 
     Ok(MultiListingResponse {
-        listings: search_whitelists,
+        listings,
+        next,
     })
 }
 
-/// - Does not return listings finalized over 2 weeks prior
+/// - Does not return listings finalized over `Config.max_expiration_seconds` prior
 /// - Does not return listings that have not been finalized
 /// - Does not return listings that are expired
 /// - Does not return listings that are Closed (sold)
+/// - Paginated by finalized time, then creator & listing id
 pub fn get_listings_for_market(
     deps: Deps,
     env: Env,
-    page_num: u8,
-) -> StdResult<MultiListingResponse> {
+    start_after: Option<MarketCursor>,
+    limit: Option<u32>,
+) -> StdResult<MarketListingsResponse> {
     let current_time = env.block.time.seconds();
-    let two_weeks_ago_in_seconds = current_time - 1_209_600;
+    // Listings finalized before this are expired
+    let max_expiration = CONFIG.load(deps.storage)?.max_expiration_seconds;
+    let two_weeks_ago_in_seconds = current_time.saturating_sub(max_expiration);
 
-    let to_skip_usize = usize::from(page_num * 20 - 20);
+    // A cursor older than the cutoff starts from the cutoff
+    let start_after = start_after
+        .filter(|cursor| cursor.time >= two_weeks_ago_in_seconds)
+        .map(|cursor| {
+            let creator = deps.api.addr_validate(&cursor.creator)?;
+            Ok::<_, StdError>((cursor.time, creator, cursor.listing_id))
+        })
+        .transpose()?;
 
-    let listings_in_range: Vec<_> = listingz()
-        .idx
-        .finalized_date
-        .prefix_range_raw(
+    let listings = listingz();
+    let listings_in_range = match &start_after {
+        Some((finalized_time, creator, listing_id)) => listings.idx.finalized_date.range_raw(
+            deps.storage,
+            Some(Bound::exclusive((*finalized_time, (creator, *listing_id)))),
+            None,
+            Order::Ascending,
+        ),
+        None => listings.idx.finalized_date.prefix_range_raw(
             deps.storage,
             Some(PrefixBound::inclusive(two_weeks_ago_in_seconds)),
             None,
             Order::Ascending,
-        )
-        .collect::<StdResult<Vec<_>>>()?
-        .iter()
-        .skip(to_skip_usize)
-        .take(20)
-        .filter_map(|entry| {
-            let x = entry.1.clone();
-            // Disregard entries that have no expiration, are expired, or are already Closed
-            if x.expiration_time.filter(|&exp| exp.seconds() >= current_time).is_none()
-                || x.status == Status::Closed
-            {
-                None
-            } else {
-                Some(x)
-            }
+        ),
+    }
+    .map(|entry| entry.map(|(_pk, listing)| listing))
+    // Disregard entries that have no expiration, are expired, or are already Closed
+    .filter(|entry| entry.as_ref().map_or(true, |listing| is_live(listing, current_time)));
+
+    let (listings, next) = paginate(listings_in_range, limit, |listing| MarketCursor {
+        time: listing.finalized_time.map_or(0, |t| t.seconds()),
+        creator: listing.creator.to_string(),
+        listing_id: listing.id,
+    })?;
+
+    Ok(MarketListingsResponse {
+        listings,
+        next,
+    })
+}
+
+/// Most Listings `get_market_listings` reads from the sort index per call,
+/// when no filter narrows it down to a creator or collection
+pub const MAX_MARKET_SCAN: usize = 300;
+
+/// Get listings matching `filter`, sorted by finalized or expiration time
+/// - Unlike `get_listings_for_market`, nothing is filtered out by default
+/// - Listings with no finalized / expiration time sort as `0`
+/// - Paginated by sort time, then creator & listing id
+/// - Filtering by creator or collection only reads that creator's or collection's
+///   Listings, through the creator prefix & `collection` index
+/// - Otherwise at most `MAX_MARKET_SCAN` Listings are read, so a short (or empty)
+///   page can still have a `next` cursor to resume the scan from
+pub fn get_market_listings(
+    deps: Deps,
+    filter: MarketFilter,
+    sort_by: MarketSort,
+    order: Order,
+    start_after: Option<MarketCursor>,
+    limit: Option<u32>,
+) -> StdResult<MarketListingsResponse> {
+    let creator = filter.creator.as_ref().map(|c| deps.api.addr_validate(c)).transpose()?;
+    let collection = filter.collection.as_ref().map(|c| deps.api.addr_validate(c)).transpose()?;
+
+    let start_after = start_after
+        .map(|cursor| {
+            let creator = deps.api.addr_validate(&cursor.creator)?;
+            Ok::<_, StdError>((cursor.time, creator, cursor.listing_id))
         })
-        .collect();
+        .transpose()?;
+
+    let sort_time = |listing: &Listing| {
+        match sort_by {
+            MarketSort::FinalizedTime => listing.finalized_time,
+            MarketSort::ExpirationTime => listing.expiration_time,
+        }
+        .map_or(0, |t| t.seconds())
+    };
+    let cursor = |listing: &Listing| MarketCursor {
+        time: sort_time(listing),
+        creator: listing.creator.to_string(),
+        listing_id: listing.id,
+    };
+    let matches = |listing: &Listing| {
+        matches_filter(listing, &filter, creator.as_ref(), collection.as_ref())
+    };
+
+    let listings = listingz();
+
+    // Creator or collection filter, sort just those Listings
+    let narrowed: Option<Vec<StdResult<Listing>>> = match (&creator, &collection) {
+        (Some(creator), _) => Some(
+            listings
+                .prefix(creator)
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|entry| entry.map(|(_id, listing)| listing))
+                .collect(),
+        ),
+        (None, Some(collection)) => Some(
+            listings
+                .idx
+                .collection
+                .ids(deps.storage, collection.clone(), None)
+                .map(|id| load_listing(deps, id?))
+                .collect(),
+        ),
+        (None, None) => None,
+    };
+
+    if let Some(narrowed) = narrowed {
+        let key = |listing: &Listing| (sort_time(listing), listing.creator.clone(), listing.id);
+
+        let mut matching = narrowed.into_iter().collect::<StdResult<Vec<Listing>>>()?;
+        matching.retain(|listing| {
+            matches(listing)
+                && start_after.as_ref().map_or(true, |after| match order {
+                    Order::Ascending => key(listing) > *after,
+                    Order::Descending => key(listing) < *after,
+                })
+        });
+        matching.sort_by_key(key);
+        if order == Order::Descending {
+            matching.reverse();
+        }
+
+        let (listings, next) = paginate(matching.into_iter().map(Ok), limit, cursor)?;
+
+        return Ok(MarketListingsResponse {
+            listings,
+            next,
+        });
+    }
+
+    let bound = start_after
+        .as_ref()
+        .map(|(time, creator, listing_id)| Bound::exclusive((*time, (creator, *listing_id))));
+    let (min, max) = match order {
+        Order::Ascending => (bound, None),
+        Order::Descending => (None, bound),
+    };
+
+    let index = match sort_by {
+        MarketSort::FinalizedTime => &listings.idx.finalized_date,
+        MarketSort::ExpirationTime => &listings.idx.expiration_date,
+    };
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT) as usize;
+    let mut page = vec![];
+    let mut next = None;
+    let mut last_read = None;
+
+    for (read, entry) in index.range_raw(deps.storage, min, max, order).enumerate() {
+        let (_pk, listing) = entry?;
+
+        // Page is full & there's more to read
+        if page.len() == limit {
+            next = page.last().map(cursor);
+            break;
+        }
+        // Scan cap reached, resume after the last Listing read
+        if read == MAX_MARKET_SCAN {
+            next = last_read;
+            break;
+        }
+
+        last_read = Some(cursor(&listing));
+        if matches(&listing) {
+            page.push(listing);
+        }
+    }
+
+    Ok(MarketListingsResponse {
+        listings: page,
+        next,
+    })
+}
+
+/// `creator` & `collection` are the validated `filter.creator` & `filter.collection`
+fn matches_filter(
+    listing: &Listing,
+    filter: &MarketFilter,
+    creator: Option<&Addr>,
+    collection: Option<&Addr>,
+) -> bool {
+    let expiration = listing.expiration_time.map(|t| t.seconds());
+
+    filter.status.as_ref().map_or(true, |status| &listing.status == status)
+        && creator.map_or(true, |creator| listing.creator == *creator)
+        && filter
+            .whitelisted
+            .map_or(true, |whitelisted| listing.whitelisted_buyer.is_some() == whitelisted)
+        && filter.expires_before.map_or(true, |before| expiration.is_some_and(|exp| exp < before))
+        && filter.expires_after.map_or(true, |after| expiration.is_some_and(|exp| exp > after))
+        && collection.map_or(true, |collection| {
+            listing.for_sale.nfts.iter().any(|nft| nft.contract_address == *collection)
+        })
+}
+
+/// Get where an NFT is in the market, `None` if it is not
+/// - Escrowed in a Listing or Bucket is found before listed by approval,
+///   as an approval left behind by a moved NFT no longer holds it
+pub fn where_is_nft(
+    deps: Deps,
+    collection: String,
+    token_id: String,
+) -> StdResult<NftLocationResponse> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let nft = Nft {
+        contract_address: collection.clone(),
+        token_id: token_id.clone(),
+    };
+    let key = (collection, token_id);
+
+    let mut approved_in = None;
+    for listing_id in listingz().idx.nft.ids(deps.storage, key.clone(), None) {
+        let listing = load_listing(deps, listing_id?)?;
+        let approved = listing.approved().contains(&nft);
+        let location = AssetLocation::Listing {
+            listing_id: listing.id,
+            creator: listing.creator,
+            status: listing.status,
+            approved,
+        };
+
+        if !approved {
+            return Ok(NftLocationResponse {
+                location: Some(location),
+            });
+        }
+        approved_in.get_or_insert(location);
+    }
+
+    if let Some(bucket_id) = bucketz().idx.nft.ids(deps.storage, key, None).next().transpose()? {
+        let bucket = load_bucket(deps, bucket_id)?;
+        return Ok(NftLocationResponse {
+            location: Some(AssetLocation::Bucket {
+                bucket_id,
+                owner: bucket.owner,
+            }),
+        });
+    }
+
+    Ok(NftLocationResponse {
+        location: approved_in,
+    })
+}
+
+/// Get all listings selling an NFT from `collection`
+/// - Paginated by listing id
+pub fn get_listings_by_collection(
+    deps: Deps,
+    collection: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<MultiListingResponse> {
+    let collection = deps.api.addr_validate(&collection)?;
+
+    let listing_data = listingz()
+        .idx
+        .collection
+        .ids(deps.storage, collection, start_after)
+        .map(|id| id.and_then(|id| load_listing(deps, id)));
+
+    let (listings, next) = paginate(listing_data, limit, |listing| listing.id)?;
+
+    Ok(MultiListingResponse {
+        listings,
+        next,
+    })
+}
+
+/// Get all listings selling `denom`, a native denom or CW20 address
+/// - Paginated by listing id
+pub fn get_listings_containing_denom(
+    deps: Deps,
+    denom: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<MultiListingResponse> {
+    let listing_data = listingz()
+        .idx
+        .denom
+        .ids(deps.storage, denom, start_after)
+        .map(|id| id.and_then(|id| load_listing(deps, id)));
+
+    let (listings, next) = paginate(listing_data, limit, |listing| listing.id)?;
+
+    Ok(MultiListingResponse {
+        listings,
+        next,
+    })
+}
+
+/// Get all buckets holding an NFT from `collection`
+/// - Paginated by bucket id
+pub fn get_buckets_by_collection(
+    deps: Deps,
+    collection: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<MultiBucketResponse> {
+    let collection = deps.api.addr_validate(&collection)?;
+
+    let bucket_data = bucketz()
+        .idx
+        .collection
+        .ids(deps.storage, collection, start_after)
+        .map(|id| id.and_then(|id| Ok((id, load_bucket(deps, id)?))));
+
+    let (buckets, next) = paginate(bucket_data, limit, |(bucket_id, _)| *bucket_id)?;
+
+    Ok(MultiBucketResponse {
+        buckets,
+        next,
+    })
+}
+
+/// Get all buckets holding `denom`, a native denom or CW20 address
+/// - Paginated by bucket id
+pub fn get_buckets_containing_denom(
+    deps: Deps,
+    denom: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<MultiBucketResponse> {
+    let bucket_data = bucketz()
+        .idx
+        .denom
+        .ids(deps.storage, denom, start_after)
+        .map(|id| id.and_then(|id| Ok((id, load_bucket(deps, id)?))));
+
+    let (buckets, next) = paginate(bucket_data, limit, |(bucket_id, _)| *bucket_id)?;
+
+    Ok(MultiBucketResponse {
+        buckets,
+        next,
+    })
+}
+
+/// Get all live listings asking for `asset`
+/// - Same filters as `get_listings_for_market`
+/// - Paginated by listing id
+pub fn get_listings_by_ask_asset(
+    deps: Deps,
+    env: Env,
+    asset: AskAsset,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<MultiListingResponse> {
+    let current_time = env.block.time.seconds();
+
+    let listings = listingz();
+    let ids = match asset {
+        AskAsset::Native(denom) => listings.idx.ask_denom.ids(deps.storage, denom, start_after),
+        AskAsset::Cw20(address) => listings.idx.ask_denom.ids(
+            deps.storage,
+            deps.api.addr_validate(&address)?.to_string(),
+            start_after,
+        ),
+        AskAsset::Collection(collection) => listings.idx.ask_collection.ids(
+            deps.storage,
+            deps.api.addr_validate(&collection)?,
+            start_after,
+        ),
+        AskAsset::Nft {
+            collection,
+            token_id,
+        } => listings.idx.ask_nft.ids(
+            deps.storage,
+            (deps.api.addr_validate(&collection)?, token_id),
+            start_after,
+        ),
+    };
+
+    let listing_data = ids
+        .map(|id| id.and_then(|id| load_listing(deps, id)))
+        // Disregard entries that have no expiration, are expired, or are already Closed
+        .filter(|entry| entry.as_ref().map_or(true, |listing| is_live(listing, current_time)));
+
+    let (listings, next) = paginate(listing_data, limit, |listing| listing.id)?;
 
     Ok(MultiListingResponse {
-        listings: listings_in_range,
+        listings,
+        next,
+    })
+}
+
+/// Get all offers made on a listing, with the funds in each offered Bucket
+/// - Includes expired offers that haven't been retracted
+/// - Paginated by bucket id
+pub fn get_offers_by_listing(
+    deps: Deps,
+    listing_id: u64,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<MultiOfferResponse> {
+    let offers = offerz().idx.listing.prefix(listing_id).range(
+        deps.storage,
+        start_after.map(Bound::exclusive),
+        None,
+        Order::Ascending,
+    );
+
+    offer_page(deps, offers, limit)
+}
+
+/// Get all offers made by a bidder, with the funds in each offered Bucket
+/// - Includes expired offers & offers on sold Listings that haven't been retracted
+/// - Paginated by bucket id
+pub fn get_offers_by_bidder(
+    deps: Deps,
+    bidder: &str,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<MultiOfferResponse> {
+    let valid_bidder = deps.api.addr_validate(bidder)?;
+
+    let offers = offerz().idx.bidder.prefix(valid_bidder).range(
+        deps.storage,
+        start_after.map(Bound::exclusive),
+        None,
+        Order::Ascending,
+    );
+
+    offer_page(deps, offers, limit)
+}
+
+fn offer_page(
+    deps: Deps,
+    offers: impl Iterator<Item = StdResult<(u64, Offer)>>,
+    limit: Option<u32>,
+) -> StdResult<MultiOfferResponse> {
+    let offer_data = offers.map(|entry| {
+        let (_bucket_id, offer) = entry?;
+        let funds = load_bucket(deps, offer.bucket_id)?.funds;
+        Ok(OfferDetails {
+            offer,
+            funds,
+        })
+    });
+
+    let (offers, next) = paginate(offer_data, limit, |details| details.offer.bucket_id)?;
+
+    Ok(MultiOfferResponse {
+        offers,
+        next,
     })
 }
 
-/// Unimplemented
+/// Get all unexpired collection offers on a collection
+/// - Paginated by offer id
+pub fn get_collection_offers(
+    deps: Deps,
+    env: Env,
+    collection: &str,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<MultiCollectionOfferResponse> {
+    let valid_collection = deps.api.addr_validate(collection)?;
+
+    let offer_data = collection_offerz()
+        .idx
+        .collection
+        .prefix(valid_collection)
+        .range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .map(|entry| entry.map(|(_id, offer)| offer))
+        // Disregard expired offers, they can't be filled
+        .filter(|entry| entry.as_ref().map_or(true, |offer| offer.expiration_time >= env.block.time));
+
+    let (offers, next) = paginate(offer_data, limit, |offer| offer.id)?;
+
+    Ok(MultiCollectionOfferResponse {
+        offers,
+        next,
+    })
+}
+
+/// Get all collection offers made by a bidder, including expired ones
+/// - Paginated by offer id
+pub fn get_collection_offers_by_bidder(
+    deps: Deps,
+    bidder: &str,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<MultiCollectionOfferResponse> {
+    let valid_bidder = deps.api.addr_validate(bidder)?;
+
+    let offer_data = collection_offerz()
+        .idx
+        .bidder
+        .prefix(valid_bidder)
+        .range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .map(|entry| entry.map(|(_id, offer)| offer));
+
+    let (offers, next) = paginate(offer_data, limit, |offer| offer.id)?;
+
+    Ok(MultiCollectionOfferResponse {
+        offers,
+        next,
+    })
+}
+
+/// Max number of IDs in a single GetListingsById / GetBucketsById
+pub const MAX_LOOKUP_IDS: usize = 30;
+
 /// Gets a single listing by id
-pub fn get_single_listing(deps: Deps, listing_id: u64) -> StdResult<SingleListingResponse> {
-    let Some((_pk, listing)): Option<(_, Listing)> = listingz().idx.id.item(deps.storage, listing_id)? else {
-        return Err(StdError::GenericErr { msg: "Invalid listing ID".to_string() });
+/// - Errors with `StdError::NotFound` if no Listing has this id
+pub fn get_listing(deps: Deps, listing_id: u64) -> StdResult<SingleListingResponse> {
+    let Some((_pk, listing)): Option<(_, Listing)> =
+        listingz().idx.id.item(deps.storage, listing_id)?
+    else {
+        return Err(StdError::not_found(format!("Listing {listing_id}")));
     };
 
     Ok(SingleListingResponse {
@@ -160,22 +692,218 @@ pub fn get_single_listing(deps: Deps, listing_id: u64) -> StdResult<SingleListin
     })
 }
 
+/// Gets what a Listing costs at the current block time
+/// - Errors with `StdError::NotFound` if no Listing has this id
+pub fn get_current_price(
+    deps: Deps,
+    env: &Env,
+    listing_id: u64,
+) -> StdResult<CurrentPriceResponse> {
+    let SingleListingResponse {
+        listing,
+    } = get_listing(deps, listing_id)?;
+
+    let price = match &listing.auction {
+        Some(Auction::English(EnglishAuction {
+            min_increment,
+            highest_bid: Some(highest),
+            ..
+        })) => listing.ask.with_amount(highest.amount.saturating_add(*min_increment)),
+        _ => listing.current_ask(env.block.time),
+    };
+
+    Ok(CurrentPriceResponse {
+        listing_id,
+        price,
+    })
+}
+
+/// Gets a single bucket by id
+/// - Errors with `StdError::NotFound` if no Bucket has this id
+pub fn get_bucket(deps: Deps, bucket_id: u64) -> StdResult<SingleBucketResponse> {
+    let Some((_pk, bucket)): Option<(_, Bucket)> =
+        bucketz().idx.id.item(deps.storage, bucket_id)?
+    else {
+        return Err(StdError::not_found(format!("Bucket {bucket_id}")));
+    };
+
+    Ok(SingleBucketResponse {
+        bucket,
+    })
+}
+
+fn load_listing(deps: Deps, listing_id: u64) -> StdResult<Listing> {
+    get_listing(deps, listing_id).map(|res| res.listing)
+}
+
+fn load_bucket(deps: Deps, bucket_id: u64) -> StdResult<Bucket> {
+    get_bucket(deps, bucket_id).map(|res| res.bucket)
+}
+
+/// Gets listings by id, in the order requested
+pub fn get_listings_by_id(deps: Deps, listing_ids: Vec<u64>) -> StdResult<ListingsByIdResponse> {
+    check_lookup_ids(&listing_ids)?;
+
+    let listings = listing_ids
+        .into_iter()
+        .map(|listing_id| {
+            Ok(ListingLookup {
+                listing_id,
+                listing: listingz()
+                    .idx
+                    .id
+                    .item(deps.storage, listing_id)?
+                    .map(|(_pk, listing)| listing),
+            })
+        })
+        .collect::<StdResult<Vec<ListingLookup>>>()?;
+
+    Ok(ListingsByIdResponse {
+        listings,
+    })
+}
+
+/// Gets the NFTs listed by approval that were moved or lost their approval,
+/// for each Listing requested that has any
+pub fn get_lapsed_approvals(
+    deps: Deps,
+    env: &Env,
+    listing_ids: Vec<u64>,
+) -> StdResult<LapsedApprovalsResponse> {
+    check_lookup_ids(&listing_ids)?;
+
+    let mut listings = vec![];
+    for listing_id in listing_ids {
+        let Some((_pk, listing)) = listingz().idx.id.item(deps.storage, listing_id)? else {
+            continue;
+        };
+
+        let lapsed: Vec<Nft> = listing
+            .approved()
+            .iter()
+            .filter(|nft| {
+                nft.approval(&deps.querier, &listing.creator, &env.contract.address).is_none()
+            })
+            .cloned()
+            .collect();
+
+        if !lapsed.is_empty() {
+            listings.push(LapsedApprovals {
+                listing_id,
+                lapsed,
+            });
+        }
+    }
+
+    Ok(LapsedApprovalsResponse {
+        listings,
+    })
+}
+
+/// Gets buckets by id, in the order requested
+pub fn get_buckets_by_id(deps: Deps, bucket_ids: Vec<u64>) -> StdResult<BucketsByIdResponse> {
+    check_lookup_ids(&bucket_ids)?;
+
+    let buckets = bucket_ids
+        .into_iter()
+        .map(|bucket_id| {
+            Ok(BucketLookup {
+                bucket_id,
+                bucket: bucketz().idx.id.item(deps.storage, bucket_id)?.map(|(_pk, bucket)| bucket),
+            })
+        })
+        .collect::<StdResult<Vec<BucketLookup>>>()?;
+
+    Ok(BucketsByIdResponse {
+        buckets,
+    })
+}
+
+fn check_lookup_ids(ids: &[u64]) -> StdResult<()> {
+    if ids.len() > MAX_LOOKUP_IDS {
+        return Err(StdError::generic_err(format!(
+            "Cannot look up over {MAX_LOOKUP_IDS} IDs at once"
+        )));
+    }
+
+    Ok(())
+}
+
 
 pub fn get_royalty_contract(deps: Deps) -> StdResult<Option<Addr>> {
     let z: Option<Addr> = ROYALTY_REGISTRY.load(deps.storage)?;
     Ok(z)
 }
 
+/// Get where fees are sent
+pub fn get_fee_routes(deps: Deps) -> StdResult<FeeRoutesResponse> {
+    Ok(FeeRoutesResponse {
+        routes: FEE_ROUTES.load(deps.storage)?,
+    })
+}
+
+/// Get pending & lifetime fees for every denom in the Fee Ledger
+pub fn get_fee_ledger(deps: Deps) -> StdResult<FeeLedgerResponse> {
+    let fees = FEE_LEDGER
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|entry| entry.map(|(_key, totals)| totals))
+        .collect::<StdResult<Vec<FeeTotals>>>()?;
+
+    Ok(FeeLedgerResponse {
+        fees,
+    })
+}
+
+/// Get the market Config & cw2 contract version
+pub fn get_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let version = get_contract_version(deps.storage)?;
+
+    Ok(ConfigResponse {
+        config: CONFIG.load(deps.storage)?,
+        contract_name: version.contract,
+        contract_version: version.version,
+    })
+}
+
+/// Get the current owner & pending owner
+pub fn get_ownership(deps: Deps) -> StdResult<OwnershipResponse> {
+    Ok(OwnershipResponse {
+        owner: OWNER.load(deps.storage)?,
+        pending_owner: PENDING_OWNER.load(deps.storage)?,
+    })
+}
+
 //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // Responses
 //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 #[cw_serde]
 pub struct FeeDenomResponse {
-    pub name: String,
-    pub denom: String,
-    /// When FeeCycle can be called next
-    pub next_change: u64,
+    /// `None` if no fee denoms are set
+    pub asset: Option<FeeAsset>,
+    pub fee_bps: Option<u64>,
+    /// When FeeCycle can be called next, `None` if the schedule does not rotate
+    pub next_change: Option<u64>,
+}
+
+#[cw_serde]
+pub struct FeeRate {
+    pub asset: FeeAsset,
+    /// 100 = 1%
+    pub fee_bps: u64,
+}
+
+#[cw_serde]
+pub struct FeeScheduleResponse {
+    /// In rotation / priority order
+    pub denoms: Vec<FeeRate>,
+    pub mode: FeeMode,
+    pub current: Option<FeeAsset>,
+    pub last_rotation: u64,
+    /// When FeeCycle can be called next, `None` if the schedule does not rotate
+    pub next_rotation: Option<u64>,
+    pub any_fungible: bool,
+    pub flat_fee: Option<FlatFee>,
 }
 
 #[cw_serde]
@@ -183,12 +911,148 @@ pub struct SingleListingResponse {
     pub listing: Listing,
 }
 
+#[cw_serde]
+pub struct CurrentPriceResponse {
+    pub listing_id: u64,
+    pub price: GenericBalance,
+}
+
+#[cw_serde]
+pub struct SingleBucketResponse {
+    pub bucket: Bucket,
+}
+
+#[cw_serde]
+pub struct ListingLookup {
+    pub listing_id: u64,
+    /// `None` if no Listing has this id
+    pub listing: Option<Listing>,
+}
+
+#[cw_serde]
+pub struct ListingsByIdResponse {
+    pub listings: Vec<ListingLookup>,
+}
+
+#[cw_serde]
+pub struct LapsedApprovals {
+    pub listing_id: u64,
+    /// NFTs no longer in the creator's wallet or no longer approved
+    pub lapsed: Vec<Nft>,
+}
+
+#[cw_serde]
+pub struct LapsedApprovalsResponse {
+    /// Only Listings with a lapsed approval
+    pub listings: Vec<LapsedApprovals>,
+}
+
+#[cw_serde]
+pub struct BucketLookup {
+    pub bucket_id: u64,
+    /// `None` if no Bucket has this id
+    pub bucket: Option<Bucket>,
+}
+
+#[cw_serde]
+pub struct BucketsByIdResponse {
+    pub buckets: Vec<BucketLookup>,
+}
+
 #[cw_serde]
 pub struct MultiListingResponse {
     pub listings: Vec<Listing>,
+    /// Pass as `start_after` for the next page, `None` on the last page
+    pub next: Option<u64>,
 }
 
 #[cw_serde]
 pub struct MultiBucketResponse {
     pub buckets: Vec<(u64, Bucket)>,
+    /// Pass as `start_after` for the next page, `None` on the last page
+    pub next: Option<u64>,
+}
+
+#[cw_serde]
+pub struct OfferDetails {
+    pub offer: Offer,
+    /// Funds in the offered Bucket
+    pub funds: GenericBalance,
+}
+
+#[cw_serde]
+pub struct MultiOfferResponse {
+    pub offers: Vec<OfferDetails>,
+    /// Pass as `start_after` for the next page, `None` on the last page
+    pub next: Option<u64>,
+}
+
+#[cw_serde]
+pub struct MultiCollectionOfferResponse {
+    pub offers: Vec<CollectionOffer>,
+    /// Pass as `start_after` for the next page, `None` on the last page
+    pub next: Option<u64>,
+}
+
+/// Position in `GetListingsForMarket` & `GetMarketListings`
+#[cw_serde]
+pub struct MarketCursor {
+    /// Finalized or expiration time, whichever the query is sorted by
+    pub time: u64,
+    pub creator: String,
+    pub listing_id: u64,
+}
+
+#[cw_serde]
+pub struct MarketListingsResponse {
+    pub listings: Vec<Listing>,
+    /// Pass as `start_after` for the next page, `None` on the last page
+    pub next: Option<MarketCursor>,
+}
+
+/// Where an asset is held in the market
+#[cw_serde]
+pub enum AssetLocation {
+    /// For sale in a Listing, or purchased & waiting to be withdrawn if `Closed`
+    Listing {
+        listing_id: u64,
+        creator: Addr,
+        status: Status,
+        /// Listed by approval, the NFT is still in the creator's wallet
+        approved: bool,
+    },
+    Bucket {
+        bucket_id: u64,
+        owner: Addr,
+    },
+}
+
+#[cw_serde]
+pub struct NftLocationResponse {
+    /// `None` if the NFT is not in the market
+    pub location: Option<AssetLocation>,
+}
+
+#[cw_serde]
+pub struct OwnershipResponse {
+    /// `None` if ownership has been renounced
+    pub owner: Option<Addr>,
+    pub pending_owner: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub config: Config,
+    pub contract_name: String,
+    pub contract_version: String,
+}
+
+#[cw_serde]
+pub struct FeeRoutesResponse {
+    pub routes: Vec<FeeRoute>,
+}
+
+#[cw_serde]
+pub struct FeeLedgerResponse {
+    pub fees: Vec<FeeTotals>,
 }
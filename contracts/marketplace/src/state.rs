@@ -11,33 +11,414 @@ pub const LISTING_ID_USED: Map<u64, bool> = Map::new("listing_id_used");
 /// Keeps track of previously used bucket IDs
 pub const BUCKET_ID_USED: Map<u64, bool> = Map::new("bucket_id_used");
 
-pub const FEE_DENOM: Item<FeeDenom> = Item::new("fee_denom");
+/// Keeps track of previously used collection offer IDs
+pub const COLLECTION_OFFER_ID_USED: Map<u64, bool> = Map::new("collection_offer_id_used");
+
+/// Commitments on sealed bid auctions, keyed by (listing id, bidder)
+pub const SEALED_BIDS: Map<(u64, &Addr), SealedBid> = Map::new("sealed_bids");
+
+/// Commitments already submitted on a sealed bid auction, keyed by (listing id, commitment)
+pub const SEALED_BID_COMMITMENTS: Map<(u64, &[u8]), Addr> = Map::new("sealed_bid_commitments");
+
+/// How the deposits of a sealed bid auction are claimed, keyed by listing id
+/// - Kept after the auction is settled, until every deposit is claimed
+pub const SEALED_BID_TERMS: Map<u64, SealedBidTerms> = Map::new("sealed_bid_terms");
+
+pub const FEE_SCHEDULE: Item<FeeSchedule> = Item::new("fee_schedule");
+
+/// Where fees are sent, bps of all routes sum to 10_000
+pub const FEE_ROUTES: Item<Vec<FeeRoute>> = Item::new("fee_routes");
 
 pub const ROYALTY_REGISTRY: Item<Option<Addr>> = Item::new("royalty_regsitry");
 
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// Contract owner, `None` once ownership has been renounced
+/// - Can only manage contract parameters, never Listings or Buckets
+pub const OWNER: Item<Option<Addr>> = Item::new("owner");
+
+/// Address proposed by the owner, must call AcceptOwnership to become owner
+pub const PENDING_OWNER: Item<Option<Addr>> = Item::new("pending_owner");
+
+/// Version of the migration still running, only set while a paged migration is unfinished
+pub const MIGRATION_IN_PROGRESS: Item<String> = Item::new("migration_in_progress");
+
+/// Market parameters, updatable by the owner
+#[cw_serde]
+pub struct Config {
+    /// Shortest time a Listing can be finalized for (seconds)
+    pub min_expiration_seconds: u64,
+    /// Longest time a Listing can be finalized for (seconds)
+    pub max_expiration_seconds: u64,
+    /// Max number of Natives + CW20s + NFTs in a Listing, Bucket, or ask
+    /// - Avoids out of gas problems when withdrawing
+    pub max_num_assets: u32,
+    /// Max sum of royalties paid on one side of a sale (100 = 1%)
+    pub max_royalty_bps: u64,
+    /// Fee taken from the Fee Denom in a sale (100 = 1%)
+    pub fee_bps: u64,
+    /// Time between Fee Denom cycles (seconds)
+    pub fee_cycle_seconds: u64,
+    /// Paid to the caller of `ProcessExpired` per Listing returned,
+    /// out of pending fees in the Fee Ledger
+    pub expiry_bounty: Option<Coin>,
+    /// Highest flat fee the fee schedule can charge, in the flat fee's asset
+    pub max_flat_fee: Uint128,
+    /// Max commitments escrowed at once on a sealed bid auction
+    pub max_sealed_bids: u32,
+}
+
+/// Highest fee the owner is able to set (10%)
+pub const MAX_FEE_BPS: u64 = 1_000;
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            // 10 minutes
+            min_expiration_seconds: 600,
+            // 14 days
+            max_expiration_seconds: 1_209_600,
+            max_num_assets: 25,
+            // 50%
+            max_royalty_bps: 5_000,
+            // 0.5%
+            fee_bps: 50,
+            // 1 week
+            fee_cycle_seconds: 604_800,
+            expiry_bounty: None,
+            // 1 JUNO
+            max_flat_fee: Uint128::new(1_000_000),
+            max_sealed_bids: 100,
+        }
+    }
+}
+
+impl Config {
+    /// Errors if any are true:
+    /// - min_expiration_seconds is 0 or greater than max_expiration_seconds
+    /// - max_num_assets is 0
+    /// - max_royalty_bps is over 10_000 (100%)
+    /// - fee_bps is over MAX_FEE_BPS
+    /// - fee_cycle_seconds is 0
+    /// - expiry_bounty amount is 0
+    /// - max_sealed_bids is 0
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.min_expiration_seconds == 0 || self.min_expiration_seconds > self.max_expiration_seconds {
+            return Err(ContractError::InvalidConfig(
+                "Expiration bounds must be non zero and min <= max".to_string(),
+            ));
+        }
+
+        if self.max_num_assets == 0 {
+            return Err(ContractError::InvalidConfig("max_num_assets cannot be 0".to_string()));
+        }
+
+        if self.max_royalty_bps > 10_000 {
+            return Err(ContractError::InvalidConfig("max_royalty_bps cannot be over 10000".to_string()));
+        }
+
+        if self.fee_bps > MAX_FEE_BPS {
+            return Err(ContractError::InvalidConfig(format!("fee_bps cannot be over {MAX_FEE_BPS}")));
+        }
+
+        if self.fee_cycle_seconds == 0 {
+            return Err(ContractError::InvalidConfig("fee_cycle_seconds cannot be 0".to_string()));
+        }
+
+        if self.expiry_bounty.as_ref().is_some_and(|bounty| bounty.amount.is_zero()) {
+            return Err(ContractError::InvalidConfig("expiry_bounty amount cannot be 0".to_string()));
+        }
+
+        if self.max_sealed_bids == 0 {
+            return Err(ContractError::InvalidConfig("max_sealed_bids cannot be 0".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Max number of denoms in the fee schedule
+pub const MAX_FEE_DENOMS: usize = 10;
+
+/// An asset fees can be charged in
+#[cw_serde]
+pub enum FeeAsset {
+    Native(String),
+    Cw20(Addr),
+}
+
+impl FeeAsset {
+    /// Native denom or CW20 contract address
+    pub fn key(&self) -> &str {
+        match self {
+            FeeAsset::Native(denom) => denom,
+            FeeAsset::Cw20(address) => address.as_str(),
+        }
+    }
+
+    /// Amount of this asset in `balance`, `None` if it isn't there
+    pub fn amount_in(&self, balance: &GenericBalance) -> Option<Uint128> {
+        match self {
+            FeeAsset::Native(denom) => balance.native.iter().find(|n| &n.denom == denom).map(|n| n.amount),
+            FeeAsset::Cw20(address) => balance.cw20.iter().find(|c| c.address == address).map(|c| c.amount),
+        }
+    }
+}
+
+/// An amount of a fee asset
+#[cw_serde]
+pub struct FeeCoin {
+    pub asset: FeeAsset,
+    pub amount: Uint128,
+}
+
+impl FeeCoin {
+    pub fn new(amount: impl Into<Uint128>, asset: FeeAsset) -> Self {
+        FeeCoin {
+            asset,
+            amount: amount.into(),
+        }
+    }
+
+    /// This amount as a `GenericBalance`
+    pub fn to_balance(&self) -> GenericBalance {
+        let (native, cw20) = match &self.asset {
+            FeeAsset::Native(denom) => (vec![coin(self.amount.u128(), denom)], vec![]),
+            FeeAsset::Cw20(address) => (
+                vec![],
+                vec![Cw20CoinVerified {
+                    address: address.clone(),
+                    amount: self.amount,
+                }],
+            ),
+        };
+
+        GenericBalance {
+            native,
+            cw20,
+            nfts: vec![],
+        }
+    }
+}
+
+impl From<Coin> for FeeCoin {
+    fn from(native: Coin) -> Self {
+        FeeCoin::new(native.amount, FeeAsset::Native(native.denom))
+    }
+}
+
+impl std::fmt::Display for FeeCoin {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}{}", self.amount, self.asset.key())
+    }
+}
+
+/// A native denom or CW20 that fees can be charged in
 #[cw_serde]
-pub enum FeeDenom {
-    JUNO(u64),
-    USDC(u64),
+pub struct FeeDenom {
+    pub asset: FeeAsset,
+    /// Fee rate for this denom (100 = 1%), `None` uses `Config.fee_bps`
+    pub fee_bps: Option<u64>,
 }
 
 impl FeeDenom {
-    pub fn value(&self) -> String {
-        match *self {
-            FeeDenom::JUNO(_) => "ujunox".to_string(),
-            //FeeDenom::JUNO => "ujuno".to_string(),
-            FeeDenom::USDC(_) => "uusdcx".to_string(),
-            //FeeDenom::USDC => "ibc/EAC38D55372F38F1AFD68DF7FE9EF762DCF69F26520643CF3F9D292A738D8034".to_string()
+    /// Fee rate charged for this denom
+    pub fn rate(&self, default_fee_bps: u64) -> u64 {
+        self.fee_bps.unwrap_or(default_fee_bps)
+    }
+}
+
+#[cw_serde]
+pub enum FeeMode {
+    /// One denom is charged at a time, `FeeCycle` moves to the
+    /// next denom every `Config.fee_cycle_seconds`
+    Rotation,
+    /// The first denom (in list order) found in a balance is charged
+    Priority,
+}
+
+/// Flat fee charged on every sale, in a native or a CW20
+/// - Purchases take it from the buyer, natives sent in & CW20s pulled through an allowance
+/// - Offers & auctions take it out of the payment
+#[cw_serde]
+pub struct FlatFee {
+    pub amount: FeeCoin,
+    pub mode: FlatFeeMode,
+}
+
+#[cw_serde]
+pub enum FlatFeeMode {
+    /// `amount` is charged on every sale
+    Always,
+    /// Only the part of `amount` not already covered by
+    /// percentage fees (in the same denom) is charged
+    Minimum,
+}
+
+impl FlatFee {
+    /// Amount due, given the percentage fees already taken
+    /// - `None` if nothing is due
+    pub fn due(&self, taken: &[&Option<FeeCoin>]) -> Option<FeeCoin> {
+        let amount = match self.mode {
+            FlatFeeMode::Always => self.amount.amount,
+            FlatFeeMode::Minimum => taken
+                .iter()
+                .filter_map(|fee| fee.as_ref())
+                .filter(|fee| fee.asset == self.amount.asset)
+                .fold(self.amount.amount, |due, fee| due.saturating_sub(fee.amount)),
+        };
+
+        (!amount.is_zero()).then(|| FeeCoin::new(amount, self.amount.asset.clone()))
+    }
+}
+
+/// Denoms that fees are charged in
+/// - An empty list means no percentage fees are charged
+#[cw_serde]
+pub struct FeeSchedule {
+    pub denoms: Vec<FeeDenom>,
+    pub mode: FeeMode,
+    /// Index of the denom currently charged (Rotation only)
+    pub current: u32,
+    /// Time the schedule was created or last rotated (seconds)
+    pub last_rotation: u64,
+    /// If the denom(s) normally charged aren't in a balance, charge the
+    /// first of `denoms` (list order) found in it instead, then any native,
+    /// then any CW20
+    pub any_fungible: bool,
+    /// Charged on top of percentage fees
+    pub flat_fee: Option<FlatFee>,
+}
+
+impl FeeSchedule {
+    /// Errors if any are true:
+    /// - Over MAX_FEE_DENOMS denoms
+    /// - Any denom is empty or duplicated
+    /// - Any fee_bps is over MAX_FEE_BPS
+    /// - Flat fee has an empty denom or a zero amount
+    /// - Flat fee is over `max_flat_fee` (`Config.max_flat_fee`)
+    pub fn new(
+        denoms: Vec<FeeDenom>,
+        mode: FeeMode,
+        any_fungible: bool,
+        flat_fee: Option<FlatFee>,
+        max_flat_fee: Uint128,
+        now: u64,
+    ) -> Result<Self, ContractError> {
+        if denoms.len() > MAX_FEE_DENOMS {
+            return Err(ContractError::GenericError(format!(
+                "Fee schedule cannot contain over {MAX_FEE_DENOMS} denoms"
+            )));
+        }
+
+        if denoms.iter().any(|d| d.asset.key().is_empty()) {
+            return Err(ContractError::GenericError("Fee denom cannot be empty".to_string()));
+        }
+
+        let dd = denoms.iter().map(|d| d.asset.key().to_string()).collect::<BTreeSet<String>>();
+        if dd.len() != denoms.len() {
+            return Err(ContractError::GenericError("Cannot contain duplicate fee denoms".to_string()));
+        }
+
+        if denoms.iter().any(|d| d.fee_bps.is_some_and(|bps| bps > MAX_FEE_BPS)) {
+            return Err(ContractError::GenericError(format!(
+                "Fee denom fee_bps cannot be over {MAX_FEE_BPS}"
+            )));
+        }
+
+        if flat_fee.as_ref().is_some_and(|f| f.amount.asset.key().is_empty() || f.amount.amount.is_zero()) {
+            return Err(ContractError::GenericError(
+                "Flat fee must have a denom and an amount over 0".to_string(),
+            ));
+        }
+
+        if flat_fee.as_ref().is_some_and(|f| f.amount.amount > max_flat_fee) {
+            return Err(ContractError::GenericError(format!(
+                "Flat fee cannot be over {max_flat_fee}"
+            )));
+        }
+
+        Ok(FeeSchedule {
+            denoms,
+            mode,
+            current: 0,
+            last_rotation: now,
+            any_fungible,
+            flat_fee,
+        })
+    }
+
+    /// The denom currently being charged
+    /// - Rotation: the current denom
+    /// - Priority: the highest priority denom
+    pub fn current(&self) -> Option<&FeeDenom> {
+        match self.mode {
+            FeeMode::Rotation => self.denoms.get(self.current as usize),
+            FeeMode::Priority => self.denoms.first(),
+        }
+    }
+
+    /// The denom charged on `balance`, if any
+    /// - With `any_fungible`, falls back to the first of `denoms` in `balance`,
+    ///   then the first native, then the first CW20 in `balance`
+    ///   (charged at `Config.fee_bps`)
+    pub fn fee_denom_for(&self, balance: &GenericBalance) -> Option<FeeDenom> {
+        let in_balance = |d: &&FeeDenom| d.asset.amount_in(balance).is_some();
+
+        let scheduled = match self.mode {
+            FeeMode::Rotation => self.current().filter(in_balance),
+            FeeMode::Priority => self.denoms.iter().find(in_balance),
+        };
+
+        if scheduled.is_some() || !self.any_fungible {
+            return scheduled.cloned();
         }
+
+        let any_native = balance.native.first().map(|n| FeeAsset::Native(n.denom.clone()));
+        let any_cw20 = balance.cw20.first().map(|c| FeeAsset::Cw20(c.address.clone()));
+
+        self.denoms.iter().find(in_balance).cloned().or_else(|| {
+            any_native.or(any_cw20).map(|asset| FeeDenom {
+                asset,
+                fee_bps: None,
+            })
+        })
     }
 
-    // pub fn create(new: String, time: u64) -> Result<Self, ContractError> {
-    //     match new.as_str() {
-    //         "JUNO" => Ok(FeeDenom::JUNO(time)),
-    //         "USDC" => Ok(FeeDenom::USDC(time)),
-    //         x => Err(ContractError::GenericError(format!("Invalid Fee Denom: {x}"))),
-    //     }
-    // }
+    /// True if fees can be charged in a CW20, including the flat fee
+    pub fn takes_cw20(&self) -> bool {
+        self.any_fungible
+            || self.denoms.iter().any(|d| matches!(d.asset, FeeAsset::Cw20(_)))
+            || self.flat_fee.as_ref().is_some_and(|f| matches!(f.amount.asset, FeeAsset::Cw20(_)))
+    }
+
+    /// CW20s can't be sent to the Community Pool, so errors if
+    /// fees can be charged in a CW20 & any route is to the Community Pool
+    pub fn check_routes(&self, routes: &[FeeRoute]) -> Result<(), ContractError> {
+        if self.takes_cw20() && routes.iter().any(|r| matches!(r.destination, FeeDestination::CommunityPool(_))) {
+            return Err(ContractError::GenericError(
+                "CW20 fees cannot be routed to the Community Pool".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Only Rotation schedules with 2 or more denoms rotate
+    pub fn rotates(&self) -> bool {
+        self.mode == FeeMode::Rotation && self.denoms.len() > 1
+    }
+
+    /// When `FeeCycle` can be called next (seconds)
+    pub fn next_rotation(&self, fee_cycle_seconds: u64) -> Option<u64> {
+        self.rotates().then(|| self.last_rotation.saturating_add(fee_cycle_seconds))
+    }
+
+    /// Moves to the next denom in the list, wrapping to the start
+    pub fn rotate(&mut self, now: u64) {
+        self.current = (self.current + 1) % self.denoms.len().max(1) as u32;
+        self.last_rotation = now;
+    }
 }
 
 //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -48,14 +429,36 @@ impl FeeDenom {
 pub struct ListingIndexes<'a> {
     pub id: UniqueIndex<'a, u64, Listing, (&'a Addr, u64)>,
     pub finalized_date: MultiIndex<'a, u64, Listing, (&'a Addr, u64)>,
+    pub expiration_date: MultiIndex<'a, u64, Listing, (&'a Addr, u64)>,
+    // Expiration of Listings `ProcessExpired` can return, 0 for all others
+    pub returnable: MultiIndex<'a, u64, Listing, (&'a Addr, u64)>,
     // (whitelisted_buyer/default, listing_id as u64)  |
     pub whitelisted_buyer: UniqueIndex<'a, (String, u64), Listing, (&'a Addr, u64)>,
+    // Assets in `for_sale`
+    pub nft: AssetIndex<'a, (Addr, String), Listing>,
+    pub collection: AssetIndex<'a, Addr, Listing>,
+    pub denom: AssetIndex<'a, String, Listing>,
+    // Assets in `ask`
+    pub ask_nft: AssetIndex<'a, (Addr, String), Listing>,
+    pub ask_collection: AssetIndex<'a, Addr, Listing>,
+    pub ask_denom: AssetIndex<'a, String, Listing>,
 }
 
 impl IndexList<Listing> for ListingIndexes<'_> {
     fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Listing>> + '_> {
-        let v: Vec<&dyn Index<Listing>> =
-            vec![&self.id, &self.finalized_date, &self.whitelisted_buyer];
+        let v: Vec<&dyn Index<Listing>> = vec![
+            &self.id,
+            &self.finalized_date,
+            &self.expiration_date,
+            &self.returnable,
+            &self.whitelisted_buyer,
+            &self.nft,
+            &self.collection,
+            &self.denom,
+            &self.ask_nft,
+            &self.ask_collection,
+            &self.ask_denom,
+        ];
         Box::new(v.into_iter())
     }
 }
@@ -69,6 +472,21 @@ pub fn listingz<'a>() -> IndexedMap<'a, (&'a Addr, u64), Listing, ListingIndexes
             "listings_im",
             "listing__finalized__date",
         ),
+        expiration_date: MultiIndex::new(
+            |_pk, a_listing| a_listing.expiration_time.map_or(0_u64, |x| x.seconds()),
+            "listings_im",
+            "listing__expiration__date",
+        ),
+        returnable: MultiIndex::new(
+            |_pk, a_listing| {
+                if a_listing.claimant.is_some() || a_listing.has_bid() {
+                    return 0;
+                }
+                a_listing.expiration_time.map_or(0_u64, |x| x.seconds())
+            },
+            "listings_im",
+            "listing__returnable",
+        ),
         whitelisted_buyer: UniqueIndex::new(
             |listing| {
                 (
@@ -81,6 +499,36 @@ pub fn listingz<'a>() -> IndexedMap<'a, (&'a Addr, u64), Listing, ListingIndexes
             },
             "listing__whitelisted__buyer",
         ),
+        nft: AssetIndex::new(
+            |listing| listing.for_sale.nft_keys(),
+            |listing| listing.id,
+            "listing__nft",
+        ),
+        collection: AssetIndex::new(
+            |listing| listing.for_sale.collections(),
+            |listing| listing.id,
+            "listing__collection",
+        ),
+        denom: AssetIndex::new(
+            |listing| listing.for_sale.denoms(),
+            |listing| listing.id,
+            "listing__denom",
+        ),
+        ask_nft: AssetIndex::new(
+            |listing| listing.ask.nft_keys(),
+            |listing| listing.id,
+            "listing__ask__nft",
+        ),
+        ask_collection: AssetIndex::new(
+            |listing| listing.ask.collections(),
+            |listing| listing.id,
+            "listing__ask__collection",
+        ),
+        ask_denom: AssetIndex::new(
+            |listing| listing.ask.denoms(),
+            |listing| listing.id,
+            "listing__ask__denom",
+        ),
     };
 
     IndexedMap::new("listings_im", indexes)
@@ -100,41 +548,312 @@ pub struct Listing {
 
     pub for_sale: GenericBalance,
     pub ask: GenericBalance,
-
-    pub fee_amount: Option<Coin>,
+    /// Set if `for_sale` can be bought in pieces
+    pub partial_fill: Option<PartialFill>,
+    /// Set if `for_sale` is auctioned instead of sold at `ask`
+    pub auction: Option<Auction>,
+    /// NFTs in `for_sale` left in the creator's wallet under an `Approve` to this contract,
+    /// pulled in when the Listing sells
+    pub approved_nfts: Option<Vec<Nft>>,
+    /// Flat fee charged on the winning bid of an English or sealed bid auction,
+    /// from the fee schedule when the auction was finalized
+    pub flat_fee: Option<FlatFee>,
+
+    pub fee_amount: Option<FeeCoin>,
 }
 
 impl Listing {
-    /// **If `Listing.fee_amount.is_some()`**
-    /// - Returns `Vec<CosmosMsg>` sending `Listing.fee_amount` to Com. Pool + `Listing.for_sale` to `Listing.claimant`
-    ///
-    /// **If `Listing.fee_amount.is_none()`**
-    /// - Returns `Vec<CosmosMsg>` sending `Listing.for_sale` to `Listing.claimant`
+    /// `ask`, or for Dutch auctions the price at `now` (the start price until finalized)
+    pub fn current_ask(&self, now: Timestamp) -> GenericBalance {
+        let (Some(Auction::Dutch(dutch)), Some(start_time), Some(end_time)) =
+            (&self.auction, self.finalized_time, self.expiration_time)
+        else {
+            return self.ask.clone();
+        };
+        let Some(start) = self.ask.single_fungible() else {
+            return self.ask.clone();
+        };
+
+        self.ask.with_amount(dutch.price(start, start_time, end_time, now))
+    }
+
+    /// True if this is an auction with a bid placed
+    pub fn has_bid(&self) -> bool {
+        self.auction.as_ref().is_some_and(Auction::has_bids)
+    }
+
+    /// NFTs in `for_sale` listed by approval instead of escrowed
+    pub fn approved(&self) -> &[Nft] {
+        self.approved_nfts.as_deref().unwrap_or_default()
+    }
+
+    /// `for_sale` less the NFTs listed by approval, ie what this contract holds
+    pub fn escrowed(&self) -> GenericBalance {
+        let mut escrowed = self.for_sale.clone();
+        escrowed.nfts.retain(|nft| !self.approved().contains(nft));
+        escrowed
+    }
+
+    /// Errors if the partial fill or auction settings are invalid, or both are set
+    /// - English & sealed bid auctions can't list NFTs by approval, a bid could
+    ///   then be stuck if the NFT is moved
+    pub fn check_mode(&self) -> Result<(), ContractError> {
+        if !self.approved().is_empty()
+            && matches!(self.auction, Some(Auction::English(_) | Auction::SealedBid(_)))
+        {
+            return Err(ContractError::GenericError(
+                "English & sealed bid auctions cannot list NFTs by approval".to_string(),
+            ));
+        }
+
+        match (&self.partial_fill, &self.auction) {
+            (Some(_), Some(_)) => Err(ContractError::GenericError(
+                "Listing cannot be both partially fillable & auctioned".to_string(),
+            )),
+            (Some(partial_fill), None) => partial_fill.check_valid(&self.for_sale, &self.ask),
+            (None, Some(auction)) => auction.check_valid(&self.ask),
+            (None, None) => Ok(()),
+        }
+    }
+
+    /// Returns `Vec<CosmosMsg>` sending `Listing.for_sale` to `destination`, or `Listing.claimant`
+    /// - `Listing.fee_amount` is already in the Fee Ledger, it is not sent here
     #[cfg(not(tarpaulin_include))]
-    pub fn withdraw_msgs(&self, contract_addr: Addr) -> Result<Vec<CosmosMsg>, ContractError> {
+    pub fn withdraw_msgs(
+        &self,
+        destination: Option<&WithdrawDestination>,
+    ) -> Result<Vec<CosmosMsg>, ContractError> {
         // Get claimant (This will not called when Listing does not have claimant)
         let user = self.claimant.as_ref().ok_or_else(|| {
             ContractError::GenericError("Listing has not been purchased".to_string())
         })?;
 
-        match &self.fee_amount {
-            // No fee amount, send for_sale to user
-            None => send_tokens_cosmos(user, &self.for_sale).map_err(|_e| {
+        destination
+            .map_or_else(
+                || send_tokens_cosmos(user, &self.for_sale),
+                |destination| destination.msgs(&self.for_sale),
+            )
+            .map_err(|_e| {
                 ContractError::GenericError("Error creating withdraw messages".to_string())
-            }),
-            // Some fee amount, send fee to CP & for_sale to user
-            Some(fee) => {
-                let mut user_msgs = send_tokens_cosmos(user, &self.for_sale).map_err(|_e| {
-                    ContractError::GenericError("Error creating withdraw messages".to_string())
-                })?;
-                let fee_msg = fee.get_cp_msg(contract_addr)?;
-                user_msgs.push(fee_msg);
-                Ok(user_msgs)
+            })
+    }
+}
+
+/// Lets a Listing be bought in pieces
+/// - `Listing.for_sale` & `Listing.ask` must each be a single Native or CW20
+/// - `Listing.ask` is the price of `unit` of `Listing.for_sale`
+/// - Each purchase must be at least `min_fill`, unless it takes the rest
+#[cw_serde]
+pub struct PartialFill {
+    pub unit: Uint128,
+    pub min_fill: Uint128,
+}
+
+impl PartialFill {
+    /// Errors if `unit` is 0, or `for_sale` or `ask` isn't a single Native or CW20
+    pub fn check_valid(
+        &self,
+        for_sale: &GenericBalance,
+        ask: &GenericBalance,
+    ) -> Result<(), ContractError> {
+        if self.unit.is_zero() {
+            return Err(ContractError::GenericError("Partial fill unit cannot be 0".to_string()));
+        }
+
+        if for_sale.single_fungible().is_none() || ask.single_fungible().is_none() {
+            return Err(ContractError::GenericError(
+                "Partial fill Listings must sell & ask for a single Native or CW20".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Price of `quantity` of the Listing, `ask` scaled by `quantity / unit` & rounded up
+    pub fn price(&self, ask: &GenericBalance, quantity: Uint128) -> Result<GenericBalance, ContractError> {
+        let Some(ask_amount) = ask.single_fungible() else {
+            return Err(ContractError::GenericError("Invalid partial fill ask".to_string()));
+        };
+
+        let unit = Uint256::from(self.unit);
+        let price = (ask_amount.full_mul(quantity) + unit - Uint256::one()) / unit;
+
+        let price = Uint128::try_from(price)
+            .map_err(|_e| ContractError::GenericError("Partial fill price overflow".to_string()))?;
+
+        Ok(ask.with_amount(price))
+    }
+}
+
+/// How an auctioned Listing is sold
+#[cw_serde]
+pub enum Auction {
+    English(EnglishAuction),
+    Dutch(DutchAuction),
+    SealedBid(SealedBidAuction),
+}
+
+impl Auction {
+    /// Errors unless `ask` is a single Native or CW20 & the settings are valid
+    pub fn check_valid(&self, ask: &GenericBalance) -> Result<(), ContractError> {
+        if ask.single_fungible().is_none() {
+            return Err(ContractError::GenericError(
+                "Auctions must ask for a single Native or CW20".to_string(),
+            ));
+        }
+
+        match self {
+            Auction::English(english) => {
+                if english.min_increment.is_zero() {
+                    return Err(ContractError::GenericError(
+                        "Minimum bid increment cannot be 0".to_string(),
+                    ));
+                }
+            }
+            Auction::Dutch(dutch) => {
+                if ask.single_fungible().is_some_and(|start| dutch.floor >= start) {
+                    return Err(ContractError::GenericError(
+                        "Floor price must be below the start price".to_string(),
+                    ));
+                }
+                if dutch.step_seconds == Some(0) {
+                    return Err(ContractError::GenericError("Step cannot be 0 seconds".to_string()));
+                }
             }
+            Auction::SealedBid(sealed) => {
+                if sealed.reveal_seconds == 0 {
+                    return Err(ContractError::GenericError(
+                        "Reveal phase cannot be 0 seconds".to_string(),
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// True if funds are escrowed as bids, the auction must be settled
+    pub fn has_bids(&self) -> bool {
+        match self {
+            Auction::English(english) => english.highest_bid.is_some(),
+            Auction::Dutch(_) => false,
+            Auction::SealedBid(sealed) => sealed.commits > 0,
+        }
+    }
+}
+
+/// Open ascending auction, the highest bid when the Listing expires wins
+/// - `Listing.ask` (a single Native or CW20) is the reserve price
+/// - Each bid must beat the highest by at least `min_increment`
+/// - A bid in the last `extension_seconds` pushes `Listing.expiration_time`
+///   back to `extension_seconds` after that bid
+#[cw_serde]
+pub struct EnglishAuction {
+    pub min_increment: Uint128,
+    pub extension_seconds: u64,
+    /// Escrowed until outbid (refunded) or the auction is settled
+    pub highest_bid: Option<Bid>,
+}
+
+/// Descending auction, bought outright with `BuyListing` at the current price
+/// - `Listing.ask` (a single Native or CW20) is the start price
+/// - Price drops from the start price at `Listing.finalized_time` to `floor`
+///   at `Listing.expiration_time`
+/// - Drops continuously, or once every `step_seconds` if set
+#[cw_serde]
+pub struct DutchAuction {
+    pub floor: Uint128,
+    pub step_seconds: Option<u64>,
+}
+
+impl DutchAuction {
+    /// Price at `now` for an auction running from `start_time` to `end_time`,
+    /// rounded up
+    pub fn price(
+        &self,
+        start: Uint128,
+        start_time: Timestamp,
+        end_time: Timestamp,
+        now: Timestamp,
+    ) -> Uint128 {
+        let duration = end_time.seconds().saturating_sub(start_time.seconds());
+        if duration == 0 {
+            return start;
         }
+
+        let mut elapsed =
+            now.seconds().clamp(start_time.seconds(), end_time.seconds()) - start_time.seconds();
+        // Steps down to the last whole step, reaching the floor at the end
+        if let Some(step) = self.step_seconds.filter(|step| *step > 0 && elapsed < duration) {
+            elapsed -= elapsed % step;
+        }
+
+        start - (start - self.floor).multiply_ratio(elapsed, duration)
     }
 }
 
+/// Commit-reveal auction, bids are hidden until the bid phase ends
+/// - `Listing.ask` (a single Native or CW20) is the reserve price
+/// - Bid phase: until `Listing.expiration_time`, bidders commit a hash
+///   of their bid with a deposit of at least the reserve
+/// - Reveal phase: the next `reveal_seconds`, bidders reveal their bid,
+///   which must be no more than their deposit
+/// - The highest reveal wins (earliest on ties), other revealed deposits are refunded
+/// - Unrevealed deposits are claimed with `ClaimDeposit` after the reveal phase, they go
+///   to the seller if `forfeit_unrevealed`, otherwise they're refunded
+#[cw_serde]
+pub struct SealedBidAuction {
+    pub reveal_seconds: u64,
+    pub forfeit_unrevealed: bool,
+    /// Number of commitments still escrowed
+    pub commits: u32,
+    pub highest_bid: Option<Bid>,
+}
+
+impl SealedBidAuction {
+    /// `sha256(listing_id as 8 big-endian bytes ++ bidder length as 4 big-endian bytes
+    /// ++ bidder ++ amount as 16 big-endian bytes ++ salt)`
+    /// - Listing id & bidder are hashed in so a commitment can't be copied
+    ///   by another bidder or onto another Listing
+    pub fn commitment(listing_id: u64, bidder: &Addr, amount: Uint128, salt: &str) -> Binary {
+        let mut hasher = Sha256::new();
+        hasher.update(listing_id.to_be_bytes());
+        hasher.update((bidder.as_str().len() as u32).to_be_bytes());
+        hasher.update(bidder.as_bytes());
+        hasher.update(amount.u128().to_be_bytes());
+        hasher.update(salt.as_bytes());
+        Binary::from(hasher.finalize().as_slice())
+    }
+}
+
+/// A bidder's commitment on a sealed bid auction
+#[cw_serde]
+pub struct SealedBid {
+    pub bidder: Addr,
+    pub commitment: Binary,
+    /// Amount of the ask asset escrowed
+    pub deposit: Uint128,
+    pub revealed: bool,
+}
+
+/// What `ClaimDeposit` needs once a sealed bid auction's Listing is settled & removed
+#[cw_serde]
+pub struct SealedBidTerms {
+    pub seller: Addr,
+    /// Deposits are amounts of this (the Listing's ask)
+    pub ask: GenericBalance,
+    pub forfeit_unrevealed: bool,
+    /// Deposits can be claimed after this
+    pub reveal_end: Timestamp,
+}
+
+/// An amount of the auctioned Listing's ask asset
+#[cw_serde]
+pub struct Bid {
+    pub bidder: Addr,
+    pub amount: Uint128,
+}
+
 #[cw_serde]
 pub enum Status {
     BeingPrepared,
@@ -147,36 +866,243 @@ pub enum Status {
 // Buckets
 //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-pub const BUCKETS: Map<(Addr, u64), Bucket> = Map::new("buckets");
+pub struct BucketIndexes<'a> {
+    pub id: UniqueIndex<'a, u64, Bucket, (Addr, u64)>,
+    // Assets in `funds`
+    pub nft: AssetIndex<'a, (Addr, String), Bucket>,
+    pub collection: AssetIndex<'a, Addr, Bucket>,
+    pub denom: AssetIndex<'a, String, Bucket>,
+}
+
+impl IndexList<Bucket> for BucketIndexes<'_> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Bucket>> + '_> {
+        let v: Vec<&dyn Index<Bucket>> = vec![&self.id, &self.nft, &self.collection, &self.denom];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Buckets keyed by (owner, bucket_id), indexed by bucket_id & the assets in them
+#[must_use]
+pub fn bucketz<'a>() -> IndexedMap<'a, (Addr, u64), Bucket, BucketIndexes<'a>> {
+    let indexes = BucketIndexes {
+        id: UniqueIndex::new(|a_bucket| a_bucket.id, "bucket__id"),
+        nft: AssetIndex::new(|bucket| bucket.funds.nft_keys(), |bucket| bucket.id, "bucket__nft"),
+        collection: AssetIndex::new(
+            |bucket| bucket.funds.collections(),
+            |bucket| bucket.id,
+            "bucket__collection",
+        ),
+        denom: AssetIndex::new(|bucket| bucket.funds.denoms(), |bucket| bucket.id, "bucket__denom"),
+    };
+
+    IndexedMap::new("buckets", indexes)
+}
 
 #[cw_serde]
 pub struct Bucket {
     pub owner: Addr,
+    pub id: u64,
     pub funds: GenericBalance,
-    pub fee_amount: Option<Coin>,
+    pub fee_amount: Option<FeeCoin>,
 }
 
 impl Bucket {
-    /// **If `Bucket.fee_amount.is_some()`**
-    /// - Returns `Vec<CosmosMsg>` sending `Bucket.fee_amount` to Com. Pool + `Bucket.funds` to `Bucket.owner`
-    ///
-    /// **If `Bucket.fee_amount.is_none()`**
-    /// - Returns `Vec<CosmosMsg>` sending `Bucket.funds` to `Bucket.owner`
+    /// Returns `Vec<CosmosMsg>` sending `Bucket.funds` to `destination`, or `Bucket.owner`
+    /// - `Bucket.fee_amount` is already in the Fee Ledger, it is not sent here
     #[cfg(not(tarpaulin_include))]
-    pub fn withdraw_msgs(&self, contract_addr: Addr) -> Result<Vec<CosmosMsg>, ContractError> {
-        match &self.fee_amount {
-            None => send_tokens_cosmos(&self.owner, &self.funds).map_err(|_e| {
+    pub fn withdraw_msgs(
+        &self,
+        destination: Option<&WithdrawDestination>,
+    ) -> Result<Vec<CosmosMsg>, ContractError> {
+        destination
+            .map_or_else(
+                || send_tokens_cosmos(&self.owner, &self.funds),
+                |destination| destination.msgs(&self.funds),
+            )
+            .map_err(|_e| {
                 ContractError::GenericError("Error creating withdraw messages".to_string())
-            }),
-            Some(fee) => {
-                let mut user_msgs = send_tokens_cosmos(&self.owner, &self.funds).map_err(|_e| {
-                    ContractError::GenericError("Error creating withdraw messages".to_string())
-                })?;
-                let fee_msg = fee.get_cp_msg(contract_addr)?;
-                user_msgs.push(fee_msg);
-                Ok(user_msgs)
-            }
+            })
+    }
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Offers
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+pub struct OfferIndexes<'a> {
+    pub listing: MultiIndex<'a, u64, Offer, u64>,
+    pub bidder: MultiIndex<'a, Addr, Offer, u64>,
+}
+
+impl IndexList<Offer> for OfferIndexes<'_> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Offer>> + '_> {
+        let v: Vec<&dyn Index<Offer>> = vec![&self.listing, &self.bidder];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Offers keyed by the id of the Bucket they lock, indexed by listing & bidder
+#[must_use]
+pub fn offerz<'a>() -> IndexedMap<'a, u64, Offer, OfferIndexes<'a>> {
+    let indexes = OfferIndexes {
+        listing: MultiIndex::new(|_pk, offer| offer.listing_id, "offers", "offer__listing"),
+        bidder: MultiIndex::new(|_pk, offer| offer.bidder.clone(), "offers", "offer__bidder"),
+    };
+
+    IndexedMap::new("offers", indexes)
+}
+
+/// A Bucket offered for a Listing in place of its `ask`
+/// - The Bucket can't be changed, withdrawn or used to buy until the Offer is retracted
+#[cw_serde]
+pub struct Offer {
+    pub bidder: Addr,
+    pub bucket_id: u64,
+    pub listing_id: u64,
+    pub expiration_time: Timestamp,
+    /// Flat fee charged if accepted, from the fee schedule when the Offer was made
+    pub flat_fee: Option<FlatFee>,
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Collection Offers
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+pub struct CollectionOfferIndexes<'a> {
+    pub collection: MultiIndex<'a, Addr, CollectionOffer, u64>,
+    pub bidder: MultiIndex<'a, Addr, CollectionOffer, u64>,
+}
+
+impl IndexList<CollectionOffer> for CollectionOfferIndexes<'_> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<CollectionOffer>> + '_> {
+        let v: Vec<&dyn Index<CollectionOffer>> = vec![&self.collection, &self.bidder];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Collection Offers keyed by id, indexed by collection & bidder
+#[must_use]
+pub fn collection_offerz<'a>() -> IndexedMap<'a, u64, CollectionOffer, CollectionOfferIndexes<'a>> {
+    let indexes = CollectionOfferIndexes {
+        collection: MultiIndex::new(
+            |_pk, offer| offer.collection.clone(),
+            "collection_offers",
+            "collection_offer__collection",
+        ),
+        bidder: MultiIndex::new(
+            |_pk, offer| offer.bidder.clone(),
+            "collection_offers",
+            "collection_offer__bidder",
+        ),
+    };
+
+    IndexedMap::new("collection_offers", indexes)
+}
+
+/// A bid of `price` each for up to `quantity` NFTs from `collection`
+/// - `price` is escrowed for every NFT not yet filled
+/// - Removed once every NFT is filled or the bidder cancels
+#[cw_serde]
+pub struct CollectionOffer {
+    pub id: u64,
+    pub bidder: Addr,
+    pub collection: Addr,
+    /// Natives & CW20s paid per NFT
+    pub price: GenericBalance,
+    /// NFTs still wanted
+    pub quantity: u32,
+    /// NFTs already filled
+    pub filled: u32,
+    pub expiration_time: Timestamp,
+}
+
+impl CollectionOffer {
+    /// Funds still in escrow, `price` for each of `quantity`
+    pub fn escrow(&self) -> StdResult<GenericBalance> {
+        let quantity = Uint128::from(self.quantity);
+
+        Ok(GenericBalance {
+            native: self
+                .price
+                .native
+                .iter()
+                .map(|n| Ok(coin(n.amount.checked_mul(quantity)?.u128(), &n.denom)))
+                .collect::<StdResult<_>>()?,
+            cw20: self
+                .price
+                .cw20
+                .iter()
+                .map(|c| {
+                    Ok(Cw20CoinVerified {
+                        address: c.address.clone(),
+                        amount: c.amount.checked_mul(quantity)?,
+                    })
+                })
+                .collect::<StdResult<_>>()?,
+            nfts: vec![],
+        })
+    }
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Asset Index
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Indexes a Listing or Bucket under every asset it holds
+/// - `UniqueIndex` & `MultiIndex` only allow one key per record,
+///   a GenericBalance can hold any number of assets
+/// - Stored as `(asset key, record id)`, so records holding an asset are ranged by id
+pub struct AssetIndex<'a, IK, T> {
+    index: fn(&T) -> BTreeSet<IK>,
+    id: fn(&T) -> u64,
+    idx_map: Map<'a, (IK, u64), bool>,
+}
+
+impl<'a, IK, T> AssetIndex<'a, IK, T>
+where
+    IK: PrimaryKey<'a> + Prefixer<'a> + KeyDeserialize,
+{
+    pub const fn new(index: fn(&T) -> BTreeSet<IK>, id: fn(&T) -> u64, namespace: &'a str) -> Self {
+        AssetIndex {
+            index,
+            id,
+            idx_map: Map::new(namespace),
+        }
+    }
+
+    /// IDs of every record holding `key`, ascending
+    pub fn ids<'c>(
+        &self,
+        store: &'c dyn Storage,
+        key: IK,
+        start_after: Option<u64>,
+    ) -> Box<dyn Iterator<Item = StdResult<u64>> + 'c> {
+        self.idx_map.prefix(key).keys(store, start_after.map(Bound::exclusive), None, Order::Ascending)
+    }
+}
+
+impl<'a, IK, T> Index<T> for AssetIndex<'a, IK, T>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    IK: PrimaryKey<'a> + Prefixer<'a> + KeyDeserialize,
+{
+    fn save(&self, store: &mut dyn Storage, _pk: &[u8], data: &T) -> StdResult<()> {
+        let id = (self.id)(data);
+        for key in (self.index)(data) {
+            self.idx_map.save(store, (key, id), &true)?;
         }
+        Ok(())
+    }
+
+    fn remove(&self, store: &mut dyn Storage, _pk: &[u8], old_data: &T) -> StdResult<()> {
+        let id = (self.id)(old_data);
+        for key in (self.index)(old_data) {
+            self.idx_map.remove(store, (key, id));
+        }
+        Ok(())
     }
 }
 
@@ -199,13 +1125,116 @@ pub struct Nft {
     pub token_id: String,
 }
 
+impl Nft {
+    /// The unexpired approval `owner` has given `spender` for this NFT,
+    /// `None` if `owner` no longer holds it or the approval is missing
+    pub fn approval(
+        &self,
+        querier: &QuerierWrapper,
+        owner: &Addr,
+        spender: &Addr,
+    ) -> Option<Approval> {
+        let res: OwnerOfResponse = querier
+            .query_wasm_smart(
+                &self.contract_address,
+                &Cw721QueryMsg::OwnerOf {
+                    token_id: self.token_id.clone(),
+                    include_expired: None,
+                },
+            )
+            .ok()?;
+
+        if res.owner != *owner {
+            return None;
+        }
+
+        res.approvals.into_iter().find(|approval| approval.spender == *spender)
+    }
+}
+
 impl GenericBalance {
+    /// `(collection, token_id)` of every NFT
+    pub fn nft_keys(&self) -> BTreeSet<(Addr, String)> {
+        self.nfts.iter().map(|nft| (nft.contract_address.clone(), nft.token_id.clone())).collect()
+    }
+
+    /// Every NFT collection with at least one NFT
+    pub fn collections(&self) -> BTreeSet<Addr> {
+        self.nfts.iter().map(|nft| nft.contract_address.clone()).collect()
+    }
+
+    /// Every native denom & CW20 address
+    pub fn denoms(&self) -> BTreeSet<String> {
+        self.native
+            .iter()
+            .map(|c| c.denom.clone())
+            .chain(self.cw20.iter().map(|c| c.address.to_string()))
+            .collect()
+    }
+
+    /// Amount of the only asset, `None` unless this is a single Native or CW20
+    pub fn single_fungible(&self) -> Option<Uint128> {
+        match (self.native.as_slice(), self.cw20.as_slice(), self.nfts.is_empty()) {
+            ([native], [], true) => Some(native.amount),
+            ([], [cw20], true) => Some(cw20.amount),
+            _ => None,
+        }
+    }
+
+    /// What's left after taking out `other`, emptied Natives & CW20s are dropped
+    /// - Errors if `other` isn't all in this balance
+    pub fn checked_sub(&self, other: &GenericBalance) -> Result<GenericBalance, ContractError> {
+        let short = || ContractError::GenericError("Funds sent do not cover the price".to_string());
+        let mut left = self.clone();
+
+        for sub in &other.native {
+            let n = left.native.iter().position(|n| n.denom == sub.denom).ok_or_else(short)?;
+            left.native[n].amount =
+                left.native[n].amount.checked_sub(sub.amount).map_err(|_e| short())?;
+        }
+        for sub in &other.cw20 {
+            let c = left.cw20.iter().position(|c| c.address == sub.address).ok_or_else(short)?;
+            left.cw20[c].amount =
+                left.cw20[c].amount.checked_sub(sub.amount).map_err(|_e| short())?;
+        }
+        for sub in &other.nfts {
+            let n = left.nfts.iter().position(|n| n == sub).ok_or_else(short)?;
+            left.nfts.remove(n);
+        }
+
+        left.native.retain(|n| !n.amount.is_zero());
+        left.cw20.retain(|c| !c.amount.is_zero());
+
+        Ok(left)
+    }
+
+    /// The same assets with every Native & CW20 amount set to `amount`
+    pub fn with_amount(&self, amount: Uint128) -> GenericBalance {
+        GenericBalance {
+            native: self.native.iter().map(|n| coin(amount.u128(), &n.denom)).collect(),
+            cw20: self
+                .cw20
+                .iter()
+                .map(|c| Cw20CoinVerified {
+                    address: c.address.clone(),
+                    amount,
+                })
+                .collect(),
+            nfts: self.nfts.clone(),
+        }
+    }
+
     /// Generate messages for sending `Cw20Cw721ExecuteMsg::Send` variants
     /// This can be used if the withdrawing contracts wants to invoke some
     /// action on their contract when the cw20/cw721 messages are received
-    /// **Currently unused, need to make a way for contracts to specify this
-    /// when withdrawing**
-    pub fn contract_msgs(&self, to: &Addr) -> StdResult<Vec<CosmosMsg>> {
+    /// - `cw20_msg` / `nft_msg` are the contract's receive payloads,
+    ///   CW20s / NFTs are transferred without one if `None`
+    pub fn contract_msgs(
+        &self,
+        to: &Addr,
+        cw20_msg: Option<&Binary>,
+        nft_msg: Option<&Binary>,
+    ) -> StdResult<Vec<CosmosMsg>> {
         let mut msgs: Vec<CosmosMsg> = if self.native.is_empty() {
             vec![]
         } else {
@@ -219,11 +1248,17 @@ impl GenericBalance {
             .cw20
             .iter()
             .map(|c| {
-                // Contract must implement the cw20 receiver interface, replace x with contract specific message variant
-                let msg = Cw20ExecuteMsg::Send {
-                    contract: to.into(),
-                    amount: c.amount,
-                    msg: to_binary("x")?,
+                // Contract must implement the cw20 receiver interface to take a hook msg
+                let msg = match cw20_msg {
+                    Some(hook) => Cw20ExecuteMsg::Send {
+                        contract: to.into(),
+                        amount: c.amount,
+                        msg: hook.clone(),
+                    },
+                    None => Cw20ExecuteMsg::Transfer {
+                        recipient: to.into(),
+                        amount: c.amount,
+                    },
                 };
                 let exec = CosmosMsg::from(WasmMsg::Execute {
                     contract_addr: c.address.to_string(),
@@ -240,11 +1275,17 @@ impl GenericBalance {
             .nfts
             .iter()
             .map(|n| {
-                // Contract must implement the cw721 receiver interface, replace x with contract specific message variant
-                let msg = Cw721ExecuteMsg::SendNft {
-                    contract: to.into(),
-                    token_id: n.token_id.clone(),
-                    msg: to_binary("x")?,
+                // Contract must implement the cw721 receiver interface to take a hook msg
+                let msg = match nft_msg {
+                    Some(hook) => Cw721ExecuteMsg::SendNft {
+                        contract: to.into(),
+                        token_id: n.token_id.clone(),
+                        msg: hook.clone(),
+                    },
+                    None => Cw721ExecuteMsg::TransferNft {
+                        recipient: to.into(),
+                        token_id: n.token_id.clone(),
+                    },
                 };
                 let exec = CosmosMsg::from(WasmMsg::Execute {
                     contract_addr: n.contract_address.to_string(),
@@ -383,11 +1424,20 @@ impl GenericBalance {
         self.nfts.push(nft);
     }
 
+    /// Adds every token in `add`, same as `add_tokens` & `add_nft`
+    pub fn add_balance(&mut self, add: GenericBalance) {
+        self.add_tokens(Balance::from(add.native));
+        for cw20 in add.cw20 {
+            self.add_tokens(Balance::Cw20(cw20));
+        }
+        self.nfts.extend(add.nfts);
+    }
+
     /// Errors if any are true:
     /// - Any Native or CW20 token amount is 0
-    /// - Number of Natives, CW20's, and NFTs are over MAX_NUM_ASSETS
+    /// - Number of Natives, CW20's, and NFTs are over `max_num_assets`
     /// - Any duplicate Native Denom, Cw20 contract_addr, or NFT (contract_addr + token_id)
-    pub fn check_valid(&self) -> Result<(), ContractError> {
+    pub fn check_valid(&self, max_num_assets: u32) -> Result<(), ContractError> {
 
         // Check Natives for 0's
         if self.native.iter().any(|n| n.amount.is_zero()) {
@@ -399,15 +1449,15 @@ impl GenericBalance {
             return Err(ContractError::GenericError("Cannot contain 0 value amounts".to_string()));
         }
 
-        // Validate number of assets (MAX_NUM_ASSETS is to avoid out of gas problems)
-        let _ = self
+        // Validate number of assets (max_num_assets is to avoid out of gas problems)
+        self
             .native.len()
             .checked_add(self.cw20.len())
             .and_then(|v| v.checked_add(self.nfts.len()))
-            .ok_or_else(|| ContractError::GenericError(format!("Listing cannot contain over {} items", MAX_NUM_ASSETS)))
+            .ok_or_else(|| ContractError::GenericError(format!("Listing cannot contain over {} items", max_num_assets)))
             .and_then(|v| {
-                if v == 0 || v as u32 > MAX_NUM_ASSETS {
-                    return Err(ContractError::GenericError(format!("Number of items must be between 1 and {}", MAX_NUM_ASSETS)));
+                if v == 0 || v as u32 > max_num_assets {
+                    return Err(ContractError::GenericError(format!("Number of items must be between 1 and {}", max_num_assets)));
                 }
                 Ok(())
             })?;
@@ -445,12 +1495,13 @@ impl GenericBalance {
     /// 
     /// - Returns `Vec<CosmosMsg>` of Royalty Payments to be sent
     /// - Mutates GenericBalnce in place by subtracting all royalty payments
-    pub fn royalties(&mut self, royalty_responses: Vec<Option<RoyaltyInfo>>) -> Result<(Vec<CosmosMsg>, u64), ContractError> {
+    /// - Errors if the sum of royalties is over `max_royalty_bps`
+    pub fn royalties(&mut self, royalty_responses: Vec<Option<RoyaltyInfo>>, max_royalty_bps: u64) -> Result<(Vec<CosmosMsg>, u64), ContractError> {
 
         // - Sum the BPS of all royalties contained in the generic balance (1 = 0.01%)
         // - Remove collections without royalties
         let (sum_royalties, all_royalties): (u64, Vec<RoyaltyInfo>) = royalty_responses.into_iter()
-            .filter_map(|r| r)
+            .flatten()
             .fold((0, vec![]), |(acc, mut vec), royalty| {
                 (acc + royalty.bps, {
                     vec.push(royalty);
@@ -459,19 +1510,22 @@ impl GenericBalance {
             });
 
         // 100 = 1%  |  5_000 = 50%
-        // If royalties are greater than 50% (at 3% cap would require min. 17 different NFT collections), 
+        // If royalties are greater than max (at 3% cap, 50% would require min. 17 different NFT collections),
         // fail transaction & provide helpful error message. this seems like an acceptable solution for now
-        if sum_royalties > 5000 {
-            return Err(ContractError::GenericError("50% Royalty Max hit, try a Listing with fewer NFTs".to_string()));
+        if sum_royalties > max_royalty_bps {
+            return Err(ContractError::GenericError(format!(
+                "{}% Royalty Max hit, try a Listing with fewer NFTs",
+                Decimal::from_ratio(max_royalty_bps, 100u64)
+            )));
         }
 
         let mut cosmos_msgs: Vec<CosmosMsg> = vec![];
 
         for native_balance in self.native.iter_mut() {
 
-            let original_balance = native_balance.amount.clone();
+            let original_balance = native_balance.amount;
 
-            let mut new_balance = native_balance.amount.clone();
+            let mut new_balance = native_balance.amount;
 
             for royalty in all_royalties.iter() {
                 // Calculate royalty amount (1 bip = 0.01%)
@@ -501,9 +1555,9 @@ impl GenericBalance {
 
         for cw20_balance in self.cw20.iter_mut() {
 
-            let original_balance = cw20_balance.amount.clone();
+            let original_balance = cw20_balance.amount;
 
-            let mut new_balance = cw20_balance.amount.clone();
+            let mut new_balance = cw20_balance.amount;
 
             for royalty in all_royalties.iter() {
                 // Calculate royalty amt (1 bip = 0.01%)
@@ -647,6 +1701,268 @@ impl GetComPoolMsg for Coin {
     }
 }
 
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Fee Routing
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Max number of fee routes
+pub const MAX_FEE_ROUTES: usize = 10;
+
+/// How fees sent to the Community Pool are funded
+#[cw_serde]
+pub enum CommunityPoolMsg {
+    /// `MsgFundCommunityPool` sent as a Stargate message
+    Stargate,
+    /// `DistributionMsg::FundCommunityPool`
+    /// - Requires the `cosmwasm_1_3` feature
+    Distribution,
+}
+
+/// Where a withdrawal is sent, instead of the withdrawer's wallet
+#[cw_serde]
+pub enum WithdrawDestination {
+    /// Sent with `BankMsg::Send`, `Cw20ExecuteMsg::Transfer` & `Cw721ExecuteMsg::TransferNft`
+    Address(Addr),
+    /// Natives are sent with `BankMsg::Send`, CW20s with `Cw20ExecuteMsg::Send` & `cw20_msg`,
+    /// NFTs with `Cw721ExecuteMsg::SendNft` & `nft_msg`
+    /// - CW20s / NFTs without a hook msg are transferred without one
+    Contract {
+        contract_addr: Addr,
+        cw20_msg: Option<Binary>,
+        nft_msg: Option<Binary>,
+    },
+}
+
+impl WithdrawDestination {
+    /// Messages sending all of `balance` here
+    pub fn msgs(&self, balance: &GenericBalance) -> StdResult<Vec<CosmosMsg>> {
+        match self {
+            WithdrawDestination::Address(addr) => send_tokens_cosmos(addr, balance),
+            WithdrawDestination::Contract {
+                contract_addr,
+                cw20_msg,
+                nft_msg,
+            } => balance.contract_msgs(contract_addr, cw20_msg.as_ref(), nft_msg.as_ref()),
+        }
+    }
+}
+
+#[cw_serde]
+pub enum FeeDestination {
+    CommunityPool(CommunityPoolMsg),
+    /// Fee is sent with `BankMsg::Send` (or `Cw20ExecuteMsg::Transfer`)
+    Address(Addr),
+    /// `msg` is executed on the contract with the fee attached as funds
+    /// - CW20 fees are sent with `Cw20ExecuteMsg::Send` & `msg`
+    Contract {
+        contract_addr: Addr,
+        msg: Binary,
+    },
+}
+
+/// Share of every fee sent to `destination` (100 = 1%)
+#[cw_serde]
+pub struct FeeRoute {
+    pub destination: FeeDestination,
+    pub bps: u64,
+}
+
+impl FeeRoute {
+    /// 100% of fees to the Community Pool (via Stargate)
+    pub fn default_routes() -> Vec<FeeRoute> {
+        vec![FeeRoute {
+            destination: FeeDestination::CommunityPool(CommunityPoolMsg::Stargate),
+            bps: 10_000,
+        }]
+    }
+
+    /// Errors if any are true:
+    /// - No routes or over MAX_FEE_ROUTES routes
+    /// - Any route is 0 bps
+    /// - bps of all routes does not sum to 10_000
+    /// - CommunityPoolMsg::Distribution is used without the `cosmwasm_1_3` feature
+    pub fn check_valid(routes: &[FeeRoute]) -> Result<(), ContractError> {
+        if routes.is_empty() || routes.len() > MAX_FEE_ROUTES {
+            return Err(ContractError::GenericError(format!(
+                "Number of fee routes must be between 1 and {MAX_FEE_ROUTES}"
+            )));
+        }
+
+        if routes.iter().any(|r| r.bps == 0) {
+            return Err(ContractError::GenericError("Fee route cannot be 0 bps".to_string()));
+        }
+
+        let total = routes.iter().try_fold(0u64, |acc, r| acc.checked_add(r.bps));
+        if total != Some(10_000) {
+            return Err(ContractError::GenericError("Fee route bps must sum to 10000".to_string()));
+        }
+
+        if cfg!(not(feature = "cosmwasm_1_3"))
+            && routes
+                .iter()
+                .any(|r| r.destination == FeeDestination::CommunityPool(CommunityPoolMsg::Distribution))
+        {
+            return Err(ContractError::GenericError(
+                "Distribution community pool requires the cosmwasm_1_3 feature".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn msg(&self, fee: FeeCoin, contract_addr: &Addr) -> Result<CosmosMsg, ContractError> {
+        let FeeAsset::Native(denom) = &fee.asset else {
+            return self.cw20_msg(fee);
+        };
+        let amount = coin(fee.amount.u128(), denom);
+
+        match &self.destination {
+            FeeDestination::CommunityPool(CommunityPoolMsg::Stargate) => amount.get_cp_msg(contract_addr.clone()),
+            FeeDestination::CommunityPool(CommunityPoolMsg::Distribution) => community_pool_distribution_msg(amount),
+            FeeDestination::Address(addr) => Ok(CosmosMsg::Bank(BankMsg::Send {
+                to_address: addr.to_string(),
+                amount: vec![amount],
+            })),
+            FeeDestination::Contract {
+                contract_addr,
+                msg,
+            } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                msg: msg.clone(),
+                funds: vec![amount],
+            })),
+        }
+    }
+
+    fn cw20_msg(&self, fee: FeeCoin) -> Result<CosmosMsg, ContractError> {
+        let cw20_msg = match &self.destination {
+            FeeDestination::CommunityPool(_) => {
+                return Err(ContractError::GenericError(
+                    "CW20 fees cannot be routed to the Community Pool".to_string(),
+                ))
+            }
+            FeeDestination::Address(addr) => Cw20ExecuteMsg::Transfer {
+                recipient: addr.to_string(),
+                amount: fee.amount,
+            },
+            FeeDestination::Contract {
+                contract_addr,
+                msg,
+            } => Cw20ExecuteMsg::Send {
+                contract: contract_addr.to_string(),
+                amount: fee.amount,
+                msg: msg.clone(),
+            },
+        };
+
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: fee.asset.key().to_string(),
+            msg: to_binary(&cw20_msg)?,
+            funds: vec![],
+        }))
+    }
+}
+
+#[cfg(feature = "cosmwasm_1_3")]
+fn community_pool_distribution_msg(amount: Coin) -> Result<CosmosMsg, ContractError> {
+    Ok(CosmosMsg::Distribution(cosmwasm_std::DistributionMsg::FundCommunityPool {
+        amount: vec![amount],
+    }))
+}
+
+#[cfg(not(feature = "cosmwasm_1_3"))]
+fn community_pool_distribution_msg(_amount: Coin) -> Result<CosmosMsg, ContractError> {
+    Err(ContractError::GenericError(
+        "Distribution community pool requires the cosmwasm_1_3 feature".to_string(),
+    ))
+}
+
+/// Splits `fee` across `routes` by bps
+/// - Rounding dust is added to the first route
+/// - Routes with a 0 amount are skipped
+pub fn fee_route_msgs(fee: &FeeCoin, routes: &[FeeRoute], contract_addr: &Addr) -> Result<Vec<CosmosMsg>, ContractError> {
+    let shares = routes
+        .iter()
+        .map(|r| fee.amount.checked_multiply_ratio(r.bps, 10_000u64))
+        .collect::<Result<Vec<Uint128>, _>>()
+        .map_err(|_e| ContractError::FeeCalc)?;
+
+    let dust = shares
+        .iter()
+        .try_fold(Uint128::zero(), |acc, s| acc.checked_add(*s))
+        .and_then(|distributed| fee.amount.checked_sub(distributed))
+        .map_err(|_e| ContractError::FeeCalc)?;
+
+    routes
+        .iter()
+        .zip(shares)
+        .enumerate()
+        .map(|(i, (route, share))| if i == 0 { (route, share + dust) } else { (route, share) })
+        .filter(|(_, share)| !share.is_zero())
+        .map(|(route, share)| route.msg(FeeCoin::new(share, fee.asset.clone()), contract_addr))
+        .collect()
+}
+
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// Fee Ledger
+//~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Fees taken in sales, keyed by `FeeAsset::key` (native denom or CW20 address)
+/// - Credited when a sale settles, sent out across `FEE_ROUTES` by SweepFees
+pub const FEE_LEDGER: Map<&str, FeeTotals> = Map::new("fee_ledger");
+
+#[cw_serde]
+pub struct FeeTotals {
+    pub asset: FeeAsset,
+    /// Taken but not yet swept
+    pub pending: Uint128,
+    /// Taken since the ledger was created
+    pub lifetime: Uint128,
+}
+
+/// Credits `fee` to the Fee Ledger as both pending and lifetime
+pub fn accrue_fee(storage: &mut dyn Storage, fee: &FeeCoin) -> Result<(), ContractError> {
+    if fee.amount.is_zero() {
+        return Ok(());
+    }
+
+    let mut totals = FEE_LEDGER.may_load(storage, fee.asset.key())?.unwrap_or(FeeTotals {
+        asset: fee.asset.clone(),
+        pending: Uint128::zero(),
+        lifetime: Uint128::zero(),
+    });
+
+    totals.pending = totals.pending.checked_add(fee.amount).map_err(|_e| ContractError::FeeCalc)?;
+    totals.lifetime = totals.lifetime.checked_add(fee.amount).map_err(|_e| ContractError::FeeCalc)?;
+
+    FEE_LEDGER.save(storage, fee.asset.key(), &totals)?;
+
+    Ok(())
+}
+
+/// CW20s can't be sent to the Community Pool, so errors if a CW20 fee is
+/// pending in the Fee Ledger & any route is to the Community Pool
+/// - Fees taken under an earlier fee schedule are still swept across `routes`
+pub fn check_pending_routes(storage: &dyn Storage, routes: &[FeeRoute]) -> Result<(), ContractError> {
+    if !routes.iter().any(|r| matches!(r.destination, FeeDestination::CommunityPool(_))) {
+        return Ok(());
+    }
+
+    for entry in FEE_LEDGER.range(storage, None, None, Order::Ascending) {
+        let (_key, totals) = entry?;
+        if matches!(totals.asset, FeeAsset::Cw20(_)) && !totals.pending.is_zero() {
+            return Err(ContractError::GenericError(format!(
+                "Pending CW20 fees ({}) cannot be routed to the Community Pool, sweep them first",
+                totals.asset.key()
+            )));
+        }
+    }
+
+    Ok(())
+}
+
 //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // Tests
@@ -934,6 +2250,171 @@ mod state_tests {
             genbal_cmp(&gen_bal_main, &gen_bal_mph).expect_err(&here("nft", line!(), column!()));
     }
 
+    fn route(destination: FeeDestination, bps: u64) -> FeeRoute {
+        FeeRoute {
+            destination,
+            bps,
+        }
+    }
+
+    #[test]
+    fn fee_route_split() {
+        let contract = Addr::unchecked("market");
+        let treasury = Addr::unchecked("treasury");
+        let staking = Addr::unchecked("staking");
+
+        // 10% Community Pool | 60% Treasury | 30% Staking
+        let routes = vec![
+            route(FeeDestination::CommunityPool(CommunityPoolMsg::Stargate), 1_000),
+            route(FeeDestination::Address(treasury.clone()), 6_000),
+            route(
+                FeeDestination::Contract {
+                    contract_addr: staking.clone(),
+                    msg: Binary::from(b"{}".to_vec()),
+                },
+                3_000,
+            ),
+        ];
+        FeeRoute::check_valid(&routes).unwrap_or_else(|_| panic!("{}", here("valid", line!(), column!())));
+
+        // 1001 -> 100 (+1 dust) | 600 | 300
+        let juno = FeeAsset::Native("ujunox".to_string());
+        let msgs = fee_route_msgs(&FeeCoin::new(1_001u128, juno.clone()), &routes, &contract)
+            .unwrap_or_else(|_| panic!("{}", here("split", line!(), column!())));
+
+        assert_eq!(msgs.len(), 3);
+        assert_eq!(msgs[0], coin(101, "ujunox").get_cp_msg(contract.clone()).unwrap());
+        assert_eq!(
+            msgs[1],
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: treasury.to_string(),
+                amount: vec![coin(600, "ujunox")]
+            })
+        );
+        assert_eq!(
+            msgs[2],
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: staking.to_string(),
+                msg: Binary::from(b"{}".to_vec()),
+                funds: vec![coin(300, "ujunox")]
+            })
+        );
+
+        // 3 -> 0 (+2 dust) | 1 | 0, 0 amount routes are skipped
+        let msgs = fee_route_msgs(&FeeCoin::new(3u128, juno), &routes, &contract)
+            .unwrap_or_else(|_| panic!("{}", here("split", line!(), column!())));
+        assert_eq!(msgs.len(), 2);
+    }
+
+    #[test]
+    fn cw20_fee_route_split() {
+        let contract = Addr::unchecked("market");
+        let token = FeeAsset::Cw20(Addr::unchecked("token"));
+        let treasury = Addr::unchecked("treasury");
+        let staking = Addr::unchecked("staking");
+
+        // 60% Treasury | 40% Staking
+        let routes = vec![
+            route(FeeDestination::Address(treasury.clone()), 6_000),
+            route(
+                FeeDestination::Contract {
+                    contract_addr: staking.clone(),
+                    msg: Binary::from(b"{}".to_vec()),
+                },
+                4_000,
+            ),
+        ];
+
+        let msgs = fee_route_msgs(&FeeCoin::new(100u128, token.clone()), &routes, &contract)
+            .unwrap_or_else(|_| panic!("{}", here("split", line!(), column!())));
+
+        let cw20_exec = |msg: Cw20ExecuteMsg| CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "token".to_string(),
+            msg: to_binary(&msg).unwrap(),
+            funds: vec![],
+        });
+        assert_eq!(
+            msgs,
+            vec![
+                cw20_exec(Cw20ExecuteMsg::Transfer {
+                    recipient: treasury.to_string(),
+                    amount: Uint128::from(60u32),
+                }),
+                cw20_exec(Cw20ExecuteMsg::Send {
+                    contract: staking.to_string(),
+                    amount: Uint128::from(40u32),
+                    msg: Binary::from(b"{}".to_vec()),
+                }),
+            ]
+        );
+
+        // CW20s can't go to the Community Pool
+        let cp = [route(FeeDestination::CommunityPool(CommunityPoolMsg::Stargate), 10_000)];
+        assert!(fee_route_msgs(&FeeCoin::new(100u128, token), &cp, &contract).is_err());
+    }
 
+    #[test]
+    fn invalid_fee_routes() {
+        let treasury = FeeDestination::Address(Addr::unchecked("treasury"));
+
+        // Empty
+        assert!(FeeRoute::check_valid(&[]).is_err());
+        // Under 100%
+        assert!(FeeRoute::check_valid(&[route(treasury.clone(), 9_999)]).is_err());
+        // Over 100%
+        assert!(FeeRoute::check_valid(&[route(treasury.clone(), 10_000), route(treasury.clone(), 1)]).is_err());
+        // 0 bps route
+        assert!(FeeRoute::check_valid(&[route(treasury.clone(), 10_000), route(treasury, 0)]).is_err());
+
+        // Distribution requires cosmwasm_1_3
+        let distribution = [route(FeeDestination::CommunityPool(CommunityPoolMsg::Distribution), 10_000)];
+        assert_eq!(FeeRoute::check_valid(&distribution).is_ok(), cfg!(feature = "cosmwasm_1_3"));
+    }
 
+    #[test]
+    fn cw20_fees_and_community_pool() {
+        let token = FeeAsset::Cw20(Addr::unchecked("token"));
+        let cp = [route(FeeDestination::CommunityPool(CommunityPoolMsg::Stargate), 10_000)];
+        let treasury = [route(FeeDestination::Address(Addr::unchecked("treasury")), 10_000)];
+
+        // CW20 flat fee on a native schedule
+        let schedule = FeeSchedule {
+            denoms: vec![FeeDenom {
+                asset: FeeAsset::Native("ujunox".to_string()),
+                fee_bps: None,
+            }],
+            mode: FeeMode::Rotation,
+            current: 0,
+            last_rotation: 0,
+            any_fungible: false,
+            flat_fee: Some(FlatFee {
+                amount: FeeCoin::new(100u128, token.clone()),
+                mode: FlatFeeMode::Always,
+            }),
+        };
+        assert!(schedule.takes_cw20());
+        assert!(schedule.check_routes(&cp).is_err());
+        assert!(FeeSchedule { flat_fee: None, ..schedule }.check_routes(&cp).is_ok());
+
+        // Pending CW20 fee in the Fee Ledger
+        let mut storage = cosmwasm_std::testing::MockStorage::new();
+        accrue_fee(&mut storage, &FeeCoin::new(100u128, FeeAsset::Native("ujunox".to_string())))
+            .unwrap();
+        assert!(check_pending_routes(&storage, &cp).is_ok());
+
+        accrue_fee(&mut storage, &FeeCoin::new(100u128, token.clone())).unwrap();
+        assert!(check_pending_routes(&storage, &cp).is_err());
+        assert!(check_pending_routes(&storage, &treasury).is_ok());
+
+        // Swept, nothing pending
+        FEE_LEDGER
+            .update(&mut storage, token.key(), |totals| -> StdResult<_> {
+                Ok(FeeTotals {
+                    pending: Uint128::zero(),
+                    ..totals.unwrap()
+                })
+            })
+            .unwrap();
+        assert!(check_pending_routes(&storage, &cp).is_ok());
+    }
 }
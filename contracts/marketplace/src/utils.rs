@@ -77,65 +77,70 @@ pub fn send_tokens_cosmos(to: &Addr, balance: &GenericBalance) -> StdResult<Vec<
     Ok(msgs)
 }
 
-/// Accepts current FeeDenom & GenericBalance, returns one of the following
+/// Accepts current FeeSchedule, default fee rate & GenericBalance, returns one of the following
 ///
 ///
-/// **If FeeDenom is not in GenericBalance || fifty_pips floored is zero**
+/// **If no scheduled FeeDenom is in GenericBalance || fee floored is zero**
 /// - Returns Fee Coin as None +
 /// - Returns Balance unchanged
 /// - `Ok((None, GenericBalance))`
 ///
 ///
-/// **If FeeDenom is in Balance && fifty_pips floored is not zero**
-/// - Returns FeeCoin as fifty_pips of Fee Denom in Balance
+/// **If a scheduled FeeDenom is in Balance && fee floored is not zero**
+/// - Returns FeeCoin as the denom's rate (or `default_fee_bps`) of Fee Denom in Balance
 /// - Returns Balance as Balance - FeeCoin
-/// - Returns Ok((Some(Coin), Balance))
+/// - Returns Ok((Some(FeeCoin), Balance))
 ///
 ///
 /// **Returns StdError on int overflow**
 pub fn calc_fee_coin(
-    fee_type: &FeeDenom,
+    fee_schedule: &FeeSchedule,
+    default_fee_bps: u64,
     balance: &GenericBalance,
-) -> StdResult<(Option<Coin>, GenericBalance)> {
-    // Get the current fee denom to check for
-    let fee_denom = fee_type.value();
-
-    // Find the fee denom in balance
-    let fee_in_balance = balance.native.iter().find(|n| n.denom == fee_denom);
-
-    // Calculate Fee Amount & return
-    match fee_in_balance {
-        // If fee_denom not found, return (None, balance)
-        None => Ok((None, balance.to_owned())),
-
-        // If fee_denom found, calculate fee coin
-        Some(fee) => {
-            // Calc 0.5% of fee_denom found
-            let fifty_pips = fee.amount.multiply_ratio(5_u128, 1000_u128);
-
-            // small amounts (like 1ujuno) will be 0, so return None
-            if fifty_pips.is_zero() {
-                return Ok((None, balance.to_owned()));
-            }
+) -> StdResult<(Option<FeeCoin>, GenericBalance)> {
+    // Get the fee denom charged on this balance
+    let Some(fee_type) = fee_schedule.fee_denom_for(balance) else {
+        return Ok((None, balance.to_owned()));
+    };
+    let fee_bps = fee_type.rate(default_fee_bps);
+
+    // Find the fee denom (native or cw20) in balance
+    let Some(amount_in_balance) = fee_type.asset.amount_in(balance) else {
+        return Ok((None, balance.to_owned()));
+    };
 
-            // Create Fee Coin
-            let fee_coin = coin(fifty_pips.u128(), fee_denom.clone());
+    // Calc fee_bps of fee denom found (1 bip = 0.01%)
+    let fee_amount = amount_in_balance.multiply_ratio(fee_bps, 10_000_u128);
 
-            // Subtract fee amount from the fee coin found in balance
-            let amount_sub_fee = fee.amount.checked_sub(fifty_pips)?;
+    // small amounts (like 1ujuno) will be 0, so return None
+    if fee_amount.is_zero() {
+        return Ok((None, balance.to_owned()));
+    }
 
-            // Create GenericBalance with fee amount removed
-            let balance_with_fee_removed = {
-                let mut x = balance.clone();
-                x.native.retain(|n| n.denom != fee_denom);
-                x.native.append(&mut coins(amount_sub_fee.u128(), fee_denom));
-                x
-            };
+    // Subtract fee amount from the fee denom found in balance
+    let amount_sub_fee = amount_in_balance.checked_sub(fee_amount)?;
 
-            // Return (Fee, Balance_minus_fee)
-            Ok((Some(fee_coin), balance_with_fee_removed))
+    // Create GenericBalance with fee amount removed
+    let balance_with_fee_removed = {
+        let mut x = balance.clone();
+        match &fee_type.asset {
+            FeeAsset::Native(denom) => {
+                x.native.retain(|n| &n.denom != denom);
+                x.native.append(&mut coins(amount_sub_fee.u128(), denom));
+            }
+            FeeAsset::Cw20(address) => {
+                x.cw20.retain(|c| c.address != address);
+                x.cw20.push(Cw20CoinVerified {
+                    address: address.clone(),
+                    amount: amount_sub_fee,
+                });
+            }
         }
-    }
+        x
+    };
+
+    // Return (Fee, Balance_minus_fee)
+    Ok((Some(FeeCoin::new(fee_amount, fee_type.asset)), balance_with_fee_removed))
 }
 
 #[cfg(test)]
@@ -147,6 +152,8 @@ mod utils_tests {
     use cw20::Cw20CoinVerified;
     use std::fmt::Display;
 
+    const MAX_FLAT: Uint128 = Uint128::new(1_000_000);
+
     fn here(ctx: impl Display, line: impl Display, col: impl Display) -> String {
         format!(
             "~~~~~~~~~~~~~~~~~~~ \n \n {} \n line {} | column {} \n ________________________",
@@ -176,6 +183,25 @@ mod utils_tests {
         vec![nft("boredcats", "30"), nft("dogs", "31"), nft("sharks", "32")]
     }
 
+    fn fee_denom(denom: &str, fee_bps: Option<u64>) -> FeeDenom {
+        FeeDenom {
+            asset: FeeAsset::Native(denom.to_string()),
+            fee_bps,
+        }
+    }
+
+    // ujunox <> uusdcx, starting at `current`
+    fn rotation(current: u32) -> FeeSchedule {
+        FeeSchedule {
+            denoms: vec![fee_denom("ujunox", None), fee_denom("uusdcx", None)],
+            mode: FeeMode::Rotation,
+            current,
+            last_rotation: 10,
+            any_fungible: false,
+            flat_fee: None,
+        }
+    }
+
     //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Testing calc_fee_coin math
     //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -187,7 +213,7 @@ mod utils_tests {
 
         let native = vec![coin(200, "uatom"), coin(300, "uosmo")];
         // ujunox
-        let juno_fee_denom = FeeDenom::JUNO(10);
+        let juno_fee_denom = rotation(0);
 
         let gbal: GenericBalance = GenericBalance {
             native,
@@ -196,7 +222,7 @@ mod utils_tests {
         };
 
         let (fee_coin, new_gbal) =
-            calc_fee_coin(&juno_fee_denom, &gbal).expect(&here("y", line!(), column!()));
+            calc_fee_coin(&juno_fee_denom, 50, &gbal).unwrap_or_else(|_| panic!("{}", here("y", line!(), column!())));
 
         //fee_coin should be none
         if fee_coin.is_some() {
@@ -208,10 +234,10 @@ mod utils_tests {
             .unwrap_or_else(|_| panic!("{}", here("Should be equal", line!(), column!(),)));
 
         // uusdcx
-        let usdc_fee_denom = FeeDenom::USDC(10);
+        let usdc_fee_denom = rotation(1);
 
         let (fee_coinx, new_gbalx) =
-            calc_fee_coin(&usdc_fee_denom, &gbal).expect(&here("y", line!(), column!()));
+            calc_fee_coin(&usdc_fee_denom, 50, &gbal).unwrap_or_else(|_| panic!("{}", here("y", line!(), column!())));
 
         //fee_coin should be none
         if fee_coinx.is_some() {
@@ -231,7 +257,7 @@ mod utils_tests {
         let native = vec![coin(200, "uatom"), coin(300, "uosmo"), coin(1000, "ujunox")];
 
         // ujunox
-        let juno_fee_denom = FeeDenom::JUNO(10);
+        let juno_fee_denom = rotation(0);
 
         let gbal: GenericBalance = GenericBalance {
             native,
@@ -240,10 +266,10 @@ mod utils_tests {
         };
 
         let (fee_coin, new_gbal) =
-            calc_fee_coin(&juno_fee_denom, &gbal).expect(&here("y", line!(), column!()));
+            calc_fee_coin(&juno_fee_denom, 50, &gbal).unwrap_or_else(|_| panic!("{}", here("y", line!(), column!())));
 
         //fee_coin should be Some(5 ujunox)
-        assert_eq!(Some(coin(5, "ujunox")), fee_coin, "Juno fee incorrect: {}", line!());
+        assert_eq!(Some(coin(5, "ujunox").into()), fee_coin, "Juno fee incorrect: {}", line!());
 
         // new_gbal should have everything the same, except 995 ujunox
         let nativex = vec![coin(200, "uatom"), coin(300, "uosmo"), coin(995, "ujunox")];
@@ -263,7 +289,7 @@ mod utils_tests {
 
         let native = vec![coin(200, "uatom"), coin(300, "uosmo"), coin(1000, "uusdcx")];
 
-        let usdc_fee_denom = FeeDenom::USDC(10);
+        let usdc_fee_denom = rotation(1);
 
         let gbal: GenericBalance = GenericBalance {
             native,
@@ -272,10 +298,10 @@ mod utils_tests {
         };
 
         let (fee_coin, new_gbal) =
-            calc_fee_coin(&usdc_fee_denom, &gbal).expect(&here("y", line!(), column!()));
+            calc_fee_coin(&usdc_fee_denom, 50, &gbal).unwrap_or_else(|_| panic!("{}", here("y", line!(), column!())));
 
         //fee_coin should be Some(5 ujunox)
-        assert_eq!(Some(coin(5, "uusdcx")), fee_coin, "USDC fee incorrect: {}", line!());
+        assert_eq!(Some(coin(5, "uusdcx").into()), fee_coin, "USDC fee incorrect: {}", line!());
 
         // new_gbal should have everything the same, except 995 ujunox
         let nativex = vec![coin(200, "uatom"), coin(300, "uosmo"), coin(995, "uusdcx")];
@@ -297,7 +323,7 @@ mod utils_tests {
         let native = vec![coin(200, "uatom"), coin(300, "uosmo"), coin(999, "ujunox")];
 
         // ujunox
-        let juno_fee_denom = FeeDenom::JUNO(10);
+        let juno_fee_denom = rotation(0);
 
         let gbal: GenericBalance = GenericBalance {
             native,
@@ -306,10 +332,10 @@ mod utils_tests {
         };
 
         let (fee_coin, new_gbal) =
-            calc_fee_coin(&juno_fee_denom, &gbal).expect(&here("y", line!(), column!()));
+            calc_fee_coin(&juno_fee_denom, 50, &gbal).unwrap_or_else(|_| panic!("{}", here("y", line!(), column!())));
 
         //fee_coin should be Some(4 ujunox)
-        assert_eq!(Some(coin(4, "ujunox")), fee_coin, "Juno fee incorrect: {}", line!());
+        assert_eq!(Some(coin(4, "ujunox").into()), fee_coin, "Juno fee incorrect: {}", line!());
 
         // new_gbal should have everything the same, except 995 ujunox
         let nativex = vec![coin(200, "uatom"), coin(300, "uosmo"), coin(995, "ujunox")];
@@ -331,7 +357,7 @@ mod utils_tests {
         let native = vec![coin(200, "uatom"), coin(300, "uosmo"), coin(999, "uusdcx")];
 
         // ujunox
-        let usdc_fee_denom = FeeDenom::USDC(10);
+        let usdc_fee_denom = rotation(1);
 
         let gbal: GenericBalance = GenericBalance {
             native,
@@ -340,10 +366,10 @@ mod utils_tests {
         };
 
         let (fee_coin, new_gbal) =
-            calc_fee_coin(&usdc_fee_denom, &gbal).expect(&here("y", line!(), column!()));
+            calc_fee_coin(&usdc_fee_denom, 50, &gbal).unwrap_or_else(|_| panic!("{}", here("y", line!(), column!())));
 
         //fee_coin should be Some(4 uusdcx)
-        assert_eq!(Some(coin(4, "uusdcx")), fee_coin, "USDC fee incorrect: {}", line!());
+        assert_eq!(Some(coin(4, "uusdcx").into()), fee_coin, "USDC fee incorrect: {}", line!());
 
         // new_gbal should have everything the same, except 995 uusdcx
         let nativex = vec![coin(200, "uatom"), coin(300, "uosmo"), coin(995, "uusdcx")];
@@ -364,7 +390,7 @@ mod utils_tests {
         let native = vec![coin(200, "uatom"), coin(300, "uosmo"), coin(200, "ujunox")];
 
         // ujunox
-        let juno_fee_denom = FeeDenom::JUNO(10);
+        let juno_fee_denom = rotation(0);
 
         let gbal: GenericBalance = GenericBalance {
             native,
@@ -373,10 +399,10 @@ mod utils_tests {
         };
 
         let (fee_coin, new_gbal) =
-            calc_fee_coin(&juno_fee_denom, &gbal).expect(&here("y", line!(), column!()));
+            calc_fee_coin(&juno_fee_denom, 50, &gbal).unwrap_or_else(|_| panic!("{}", here("y", line!(), column!())));
 
         //fee_coin should be Some(1 ujunox)
-        assert_eq!(Some(coin(1, "ujunox")), fee_coin, "Juno fee incorrect: {}", line!());
+        assert_eq!(Some(coin(1, "ujunox").into()), fee_coin, "Juno fee incorrect: {}", line!());
 
         // new_gbal should have everything the same, except 199 ujunox
         let nativex = vec![coin(200, "uatom"), coin(300, "uosmo"), coin(199, "ujunox")];
@@ -397,7 +423,7 @@ mod utils_tests {
         let native = vec![coin(200, "uatom"), coin(300, "uosmo"), coin(200, "uusdcx")];
 
         // ujunox
-        let usdc_fee_denom = FeeDenom::USDC(10);
+        let usdc_fee_denom = rotation(1);
 
         let gbal: GenericBalance = GenericBalance {
             native,
@@ -406,10 +432,10 @@ mod utils_tests {
         };
 
         let (fee_coin, new_gbal) =
-            calc_fee_coin(&usdc_fee_denom, &gbal).expect(&here("y", line!(), column!()));
+            calc_fee_coin(&usdc_fee_denom, 50, &gbal).unwrap_or_else(|_| panic!("{}", here("y", line!(), column!())));
 
         //fee_coin should be Some(1 ujunox)
-        assert_eq!(Some(coin(1, "uusdcx")), fee_coin, "USDC fee incorrect: {}", line!());
+        assert_eq!(Some(coin(1, "uusdcx").into()), fee_coin, "USDC fee incorrect: {}", line!());
 
         // new_gbal should have everything the same, except 199 ujunox
         let nativex = vec![coin(200, "uatom"), coin(300, "uosmo"), coin(199, "uusdcx")];
@@ -431,7 +457,7 @@ mod utils_tests {
         let native = vec![coin(200, "uatom"), coin(300, "uosmo"), coin(199, "ujunox")];
 
         // ujunox
-        let juno_fee_denom = FeeDenom::JUNO(10);
+        let juno_fee_denom = rotation(0);
 
         let gbal: GenericBalance = GenericBalance {
             native,
@@ -440,7 +466,7 @@ mod utils_tests {
         };
 
         let (fee_coin, new_gbal) =
-            calc_fee_coin(&juno_fee_denom, &gbal).expect(&here("y", line!(), column!()));
+            calc_fee_coin(&juno_fee_denom, 50, &gbal).unwrap_or_else(|_| panic!("{}", here("y", line!(), column!())));
 
         //fee_coin should be none
         if fee_coin.is_some() {
@@ -461,7 +487,7 @@ mod utils_tests {
         let native = vec![coin(200, "uatom"), coin(300, "uosmo"), coin(199, "uusdcx")];
 
         // uusdcx
-        let usdc_fee_denom = FeeDenom::USDC(10);
+        let usdc_fee_denom = rotation(1);
 
         let gbal: GenericBalance = GenericBalance {
             native,
@@ -470,7 +496,7 @@ mod utils_tests {
         };
 
         let (fee_coin, new_gbal) =
-            calc_fee_coin(&usdc_fee_denom, &gbal).expect(&here("y", line!(), column!()));
+            calc_fee_coin(&usdc_fee_denom, 50, &gbal).unwrap_or_else(|_| panic!("{}", here("y", line!(), column!())));
 
         //fee_coin should be none
         if fee_coin.is_some() {
@@ -481,4 +507,214 @@ mod utils_tests {
         genbal_cmp(&new_gbal, &gbal)
             .unwrap_or_else(|_| panic!("{}", here("Should be equal", line!(), column!(),)));
     }
+
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Testing fee schedule modes & rates
+    //~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+    #[test]
+    fn priority_takes_first_denom_found() {
+        // Priority: uatom > ujunox > uosmo
+        // GenericBalance contains ujunox & uosmo, so ujunox is charged
+        let schedule = FeeSchedule::new(
+            vec![fee_denom("uatom", None), fee_denom("ujunox", Some(100)), fee_denom("uosmo", None)],
+            FeeMode::Priority,
+            false,
+            None,
+            MAX_FLAT,
+            10,
+        )
+        .unwrap_or_else(|_| panic!("{}", here("y", line!(), column!())));
+
+        let gbal = GenericBalance {
+            native: vec![coin(300, "uosmo"), coin(1000, "ujunox")],
+            cw20: cw20s(),
+            nfts: nftgen(),
+        };
+
+        let (fee_coin, new_gbal) =
+            calc_fee_coin(&schedule, 50, &gbal).unwrap_or_else(|_| panic!("{}", here("y", line!(), column!())));
+
+        // ujunox rate is 1%
+        assert_eq!(Some(coin(10, "ujunox").into()), fee_coin, "Priority fee incorrect: {}", line!());
+
+        let test = GenericBalance {
+            native: vec![coin(300, "uosmo"), coin(990, "ujunox")],
+            cw20: cw20s(),
+            nfts: nftgen(),
+        };
+        genbal_cmp(&new_gbal, &test)
+            .unwrap_or_else(|_| panic!("{}", here("Should be equal", line!(), column!())));
+    }
+
+    #[test]
+    fn rotation_only_takes_current_denom() {
+        // Current denom is uusdcx, GenericBalance only contains ujunox
+        let gbal = GenericBalance {
+            native: vec![coin(1000, "ujunox")],
+            cw20: vec![],
+            nfts: vec![],
+        };
+
+        let (fee_coin, _) =
+            calc_fee_coin(&rotation(1), 50, &gbal).unwrap_or_else(|_| panic!("{}", here("y", line!(), column!())));
+
+        if fee_coin.is_some() {
+            panic!("{}", here("Fee coin should be none", line!(), column!()));
+        }
+
+        // Rotating wraps back to ujunox
+        let mut schedule = rotation(1);
+        schedule.rotate(20);
+        assert_eq!(schedule.current().map(|d| d.asset.key()), Some("ujunox"));
+
+        let (fee_coin, _) =
+            calc_fee_coin(&schedule, 50, &gbal).unwrap_or_else(|_| panic!("{}", here("y", line!(), column!())));
+        assert_eq!(Some(coin(5, "ujunox").into()), fee_coin, "Rotation fee incorrect: {}", line!());
+    }
+
+    #[test]
+    fn empty_schedule_takes_no_fee() {
+        let schedule = FeeSchedule::new(vec![], FeeMode::Rotation, false, None, MAX_FLAT, 10)
+            .unwrap_or_else(|_| panic!("{}", here("y", line!(), column!())));
+
+        let gbal = GenericBalance {
+            native: vec![coin(1000, "ujunox")],
+            cw20: vec![],
+            nfts: vec![],
+        };
+
+        let (fee_coin, _) =
+            calc_fee_coin(&schedule, 50, &gbal).unwrap_or_else(|_| panic!("{}", here("y", line!(), column!())));
+
+        if fee_coin.is_some() {
+            panic!("{}", here("Fee coin should be none", line!(), column!()));
+        }
+        if schedule.next_rotation(100).is_some() {
+            panic!("{}", here("Empty schedule should not rotate", line!(), column!()));
+        }
+    }
+
+    #[test]
+    fn invalid_fee_schedules() {
+        // Duplicate denom
+        let dupe = vec![fee_denom("ujunox", None), fee_denom("ujunox", Some(10))];
+        assert!(FeeSchedule::new(dupe, FeeMode::Priority, false, None, MAX_FLAT, 10).is_err());
+
+        // Empty denom
+        let empty = vec![fee_denom("", None)];
+        assert!(FeeSchedule::new(empty, FeeMode::Priority, false, None, MAX_FLAT, 10).is_err());
+
+        // Rate over MAX_FEE_BPS
+        let high = vec![fee_denom("ujunox", Some(MAX_FEE_BPS + 1))];
+        assert!(FeeSchedule::new(high, FeeMode::Priority, false, None, MAX_FLAT, 10).is_err());
+
+        // Over MAX_FEE_DENOMS
+        let many = (0..=MAX_FEE_DENOMS).map(|i| fee_denom(&format!("denom{i}"), None)).collect();
+        assert!(FeeSchedule::new(many, FeeMode::Rotation, false, None, MAX_FLAT, 10).is_err());
+
+        // Zero flat fee
+        let zero = FlatFee {
+            amount: coin(0, "ujunox").into(),
+            mode: FlatFeeMode::Always,
+        };
+        let res = FeeSchedule::new(vec![], FeeMode::Rotation, false, Some(zero), MAX_FLAT, 10);
+        assert!(res.is_err());
+
+        // Flat fee over the max
+        let high = FlatFee {
+            amount: coin(MAX_FLAT.u128() + 1, "ujunox").into(),
+            mode: FlatFeeMode::Always,
+        };
+        let res = FeeSchedule::new(vec![], FeeMode::Rotation, false, Some(high), MAX_FLAT, 10);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn any_fungible_falls_back() {
+        // Current denom is uusdcx, GenericBalance contains ujunox & uosmo
+        let gbal = GenericBalance {
+            native: vec![coin(1000, "uosmo"), coin(1000, "ujunox")],
+            cw20: vec![],
+            nfts: vec![],
+        };
+        let mut schedule = rotation(1);
+        schedule.any_fungible = true;
+
+        // ujunox is in the schedule, so it's charged before uosmo
+        let (fee_coin, _) =
+            calc_fee_coin(&schedule, 50, &gbal).unwrap_or_else(|_| panic!("{}", here("y", line!(), column!())));
+        assert_eq!(Some(coin(5, "ujunox").into()), fee_coin, "Schedule fallback incorrect: {}", line!());
+
+        // Nothing in the schedule, so the first native is charged
+        let gbal = GenericBalance {
+            native: vec![coin(2000, "uosmo"), coin(1000, "uatom")],
+            cw20: vec![],
+            nfts: vec![],
+        };
+        let (fee_coin, new_gbal) =
+            calc_fee_coin(&schedule, 50, &gbal).unwrap_or_else(|_| panic!("{}", here("y", line!(), column!())));
+        assert_eq!(Some(coin(10, "uosmo").into()), fee_coin, "Native fallback incorrect: {}", line!());
+
+        let test = GenericBalance {
+            native: vec![coin(1000, "uatom"), coin(1990, "uosmo")],
+            cw20: vec![],
+            nfts: vec![],
+        };
+        genbal_cmp(&new_gbal, &test)
+            .unwrap_or_else(|_| panic!("{}", here("Should be equal", line!(), column!())));
+    }
+
+    #[test]
+    fn cw20_fee_denom() {
+        let schedule = FeeSchedule::new(
+            vec![FeeDenom {
+                asset: FeeAsset::Cw20(Addr::unchecked("tokenA")),
+                fee_bps: Some(100),
+            }],
+            FeeMode::Priority,
+            false,
+            None,
+            MAX_FLAT,
+            10,
+        )
+        .unwrap_or_else(|_| panic!("{}", here("y", line!(), column!())));
+
+        let gbal = GenericBalance {
+            native: vec![coin(1000, "ujunox")],
+            cw20: vec![cw20("foo", 1), cw20("tokenA", 100)],
+            nfts: vec![],
+        };
+
+        let (fee_coin, new_gbal) =
+            calc_fee_coin(&schedule, 50, &gbal).unwrap_or_else(|_| panic!("{}", here("y", line!(), column!())));
+
+        // 1% of 100 tokenA, natives untouched
+        assert_eq!(Some(FeeCoin::new(1u128, FeeAsset::Cw20(Addr::unchecked("tokenA")))), fee_coin);
+        assert_eq!(new_gbal.native, vec![coin(1000, "ujunox")]);
+        assert_eq!(new_gbal.cw20, vec![cw20("foo", 1), cw20("tokenA", 99)]);
+    }
+
+    #[test]
+    fn flat_fee_due() {
+        let always = FlatFee {
+            amount: coin(100, "ujunox").into(),
+            mode: FlatFeeMode::Always,
+        };
+        let minimum = FlatFee {
+            amount: coin(100, "ujunox").into(),
+            mode: FlatFeeMode::Minimum,
+        };
+
+        let juno_fee = Some(FeeCoin::from(coin(30, "ujunox")));
+        let osmo_fee = Some(FeeCoin::from(coin(500, "uosmo")));
+
+        assert_eq!(always.due(&[&juno_fee, &None]), Some(coin(100, "ujunox").into()));
+
+        // Only fees in the same denom count towards the minimum
+        assert_eq!(minimum.due(&[&None, &None]), Some(coin(100, "ujunox").into()));
+        assert_eq!(minimum.due(&[&juno_fee, &osmo_fee]), Some(coin(70, "ujunox").into()));
+        assert_eq!(minimum.due(&[&juno_fee, &Some(coin(70, "ujunox").into())]), None);
+        assert_eq!(minimum.due(&[&Some(coin(1_000, "ujunox").into()), &None]), None);
+    }
 }
@@ -99,7 +99,7 @@ function upload_market {
 
     # == INSTANTIATE ==
     ADMIN="$KEY_ADDR"
-    MARKET_INIT=`printf '{"royalty_code_id":%d}' $ROYALTY_CODE_ID`
+    MARKET_INIT=`printf '{"royalty_code_id":%d,"fee_denoms":[{"asset":{"native":"ujunox"}},{"asset":{"native":"uusdcx"}}],"fee_mode":"rotation"}' $ROYALTY_CODE_ID`
     # Do this after cw721 upload for testing cw721
     MARKET_TX=$($BINARY tx wasm instantiate "$MARKET_BASE_CODE_ID" "$MARKET_INIT" --label "fuzion_market" $JUNOD_COMMAND_ARGS --admin $KEY_ADDR | jq -r '.txhash') && echo $MARKET_TX
 